@@ -0,0 +1,369 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Detection of the base address of raw binaries, such as firmware images.
+//!
+//! ```no_run
+//! use binaryninja::basedetection::{BaseAddressDetection, BaseAddressDetectionSettings};
+//! use binaryninja::metadata::Metadata;
+//! use binaryninja::rc::Ref;
+//! use std::collections::HashMap;
+//!
+//! let headless_session = binaryninja::headless::Session::new();
+//! let bv = headless_session.load("firmware.bin").unwrap();
+//!
+//! let detection = BaseAddressDetection::new(&bv);
+//! let settings = BaseAddressDetectionSettings::default().alignment(0x1000);
+//! detection.detect(&settings).expect("Base address detection failed");
+//! let result = detection.scores(10);
+//!
+//! if let Some(best) = result.scores.first() {
+//!     let options: Ref<Metadata> = HashMap::from([
+//!         ("loader.imageBase", best.base_address.into()),
+//!     ]).into();
+//!     let rebased = headless_session.load_with_options("firmware.bin", true, Some(options));
+//! }
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNBaseAddressDetectionConfidence as BaseAddressDetectionConfidence;
+pub use binaryninjacore_sys::BNBaseAddressDetectionPOISetting as BaseAddressDetectionPOISetting;
+pub use binaryninjacore_sys::BNBaseAddressDetectionPOIType as BaseAddressDetectionPOIType;
+
+use std::ffi::{c_char, CString};
+use std::ptr::NonNull;
+
+use crate::architecture::CoreArchitecture;
+use crate::binaryview::BinaryView;
+use crate::rc::*;
+
+/// Amount of analysis performed on the binary before searching for the base address
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BaseAddressDetectionAnalysis {
+    Basic,
+    ControlFlow,
+    Full,
+}
+
+impl BaseAddressDetectionAnalysis {
+    fn as_raw(&self) -> &'static [u8] {
+        match self {
+            BaseAddressDetectionAnalysis::Basic => b"basic\0",
+            BaseAddressDetectionAnalysis::ControlFlow => b"controlFlow\0",
+            BaseAddressDetectionAnalysis::Full => b"full\0",
+        }
+    }
+}
+
+/// Settings for [BaseAddressDetection::detect], the defaults match the ones used by the UI.
+#[derive(Clone, Debug)]
+pub struct BaseAddressDetectionSettings {
+    arch: Option<CoreArchitecture>,
+    analysis: BaseAddressDetectionAnalysis,
+    min_strlen: u32,
+    alignment: u32,
+    lower_boundary: u64,
+    upper_boundary: u64,
+    poi_analysis: BaseAddressDetectionPOISetting,
+    max_pointers: u32,
+}
+
+impl BaseAddressDetectionSettings {
+    /// CPU architecture of the binary, auto detected if not set
+    pub fn arch(mut self, value: CoreArchitecture) -> Self {
+        self.arch = Some(value);
+        self
+    }
+
+    pub fn analysis(mut self, value: BaseAddressDetectionAnalysis) -> Self {
+        self.analysis = value;
+        self
+    }
+
+    /// Minimum length of a string to be considered a point-of-interest
+    pub fn min_strlen(mut self, value: u32) -> Self {
+        self.min_strlen = value;
+        self
+    }
+
+    /// Byte boundary the candidate base addresses are aligned to, must be non zero
+    pub fn alignment(mut self, value: u32) -> Self {
+        self.alignment = value;
+        self
+    }
+
+    /// Lowest base address to test
+    pub fn low_boundary(mut self, value: u64) -> Self {
+        self.lower_boundary = value;
+        self
+    }
+
+    /// Highest base address to test
+    pub fn high_boundary(mut self, value: u64) -> Self {
+        self.upper_boundary = value;
+        self
+    }
+
+    /// Kind of points-of-interest used to score the candidates
+    pub fn poi_analysis(mut self, value: BaseAddressDetectionPOISetting) -> Self {
+        self.poi_analysis = value;
+        self
+    }
+
+    /// Maximum number of candidate pointers collected per pointer cluster, must be at least 2
+    pub fn max_pointers(mut self, value: u32) -> Self {
+        self.max_pointers = value;
+        self
+    }
+}
+
+impl Default for BaseAddressDetectionSettings {
+    fn default() -> Self {
+        Self {
+            arch: None,
+            analysis: BaseAddressDetectionAnalysis::Full,
+            min_strlen: 10,
+            alignment: 1024,
+            lower_boundary: 0,
+            upper_boundary: u64::MAX,
+            poi_analysis: BaseAddressDetectionPOISetting::POIAnalysisAll,
+            max_pointers: 128,
+        }
+    }
+}
+
+/// A candidate base address and its score, the higher the better
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BaseAddressDetectionScore {
+    pub score: usize,
+    pub base_address: u64,
+}
+
+/// The ranked candidates found by [BaseAddressDetection::detect]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BaseAddressDetectionResult {
+    /// Candidates, sorted from the best to the worst
+    pub scores: Vec<BaseAddressDetectionScore>,
+    pub confidence: BaseAddressDetectionConfidence,
+    pub last_tested_base_address: u64,
+}
+
+/// A pointer that points to a point-of-interest when the binary is loaded at a candidate base address
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BaseAddressDetectionReason {
+    pub pointer: u64,
+    pub poi_offset: u64,
+    pub poi_type: BaseAddressDetectionPOIType,
+}
+
+impl CoreArrayProvider for BaseAddressDetectionReason {
+    type Raw = BNBaseAddressDetectionReason;
+    type Context = ();
+    type Wrapped<'a> = Self;
+}
+
+unsafe impl CoreArrayProviderInner for BaseAddressDetectionReason {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeBaseAddressDetectionReasons(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self {
+            pointer: raw.Pointer,
+            poi_offset: raw.POIOffset,
+            poi_type: raw.POIType,
+        }
+    }
+}
+
+pub struct BaseAddressDetection {
+    handle: NonNull<BNBaseAddressDetection>,
+}
+
+unsafe impl Send for BaseAddressDetection {}
+unsafe impl Sync for BaseAddressDetection {}
+
+impl BaseAddressDetection {
+    pub fn new(view: &BinaryView) -> Self {
+        let handle = unsafe { BNCreateBaseAddressDetection(view.handle) };
+        Self {
+            handle: NonNull::new(handle).unwrap(),
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNBaseAddressDetection {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Run the initial analysis and search for candidate base addresses, this can take a
+    /// long time depending on the size of the binary and the settings used.
+    ///
+    /// Fails if the settings are invalid, or if no result was found.
+    pub fn detect(&self, settings: &BaseAddressDetectionSettings) -> Result<(), ()> {
+        if settings.alignment == 0
+            || settings.max_pointers < 2
+            || settings.upper_boundary < settings.lower_boundary
+        {
+            return Err(());
+        }
+        let arch_name = settings
+            .arch
+            .map(|arch| CString::new(arch.name().as_str()).unwrap())
+            .unwrap_or_default();
+        let mut raw_settings = BNBaseAddressDetectionSettings {
+            Architecture: arch_name.as_ptr(),
+            Analysis: settings.analysis.as_raw().as_ptr() as *const c_char,
+            MinStrlen: settings.min_strlen,
+            Alignment: settings.alignment,
+            LowerBoundary: settings.lower_boundary,
+            UpperBoundary: settings.upper_boundary,
+            POIAnalysis: settings.poi_analysis,
+            MaxPointersPerCluster: settings.max_pointers,
+        };
+        if unsafe { BNDetectBaseAddress(self.as_raw(), &mut raw_settings) } {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Abort a running detection, can be called from another thread
+    pub fn abort(&self) {
+        unsafe { BNAbortBaseAddressDetection(self.as_raw()) }
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        unsafe { BNIsBaseAddressDetectionAborted(self.as_raw()) }
+    }
+
+    /// The `max_candidates` best candidates found by the last [BaseAddressDetection::detect]
+    pub fn scores(&self, max_candidates: usize) -> BaseAddressDetectionResult {
+        let mut raw_scores = vec![
+            BNBaseAddressDetectionScore {
+                Score: 0,
+                BaseAddress: 0,
+            };
+            max_candidates
+        ];
+        let mut confidence = BaseAddressDetectionConfidence::NoConfidence;
+        let mut last_tested_base_address = 0;
+        let count = unsafe {
+            BNGetBaseAddressDetectionScores(
+                self.as_raw(),
+                raw_scores.as_mut_ptr(),
+                raw_scores.len(),
+                &mut confidence,
+                &mut last_tested_base_address,
+            )
+        };
+        let mut scores: Vec<_> = raw_scores[..count]
+            .iter()
+            .map(|raw| BaseAddressDetectionScore {
+                score: raw.Score,
+                base_address: raw.BaseAddress,
+            })
+            .collect();
+        scores.sort_by_key(|s| std::cmp::Reverse(s.score));
+        BaseAddressDetectionResult {
+            scores,
+            confidence,
+            last_tested_base_address,
+        }
+    }
+
+    /// The pointers that made `base_address` a candidate
+    pub fn reasons(&self, base_address: u64) -> Array<BaseAddressDetectionReason> {
+        let mut count = 0;
+        let reasons =
+            unsafe { BNGetBaseAddressDetectionReasons(self.as_raw(), base_address, &mut count) };
+        assert!(!reasons.is_null());
+        unsafe { Array::new(reasons, count, ()) }
+    }
+}
+
+impl Drop for BaseAddressDetection {
+    fn drop(&mut self) {
+        unsafe { BNFreeBaseAddressDetection(self.as_raw()) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::architecture::CoreArchitecture;
+    use crate::binaryview::BinaryView;
+    use crate::filemetadata::FileMetadata;
+
+    use super::*;
+
+    const BASE: u64 = 0x4000_0000;
+
+    /// Raw image meant to be loaded at [BASE], with a table of pointers to strings
+    fn image() -> Vec<u8> {
+        let mut data = vec![0u8; 0x2000];
+        let mut pointers = Vec::new();
+        let strings = [
+            "first string of the image",
+            "second string of the image",
+            "third string of the image",
+            "fourth string of the image",
+        ];
+        for (i, string) in strings.iter().enumerate() {
+            let offset = 0x1000 + i * 0x40;
+            data[offset..offset + string.len()].copy_from_slice(string.as_bytes());
+            pointers.push(BASE + offset as u64);
+        }
+        for (i, pointer) in pointers.iter().enumerate() {
+            let offset = 0x100 + i * 8;
+            data[offset..offset + 8].copy_from_slice(&pointer.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn invalid_settings() {
+        crate::headless::init();
+
+        let view = BinaryView::from_data(&FileMetadata::new(), &image()).unwrap();
+        let detection = BaseAddressDetection::new(&view);
+        let settings = BaseAddressDetectionSettings::default();
+        assert!(detection.detect(&settings.clone().alignment(0)).is_err());
+        assert!(detection.detect(&settings.clone().max_pointers(1)).is_err());
+        let settings = settings.low_boundary(0x2000).high_boundary(0x1000);
+        assert!(detection.detect(&settings).is_err());
+    }
+
+    #[test]
+    fn detect_with_arch() {
+        crate::headless::init();
+
+        let view = BinaryView::from_data(&FileMetadata::new(), &image()).unwrap();
+        let detection = BaseAddressDetection::new(&view);
+        let settings = BaseAddressDetectionSettings::default()
+            .arch(CoreArchitecture::by_name("x86_64").unwrap())
+            .analysis(BaseAddressDetectionAnalysis::Basic)
+            .alignment(0x1000)
+            .min_strlen(4);
+        // the image may not have enough pointers for a result, but a result must be consistent
+        if detection.detect(&settings).is_ok() {
+            let result = detection.scores(10);
+            assert!(result.scores.len() <= 10);
+            assert!(result
+                .scores
+                .windows(2)
+                .all(|pair| pair[0].score >= pair[1].score));
+        }
+    }
+}
@@ -21,34 +21,42 @@ use binaryninjacore_sys::*;
 
 pub use binaryninjacore_sys::BNAnalysisState as AnalysisState;
 pub use binaryninjacore_sys::BNModificationStatus as ModificationStatus;
+pub use binaryninjacore_sys::BNStringType as StringType;
 
 use std::collections::HashMap;
 use std::ffi::{c_char, c_void};
-use std::ops::Range;
-use std::{ops, ptr, result, slice};
+use std::ops::{Range, RangeBounds};
+use std::{ffi, mem, ops, ptr, result, slice};
 
 use crate::architecture::{Architecture, CoreArchitecture};
 use crate::basicblock::BasicBlock;
 use crate::component::{Component, ComponentBuilder, IntoComponentGuid};
 use crate::databuffer::DataBuffer;
 use crate::debuginfo::DebugInfo;
+use crate::disassembly::DisassemblySettings;
+use crate::externallibrary::{ExternalLibrary, ExternalLocation};
 use crate::fileaccessor::FileAccessor;
 use crate::filemetadata::FileMetadata;
 use crate::flowgraph::FlowGraph;
 use crate::function::{Function, NativeBlock};
 use crate::linearview::{LinearDisassemblyLine, LinearViewCursor};
 use crate::metadata::Metadata;
+use crate::mlil::FunctionGraphType;
 use crate::platform::Platform;
 use crate::relocation::Relocation;
+use crate::search::{FindFlag, FindMatches, Query};
 use crate::section::{Section, SectionBuilder};
 use crate::segment::{Segment, SegmentBuilder};
 use crate::settings::Settings;
 use crate::symbol::{Symbol, SymbolType};
-use crate::tags::{Tag, TagType};
+use crate::tags::{Tag, TagReference, TagType};
+use crate::typearchive::TypeArchive;
+use crate::typecontainer::TypeContainer;
 use crate::typelibrary::TypeLibrary;
 use crate::types::{
     Conf, DataVariable, NamedTypeReference, QualifiedName, QualifiedNameAndType, Type,
 };
+use crate::workflow::Workflow;
 use crate::Endianness;
 
 use crate::rc::*;
@@ -222,6 +230,47 @@ pub trait BinaryViewExt: BinaryViewBase {
         unsafe { BnString::from_raw(ptr) }
     }
 
+    /// Rebase the view to `new_base`, returning the new view. Analysis, symbols, comments and
+    /// tags are carried over by the core.
+    ///
+    /// NOTE: This does not update the UI components associated with the view, if any.
+    fn rebase(&self, new_base: u64) -> Result<Ref<BinaryView>> {
+        if !unsafe { BNRebase(self.as_ref().handle, new_base) } {
+            return Err(());
+        }
+        self.file().get_view_of_type(self.view_type())
+    }
+
+    /// Same as [BinaryViewExt::rebase], the rebase is aborted if `progress` returns `false`.
+    fn rebase_with_progress<F>(&self, new_base: u64, mut progress: F) -> Result<Ref<BinaryView>>
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        unsafe extern "C" fn cb_progress_func<F: FnMut(usize, usize) -> bool>(
+            ctxt: *mut c_void,
+            progress: usize,
+            total: usize,
+        ) -> bool {
+            ffi_wrap!("BinaryViewExt::rebase_with_progress", {
+                let closure = &mut *(ctxt as *mut F);
+                closure(progress, total)
+            })
+        }
+
+        let success = unsafe {
+            BNRebaseWithProgress(
+                self.as_ref().handle,
+                new_base,
+                &mut progress as *mut F as *mut c_void,
+                Some(cb_progress_func::<F>),
+            )
+        };
+        if !success {
+            return Err(());
+        }
+        self.file().get_view_of_type(self.view_type())
+    }
+
     /// Reads up to `len` bytes from address `offset`
     fn read_vec(&self, offset: u64, len: usize) -> Vec<u8> {
         let mut ret = vec![0; len];
@@ -241,6 +290,164 @@ pub trait BinaryViewExt: BinaryViewBase {
         read_size
     }
 
+    /// Search for `data` in `range`, see [crate::search] for details. Fails if `data` is empty.
+    fn find_data<R: RangeBounds<u64>>(
+        &self,
+        range: R,
+        data: &[u8],
+        flags: FindFlag,
+    ) -> Result<FindMatches> {
+        let query = Query::data(data, flags)?;
+        Ok(FindMatches::new(self.as_ref(), range, query))
+    }
+
+    /// Search for `data` in `range`, the search is canceled when `progress` returns `false`.
+    /// Fails if `data` is empty.
+    fn find_data_with_progress<R, F>(
+        &self,
+        range: R,
+        data: &[u8],
+        flags: FindFlag,
+        progress: F,
+    ) -> Result<FindMatches<F>>
+    where
+        R: RangeBounds<u64>,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let query = Query::data(data, flags)?;
+        let matches = FindMatches::with_progress(self.as_ref(), range, query, progress);
+        Ok(matches)
+    }
+
+    /// Search for `text` in the lines of the `graph_type` representation of the code in `range`,
+    /// `settings` defaults to the default [DisassemblySettings]
+    fn find_text<R, S>(
+        &self,
+        range: R,
+        text: S,
+        settings: Option<&DisassemblySettings>,
+        flags: FindFlag,
+        graph_type: FunctionGraphType,
+    ) -> FindMatches
+    where
+        R: RangeBounds<u64>,
+        S: BnStrCompatible,
+    {
+        let text = text.into_bytes_with_nul().as_ref().to_vec();
+        let query = Query::text(text, settings, flags, graph_type);
+        FindMatches::new(self.as_ref(), range, query)
+    }
+
+    /// Search for `text` in the lines of the `graph_type` representation of the code in `range`,
+    /// the search is canceled when `progress` returns `false`
+    fn find_text_with_progress<R, S, F>(
+        &self,
+        range: R,
+        text: S,
+        settings: Option<&DisassemblySettings>,
+        flags: FindFlag,
+        graph_type: FunctionGraphType,
+        progress: F,
+    ) -> FindMatches<F>
+    where
+        R: RangeBounds<u64>,
+        S: BnStrCompatible,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let text = text.into_bytes_with_nul().as_ref().to_vec();
+        let query = Query::text(text, settings, flags, graph_type);
+        FindMatches::with_progress(self.as_ref(), range, query, progress)
+    }
+
+    /// Search for `constant` in the `graph_type` representation of the code in `range`
+    fn find_constant<R: RangeBounds<u64>>(
+        &self,
+        range: R,
+        constant: u64,
+        settings: Option<&DisassemblySettings>,
+        graph_type: FunctionGraphType,
+    ) -> FindMatches {
+        let query = Query::constant(constant, settings, graph_type);
+        FindMatches::new(self.as_ref(), range, query)
+    }
+
+    /// Search for `constant` in the `graph_type` representation of the code in `range`,
+    /// the search is canceled when `progress` returns `false`
+    fn find_constant_with_progress<R, F>(
+        &self,
+        range: R,
+        constant: u64,
+        settings: Option<&DisassemblySettings>,
+        graph_type: FunctionGraphType,
+        progress: F,
+    ) -> FindMatches<F>
+    where
+        R: RangeBounds<u64>,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let query = Query::constant(constant, settings, graph_type);
+        FindMatches::with_progress(self.as_ref(), range, query, progress)
+    }
+
+    /// Search for a hex `pattern` in `range`, `?` matches any nibble and whitespace is ignored
+    /// (eg: `"48 8b ?? ?8"`). Fails if the pattern is malformed.
+    fn find_pattern<R: RangeBounds<u64>>(&self, range: R, pattern: &str) -> Result<FindMatches> {
+        let query = Query::pattern(pattern)?;
+        Ok(FindMatches::new(self.as_ref(), range, query))
+    }
+
+    /// Search in `range` for the bytes where `byte & mask == data & mask`.
+    /// Fails if `data` and `mask` are not the same length.
+    fn find_pattern_with_mask<R: RangeBounds<u64>>(
+        &self,
+        range: R,
+        data: &[u8],
+        mask: &[u8],
+    ) -> Result<FindMatches> {
+        let query = Query::masked(data, mask)?;
+        Ok(FindMatches::new(self.as_ref(), range, query))
+    }
+
+    /// Search for a hex `pattern` in `range`, see [BinaryViewExt::find_pattern]
+    ///
+    /// `progress` is called with the offset in `range` of every match, the search is canceled
+    /// when it returns `false`.
+    fn find_pattern_with_progress<R, F>(
+        &self,
+        range: R,
+        pattern: &str,
+        progress: F,
+    ) -> Result<FindMatches<F>>
+    where
+        R: RangeBounds<u64>,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let query = Query::pattern(pattern)?;
+        let matches = FindMatches::with_progress(self.as_ref(), range, query, progress);
+        Ok(matches)
+    }
+
+    /// Search in `range` for the bytes where `byte & mask == data & mask`, see
+    /// [BinaryViewExt::find_pattern_with_mask]
+    ///
+    /// `progress` is called with the offset in `range` of every match, the search is canceled
+    /// when it returns `false`.
+    fn find_pattern_with_mask_and_progress<R, F>(
+        &self,
+        range: R,
+        data: &[u8],
+        mask: &[u8],
+        progress: F,
+    ) -> Result<FindMatches<F>>
+    where
+        R: RangeBounds<u64>,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let query = Query::masked(data, mask)?;
+        let matches = FindMatches::with_progress(self.as_ref(), range, query, progress);
+        Ok(matches)
+    }
+
     fn notify_data_written(&self, offset: u64, len: usize) {
         unsafe {
             BNNotifyDataWritten(self.as_ref().handle, offset, len);
@@ -402,6 +609,83 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    /// Assemble `code` for `arch` and write it at `addr`, returning the number of bytes written
+    fn assemble_and_write<A: Architecture>(
+        &self,
+        arch: &A,
+        addr: u64,
+        code: &str,
+    ) -> result::Result<usize, String> {
+        let data = arch.assemble(code, addr)?;
+        let written = self.write(addr, &data);
+        if written != data.len() {
+            return Err(format!(
+                "wrote {} of {} bytes at 0x{:x}",
+                written,
+                data.len(),
+                addr
+            ));
+        }
+
+        self.update_analysis();
+        Ok(written)
+    }
+
+    fn is_never_branch_patch_available<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        instruction_data(self, arch, addr)
+            .is_some_and(|data| arch.is_never_branch_patch_available(&data, addr))
+    }
+
+    fn is_always_branch_patch_available<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        instruction_data(self, arch, addr)
+            .is_some_and(|data| arch.is_always_branch_patch_available(&data, addr))
+    }
+
+    fn is_invert_branch_patch_available<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        instruction_data(self, arch, addr)
+            .is_some_and(|data| arch.is_invert_branch_patch_available(&data, addr))
+    }
+
+    fn is_skip_and_return_zero_patch_available<A: Architecture>(
+        &self,
+        arch: &A,
+        addr: u64,
+    ) -> bool {
+        instruction_data(self, arch, addr)
+            .is_some_and(|data| arch.is_skip_and_return_zero_patch_available(&data, addr))
+    }
+
+    fn is_skip_and_return_value_patch_available<A: Architecture>(
+        &self,
+        arch: &A,
+        addr: u64,
+    ) -> bool {
+        instruction_data(self, arch, addr)
+            .is_some_and(|data| arch.is_skip_and_return_value_patch_available(&data, addr))
+    }
+
+    /// Replace the instruction at `addr` with NOPs
+    fn convert_to_nop<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        patch_instruction(self, arch, addr, |data| arch.convert_to_nop(data, addr))
+    }
+
+    /// Make the conditional branch at `addr` always taken
+    fn always_branch<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        patch_instruction(self, arch, addr, |data| arch.always_branch(data, addr))
+    }
+
+    /// Invert the condition of the branch at `addr`
+    fn invert_branch<A: Architecture>(&self, arch: &A, addr: u64) -> bool {
+        patch_instruction(self, arch, addr, |data| arch.invert_branch(data, addr))
+    }
+
+    /// Replace the call at `addr` with an instruction returning `value` in place of the callee
+    fn skip_and_return_value<A: Architecture>(&self, arch: &A, addr: u64, value: u64) -> bool {
+        patch_instruction(self, arch, addr, |data| {
+            arch.skip_and_return_value(data, addr, value)
+        })
+    }
+
     fn symbol_by_address(&self, addr: u64) -> Result<Ref<Symbol>> {
         unsafe {
             let raw_sym = BNGetSymbolByAddress(self.as_ref().handle, addr, ptr::null_mut());
@@ -597,6 +881,52 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    /// List of all the strings detected in this view
+    fn strings(&self) -> Array<StringReference> {
+        let mut count = 0;
+        let strings = unsafe { BNGetStrings(self.as_ref().handle, &mut count) };
+        assert!(!strings.is_null());
+        unsafe { Array::new(strings, count, ()) }
+    }
+
+    /// List of the strings detected in `range`
+    fn strings_in_range(&self, range: Range<u64>) -> Array<StringReference> {
+        let mut count = 0;
+        let strings = unsafe {
+            BNGetStringsInRange(
+                self.as_ref().handle,
+                range.start,
+                range.end.saturating_sub(range.start),
+                &mut count,
+            )
+        };
+        assert!(!strings.is_null());
+        unsafe { Array::new(strings, count, ()) }
+    }
+
+    /// The string detected at `addr`, if any
+    fn string_at(&self, addr: u64) -> Option<StringReference> {
+        let mut string = BNStringReference {
+            type_: StringType::AsciiString,
+            start: 0,
+            length: 0,
+        };
+        unsafe { BNGetStringAtAddress(self.as_ref().handle, addr, &mut string) }
+            .then(|| StringReference::from_raw(&string))
+    }
+
+    /// Define a user string of `ty` at `addr`, `length` is the size in bytes of the string.
+    /// The string is defined as a data variable using an array of the matching character type.
+    fn define_user_string(&self, addr: u64, ty: StringType, length: usize) {
+        let char_type = match ty {
+            StringType::AsciiString | StringType::Utf8String => Type::char(),
+            StringType::Utf16String => Type::wide_char(2),
+            StringType::Utf32String => Type::wide_char(4),
+        };
+        let count = length / StringReference::char_width(ty);
+        self.define_user_data_var(addr, &Type::array(&char_type, count as u64));
+    }
+
     fn define_auto_type<S: BnStrCompatible>(
         &self,
         name: S,
@@ -730,6 +1060,24 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    /// Type container for all the types in the view, both user and auto types
+    fn type_container(&self) -> TypeContainer {
+        let container = unsafe { BNGetAnalysisTypeContainer(self.as_ref().handle) };
+        unsafe { TypeContainer::from_raw(ptr::NonNull::new(container).unwrap()) }
+    }
+
+    /// Type container for the auto types of the view, types added to it are auto types
+    fn auto_type_container(&self) -> TypeContainer {
+        let container = unsafe { BNGetAnalysisAutoTypeContainer(self.as_ref().handle) };
+        unsafe { TypeContainer::from_raw(ptr::NonNull::new(container).unwrap()) }
+    }
+
+    /// Type container for the user types of the view, types added to it are user types
+    fn user_type_container(&self) -> TypeContainer {
+        let container = unsafe { BNGetAnalysisUserTypeContainer(self.as_ref().handle) };
+        unsafe { TypeContainer::from_raw(ptr::NonNull::new(container).unwrap()) }
+    }
+
     fn dependency_sorted_types(&self) -> Array<QualifiedNameAndType> {
         unsafe {
             let mut count = 0usize;
@@ -1280,6 +1628,30 @@ pub trait BinaryViewExt: BinaryViewBase {
         }
     }
 
+    /// Retrieves the addresses referenced by the code at `addr`, in every function containing it
+    ///
+    /// `arch` defaults to the architecture of each function.
+    fn code_refs_from(&self, addr: u64, arch: Option<CoreArchitecture>) -> Vec<u64> {
+        let mut result = Vec::new();
+        for func in &self.functions_containing(addr) {
+            let mut src = BNReferenceSource {
+                func: func.handle,
+                arch: arch.unwrap_or_else(|| func.arch()).0,
+                addr,
+            };
+            let mut count = 0;
+            unsafe {
+                let refs = BNGetCodeReferencesFrom(self.as_ref().handle, &mut src, &mut count);
+                if refs.is_null() {
+                    continue;
+                }
+                result.extend_from_slice(slice::from_raw_parts(refs, count));
+                BNFreeAddressList(refs);
+            }
+        }
+        result
+    }
+
     /// Retrieves a list of [CodeReference]s pointing into a given [Range].
     fn get_code_refs_in_range(&self, range: Range<u64>) -> Array<CodeReference> {
         unsafe {
@@ -1625,10 +1997,142 @@ pub trait BinaryViewExt: BinaryViewBase {
         let name = QualifiedName(result_name);
         Some((lib, name))
     }
+
+    /// Register a [BinaryDataNotification] listener for changes made to this view, the
+    /// listener stays registered until the returned handle is dropped
+    fn register_notification<T: BinaryDataNotification>(
+        &self,
+        notification: T,
+    ) -> BinaryDataNotificationHandle<T> {
+        BinaryDataNotificationHandle::register(self.as_ref(), notification)
+    }
+
+    /// The [Workflow] used by default to analyze the functions of this view
+    fn workflow(&self) -> Option<Ref<Workflow>> {
+        let workflow = unsafe { BNGetWorkflowForBinaryView(self.as_ref().handle) };
+        (!workflow.is_null()).then(|| unsafe { Workflow::from_raw(workflow) })
+    }
 }
 
 impl<T: BinaryViewBase> BinaryViewExt for T {}
 
+fn instruction_data<V, A>(view: &V, arch: &A, addr: u64) -> Option<Vec<u8>>
+where
+    V: BinaryViewExt + ?Sized,
+    A: Architecture,
+{
+    let len = view.instruction_len(arch, addr)?;
+    let data = view.read_vec(addr, len);
+    (data.len() == len).then_some(data)
+}
+
+/// Apply `patch` to the instruction at `addr` and write it back if it succeeded
+fn patch_instruction<V, A, F>(view: &V, arch: &A, addr: u64, patch: F) -> bool
+where
+    V: BinaryViewExt + ?Sized,
+    A: Architecture,
+    F: FnOnce(&mut [u8]) -> bool,
+{
+    let Some(mut data) = instruction_data(view, arch, addr) else {
+        return false;
+    };
+    if !patch(&mut data) || view.write(addr, &data) != data.len() {
+        return false;
+    }
+
+    view.update_analysis();
+    true
+}
+
+/// A string detected by the core, see [BinaryViewExt::strings]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StringReference {
+    pub ty: StringType,
+    pub start: u64,
+    /// Length of the string in bytes
+    pub length: usize,
+}
+
+impl StringReference {
+    pub(crate) fn from_raw(value: &BNStringReference) -> Self {
+        Self {
+            ty: value.type_,
+            start: value.start,
+            length: value.length,
+        }
+    }
+
+    fn char_width(ty: StringType) -> usize {
+        match ty {
+            StringType::AsciiString | StringType::Utf8String => 1,
+            StringType::Utf16String => 2,
+            StringType::Utf32String => 4,
+        }
+    }
+
+    /// Address range of the string
+    pub fn range(&self) -> Range<u64> {
+        self.start..self.start + self.length as u64
+    }
+
+    /// Read and decode the content of the string from `view`, invalid characters are
+    /// replaced with [char::REPLACEMENT_CHARACTER]
+    pub fn content(&self, view: &BinaryView) -> String {
+        let data = view.read_vec(self.start, self.length);
+        Self::decode(self.ty, &data, view.default_endianness())
+    }
+
+    fn decode(ty: StringType, data: &[u8], endianness: Endianness) -> String {
+        match ty {
+            StringType::AsciiString => data
+                .iter()
+                .map(|&b| match b.is_ascii() {
+                    true => b as char,
+                    false => char::REPLACEMENT_CHARACTER,
+                })
+                .collect(),
+            StringType::Utf8String => String::from_utf8_lossy(data).into_owned(),
+            StringType::Utf16String => {
+                let units: Vec<u16> = data
+                    .chunks_exact(2)
+                    .map(|c| match endianness {
+                        Endianness::LittleEndian => u16::from_le_bytes([c[0], c[1]]),
+                        Endianness::BigEndian => u16::from_be_bytes([c[0], c[1]]),
+                    })
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            StringType::Utf32String => data
+                .chunks_exact(4)
+                .map(|c| {
+                    let c = [c[0], c[1], c[2], c[3]];
+                    let value = match endianness {
+                        Endianness::LittleEndian => u32::from_le_bytes(c),
+                        Endianness::BigEndian => u32::from_be_bytes(c),
+                    };
+                    char::from_u32(value).unwrap_or(char::REPLACEMENT_CHARACTER)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CoreArrayProvider for StringReference {
+    type Raw = BNStringReference;
+    type Context = ();
+    type Wrapped<'a> = StringReference;
+}
+
+unsafe impl CoreArrayProviderInner for StringReference {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeStringReferenceList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::from_raw(raw)
+    }
+}
+
 #[derive(PartialEq, Eq, Hash)]
 pub struct BinaryView {
     pub(crate) handle: *mut BNBinaryView,
@@ -1855,3 +2359,913 @@ where
         );
     }
 }
+
+/// Listener for changes made to a [BinaryView], see [BinaryViewExt::register_notification].
+///
+/// All methods have an empty default implementation, only implement the ones you care about.
+/// The callbacks are invoked from the analysis threads, so they may run concurrently.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use binaryninja::binaryview::{BinaryDataNotification, BinaryView, BinaryViewExt};
+/// use binaryninja::function::Function;
+///
+/// struct FunctionCounter {
+///     count: AtomicUsize,
+/// }
+///
+/// impl BinaryDataNotification for FunctionCounter {
+///     fn function_added(&self, _view: &BinaryView, _func: &Function) {
+///         self.count.fetch_add(1, Ordering::Relaxed);
+///     }
+///
+///     fn function_removed(&self, _view: &BinaryView, _func: &Function) {
+///         self.count.fetch_sub(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// # let bv: BinaryView = todo!();
+/// let handle = bv.register_notification(FunctionCounter {
+///     count: AtomicUsize::new(0),
+/// });
+/// bv.update_analysis_and_wait();
+/// // the notification is unregistered when `handle` is dropped
+/// drop(handle);
+/// ```
+pub trait BinaryDataNotification: 'static + Send + Sync {
+    fn data_written(&self, _view: &BinaryView, _offset: u64, _len: usize) {}
+    fn data_inserted(&self, _view: &BinaryView, _offset: u64, _len: usize) {}
+    fn data_removed(&self, _view: &BinaryView, _offset: u64, _len: u64) {}
+
+    fn function_added(&self, _view: &BinaryView, _func: &Function) {}
+    fn function_removed(&self, _view: &BinaryView, _func: &Function) {}
+    fn function_updated(&self, _view: &BinaryView, _func: &Function) {}
+    fn function_update_requested(&self, _view: &BinaryView, _func: &Function) {}
+
+    fn data_variable_added(&self, _view: &BinaryView, _var: &DataVariable) {}
+    fn data_variable_removed(&self, _view: &BinaryView, _var: &DataVariable) {}
+    fn data_variable_updated(&self, _view: &BinaryView, _var: &DataVariable) {}
+    fn data_metadata_updated(&self, _view: &BinaryView, _offset: u64) {}
+
+    fn tag_type_updated(&self, _view: &BinaryView, _tag_type: &TagType) {}
+    fn tag_added(&self, _view: &BinaryView, _tag_ref: &TagReference) {}
+    fn tag_removed(&self, _view: &BinaryView, _tag_ref: &TagReference) {}
+    fn tag_updated(&self, _view: &BinaryView, _tag_ref: &TagReference) {}
+
+    fn symbol_added(&self, _view: &BinaryView, _symbol: &Symbol) {}
+    fn symbol_removed(&self, _view: &BinaryView, _symbol: &Symbol) {}
+    fn symbol_updated(&self, _view: &BinaryView, _symbol: &Symbol) {}
+
+    fn string_found(&self, _view: &BinaryView, _ty: StringType, _offset: u64, _len: usize) {}
+    fn string_removed(&self, _view: &BinaryView, _ty: StringType, _offset: u64, _len: usize) {}
+
+    fn type_defined(&self, _view: &BinaryView, _name: &QualifiedName, _ty: &Type) {}
+    fn type_undefined(&self, _view: &BinaryView, _name: &QualifiedName, _ty: &Type) {}
+    fn type_reference_changed(&self, _view: &BinaryView, _name: &QualifiedName, _ty: &Type) {}
+    fn type_field_reference_changed(
+        &self,
+        _view: &BinaryView,
+        _name: &QualifiedName,
+        _offset: u64,
+    ) {
+    }
+
+    fn segment_added(&self, _view: &BinaryView, _segment: &Segment) {}
+    fn segment_removed(&self, _view: &BinaryView, _segment: &Segment) {}
+    fn segment_updated(&self, _view: &BinaryView, _segment: &Segment) {}
+
+    fn section_added(&self, _view: &BinaryView, _section: &Section) {}
+    fn section_removed(&self, _view: &BinaryView, _section: &Section) {}
+    fn section_updated(&self, _view: &BinaryView, _section: &Section) {}
+
+    fn component_name_updated(
+        &self,
+        _view: &BinaryView,
+        _previous_name: &str,
+        _component: &Component,
+    ) {
+    }
+    fn component_added(&self, _view: &BinaryView, _component: &Component) {}
+    fn component_moved(
+        &self,
+        _view: &BinaryView,
+        _former_parent: &Component,
+        _new_parent: &Component,
+        _component: &Component,
+    ) {
+    }
+    fn component_removed(
+        &self,
+        _view: &BinaryView,
+        _former_parent: &Component,
+        _component: &Component,
+    ) {
+    }
+    fn component_function_added(
+        &self,
+        _view: &BinaryView,
+        _component: &Component,
+        _func: &Function,
+    ) {
+    }
+    fn component_function_removed(
+        &self,
+        _view: &BinaryView,
+        _component: &Component,
+        _func: &Function,
+    ) {
+    }
+    fn component_data_variable_added(
+        &self,
+        _view: &BinaryView,
+        _component: &Component,
+        _var: &DataVariable,
+    ) {
+    }
+    fn component_data_variable_removed(
+        &self,
+        _view: &BinaryView,
+        _component: &Component,
+        _var: &DataVariable,
+    ) {
+    }
+
+    fn external_library_added(&self, _view: &BinaryView, _library: &ExternalLibrary) {}
+    fn external_library_updated(&self, _view: &BinaryView, _library: &ExternalLibrary) {}
+    fn external_library_removed(&self, _view: &BinaryView, _library: &ExternalLibrary) {}
+    fn external_location_added(&self, _view: &BinaryView, _location: &ExternalLocation) {}
+    fn external_location_updated(&self, _view: &BinaryView, _location: &ExternalLocation) {}
+    fn external_location_removed(&self, _view: &BinaryView, _location: &ExternalLocation) {}
+
+    fn type_archive_attached(&self, _view: &BinaryView, _id: &str, _path: &str) {}
+    fn type_archive_detached(&self, _view: &BinaryView, _id: &str, _path: &str) {}
+    fn type_archive_connected(&self, _view: &BinaryView, _archive: &TypeArchive) {}
+    fn type_archive_disconnected(&self, _view: &BinaryView, _archive: &TypeArchive) {}
+
+    /// Called when the view is rebased, `old_view` is the view this notification was registered on
+    fn rebased(&self, _old_view: &BinaryView, _new_view: &BinaryView) {}
+}
+
+/// Keeps a [BinaryDataNotification] registered, unregistering it when dropped.
+pub struct BinaryDataNotificationHandle<T: BinaryDataNotification> {
+    callback: *mut T,
+    notification: Box<BNBinaryDataNotification>,
+    view: Ref<BinaryView>,
+}
+
+impl<T: BinaryDataNotification> BinaryDataNotificationHandle<T> {
+    pub(crate) fn register(view: &BinaryView, callback: T) -> Self {
+        // SAFETY free on [BinaryDataNotificationHandle::Drop]
+        let callback = Box::leak(Box::new(callback));
+        // the core identifies the notification by its address, so it needs to be kept alive
+        let mut notification = Box::new(BNBinaryDataNotification {
+            context: callback as *mut T as *mut c_void,
+            notificationBarrier: None,
+            dataWritten: Some(cb_data_written::<T>),
+            dataInserted: Some(cb_data_inserted::<T>),
+            dataRemoved: Some(cb_data_removed::<T>),
+            functionAdded: Some(cb_function_added::<T>),
+            functionRemoved: Some(cb_function_removed::<T>),
+            functionUpdated: Some(cb_function_updated::<T>),
+            functionUpdateRequested: Some(cb_function_update_requested::<T>),
+            dataVariableAdded: Some(cb_data_variable_added::<T>),
+            dataVariableRemoved: Some(cb_data_variable_removed::<T>),
+            dataVariableUpdated: Some(cb_data_variable_updated::<T>),
+            dataMetadataUpdated: Some(cb_data_metadata_updated::<T>),
+            tagTypeUpdated: Some(cb_tag_type_updated::<T>),
+            tagAdded: Some(cb_tag_added::<T>),
+            tagRemoved: Some(cb_tag_removed::<T>),
+            tagUpdated: Some(cb_tag_updated::<T>),
+            symbolAdded: Some(cb_symbol_added::<T>),
+            symbolRemoved: Some(cb_symbol_removed::<T>),
+            symbolUpdated: Some(cb_symbol_updated::<T>),
+            stringFound: Some(cb_string_found::<T>),
+            stringRemoved: Some(cb_string_removed::<T>),
+            typeDefined: Some(cb_type_defined::<T>),
+            typeUndefined: Some(cb_type_undefined::<T>),
+            typeReferenceChanged: Some(cb_type_reference_changed::<T>),
+            typeFieldReferenceChanged: Some(cb_type_field_reference_changed::<T>),
+            segmentAdded: Some(cb_segment_added::<T>),
+            segmentRemoved: Some(cb_segment_removed::<T>),
+            segmentUpdated: Some(cb_segment_updated::<T>),
+            sectionAdded: Some(cb_section_added::<T>),
+            sectionRemoved: Some(cb_section_removed::<T>),
+            sectionUpdated: Some(cb_section_updated::<T>),
+            componentNameUpdated: Some(cb_component_name_updated::<T>),
+            componentAdded: Some(cb_component_added::<T>),
+            componentMoved: Some(cb_component_moved::<T>),
+            componentRemoved: Some(cb_component_removed::<T>),
+            componentFunctionAdded: Some(cb_component_function_added::<T>),
+            componentFunctionRemoved: Some(cb_component_function_removed::<T>),
+            componentDataVariableAdded: Some(cb_component_data_variable_added::<T>),
+            componentDataVariableRemoved: Some(cb_component_data_variable_removed::<T>),
+            externalLibraryAdded: Some(cb_external_library_added::<T>),
+            externalLibraryUpdated: Some(cb_external_library_updated::<T>),
+            externalLibraryRemoved: Some(cb_external_library_removed::<T>),
+            externalLocationAdded: Some(cb_external_location_added::<T>),
+            externalLocationUpdated: Some(cb_external_location_updated::<T>),
+            externalLocationRemoved: Some(cb_external_location_removed::<T>),
+            typeArchiveAttached: Some(cb_type_archive_attached::<T>),
+            typeArchiveDetached: Some(cb_type_archive_detached::<T>),
+            typeArchiveConnected: Some(cb_type_archive_connected::<T>),
+            typeArchiveDisconnected: Some(cb_type_archive_disconnected::<T>),
+            undoEntryAdded: None,
+            undoEntryTaken: None,
+            redoEntryTaken: None,
+            rebased: Some(cb_rebased::<T>),
+        });
+        unsafe { BNRegisterDataNotification(view.handle, notification.as_mut()) };
+        Self {
+            callback,
+            notification,
+            view: view.to_owned(),
+        }
+    }
+}
+
+impl<T: BinaryDataNotification> Drop for BinaryDataNotificationHandle<T> {
+    fn drop(&mut self) {
+        // unregister the notification callback
+        unsafe { BNUnregisterDataNotification(self.view.handle, self.notification.as_mut()) }
+        // free the context created at [BinaryDataNotificationHandle::register]
+        drop(unsafe { Box::from_raw(self.callback) });
+    }
+}
+
+unsafe extern "C" fn cb_data_written<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    offset: u64,
+    len: usize,
+) {
+    ffi_wrap!("BinaryDataNotification::data_written", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.data_written(&BinaryView { handle: view }, offset, len)
+    })
+}
+
+unsafe extern "C" fn cb_data_inserted<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    offset: u64,
+    len: usize,
+) {
+    ffi_wrap!("BinaryDataNotification::data_inserted", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.data_inserted(&BinaryView { handle: view }, offset, len)
+    })
+}
+
+unsafe extern "C" fn cb_data_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    offset: u64,
+    len: u64,
+) {
+    ffi_wrap!("BinaryDataNotification::data_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.data_removed(&BinaryView { handle: view }, offset, len)
+    })
+}
+
+unsafe extern "C" fn cb_function_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    func: *mut BNFunction,
+) {
+    ffi_wrap!("BinaryDataNotification::function_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.function_added(&BinaryView { handle: view }, &Function { handle: func })
+    })
+}
+
+unsafe extern "C" fn cb_function_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    func: *mut BNFunction,
+) {
+    ffi_wrap!("BinaryDataNotification::function_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.function_removed(&BinaryView { handle: view }, &Function { handle: func })
+    })
+}
+
+unsafe extern "C" fn cb_function_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    func: *mut BNFunction,
+) {
+    ffi_wrap!("BinaryDataNotification::function_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.function_updated(&BinaryView { handle: view }, &Function { handle: func })
+    })
+}
+
+unsafe extern "C" fn cb_function_update_requested<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    func: *mut BNFunction,
+) {
+    ffi_wrap!("BinaryDataNotification::function_update_requested", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.function_update_requested(&BinaryView { handle: view }, &Function { handle: func })
+    })
+}
+
+unsafe extern "C" fn cb_data_variable_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    var: *mut BNDataVariable,
+) {
+    ffi_wrap!("BinaryDataNotification::data_variable_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.data_variable_added(&BinaryView { handle: view }, &*(var as *const DataVariable))
+    })
+}
+
+unsafe extern "C" fn cb_data_variable_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    var: *mut BNDataVariable,
+) {
+    ffi_wrap!("BinaryDataNotification::data_variable_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.data_variable_removed(&BinaryView { handle: view }, &*(var as *const DataVariable))
+    })
+}
+
+unsafe extern "C" fn cb_data_variable_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    var: *mut BNDataVariable,
+) {
+    ffi_wrap!("BinaryDataNotification::data_variable_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.data_variable_updated(&BinaryView { handle: view }, &*(var as *const DataVariable))
+    })
+}
+
+unsafe extern "C" fn cb_data_metadata_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    offset: u64,
+) {
+    ffi_wrap!("BinaryDataNotification::data_metadata_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.data_metadata_updated(&BinaryView { handle: view }, offset)
+    })
+}
+
+unsafe extern "C" fn cb_tag_type_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_type: *mut BNTagType,
+) {
+    ffi_wrap!("BinaryDataNotification::tag_type_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.tag_type_updated(&BinaryView { handle: view }, &TagType { handle: tag_type })
+    })
+}
+
+unsafe extern "C" fn cb_tag_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_ref: *mut BNTagReference,
+) {
+    ffi_wrap!("BinaryDataNotification::tag_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.tag_added(
+            &BinaryView { handle: view },
+            &TagReference::from_borrowed_raw(&*tag_ref),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_tag_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_ref: *mut BNTagReference,
+) {
+    ffi_wrap!("BinaryDataNotification::tag_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.tag_removed(
+            &BinaryView { handle: view },
+            &TagReference::from_borrowed_raw(&*tag_ref),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_tag_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    tag_ref: *mut BNTagReference,
+) {
+    ffi_wrap!("BinaryDataNotification::tag_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.tag_updated(
+            &BinaryView { handle: view },
+            &TagReference::from_borrowed_raw(&*tag_ref),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_symbol_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    sym: *mut BNSymbol,
+) {
+    ffi_wrap!("BinaryDataNotification::symbol_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.symbol_added(&BinaryView { handle: view }, &Symbol::from_raw(sym))
+    })
+}
+
+unsafe extern "C" fn cb_symbol_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    sym: *mut BNSymbol,
+) {
+    ffi_wrap!("BinaryDataNotification::symbol_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.symbol_removed(&BinaryView { handle: view }, &Symbol::from_raw(sym))
+    })
+}
+
+unsafe extern "C" fn cb_symbol_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    sym: *mut BNSymbol,
+) {
+    ffi_wrap!("BinaryDataNotification::symbol_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.symbol_updated(&BinaryView { handle: view }, &Symbol::from_raw(sym))
+    })
+}
+
+unsafe extern "C" fn cb_string_found<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    ty: BNStringType,
+    offset: u64,
+    len: usize,
+) {
+    ffi_wrap!("BinaryDataNotification::string_found", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.string_found(&BinaryView { handle: view }, ty, offset, len)
+    })
+}
+
+unsafe extern "C" fn cb_string_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    ty: BNStringType,
+    offset: u64,
+    len: usize,
+) {
+    ffi_wrap!("BinaryDataNotification::string_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.string_removed(&BinaryView { handle: view }, ty, offset, len)
+    })
+}
+
+unsafe extern "C" fn cb_type_defined<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    name: *mut BNQualifiedName,
+    ty: *mut BNType,
+) {
+    ffi_wrap!("BinaryDataNotification::type_defined", {
+        let ctxt = &*(ctxt as *const T);
+        let name = mem::ManuallyDrop::new(QualifiedName(*name));
+        ctxt.type_defined(&BinaryView { handle: view }, &name, &Type { handle: ty })
+    })
+}
+
+unsafe extern "C" fn cb_type_undefined<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    name: *mut BNQualifiedName,
+    ty: *mut BNType,
+) {
+    ffi_wrap!("BinaryDataNotification::type_undefined", {
+        let ctxt = &*(ctxt as *const T);
+        let name = mem::ManuallyDrop::new(QualifiedName(*name));
+        ctxt.type_undefined(&BinaryView { handle: view }, &name, &Type { handle: ty })
+    })
+}
+
+unsafe extern "C" fn cb_type_reference_changed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    name: *mut BNQualifiedName,
+    ty: *mut BNType,
+) {
+    ffi_wrap!("BinaryDataNotification::type_reference_changed", {
+        let ctxt = &*(ctxt as *const T);
+        let name = mem::ManuallyDrop::new(QualifiedName(*name));
+        ctxt.type_reference_changed(&BinaryView { handle: view }, &name, &Type { handle: ty })
+    })
+}
+
+unsafe extern "C" fn cb_type_field_reference_changed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    name: *mut BNQualifiedName,
+    offset: u64,
+) {
+    ffi_wrap!("BinaryDataNotification::type_field_reference_changed", {
+        let ctxt = &*(ctxt as *const T);
+        let name = mem::ManuallyDrop::new(QualifiedName(*name));
+        ctxt.type_field_reference_changed(&BinaryView { handle: view }, &name, offset)
+    })
+}
+
+unsafe extern "C" fn cb_segment_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    segment: *mut BNSegment,
+) {
+    ffi_wrap!("BinaryDataNotification::segment_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.segment_added(&BinaryView { handle: view }, &Segment::from_raw(segment))
+    })
+}
+
+unsafe extern "C" fn cb_segment_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    segment: *mut BNSegment,
+) {
+    ffi_wrap!("BinaryDataNotification::segment_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.segment_removed(&BinaryView { handle: view }, &Segment::from_raw(segment))
+    })
+}
+
+unsafe extern "C" fn cb_segment_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    segment: *mut BNSegment,
+) {
+    ffi_wrap!("BinaryDataNotification::segment_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.segment_updated(&BinaryView { handle: view }, &Segment::from_raw(segment))
+    })
+}
+
+unsafe extern "C" fn cb_section_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    section: *mut BNSection,
+) {
+    ffi_wrap!("BinaryDataNotification::section_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.section_added(&BinaryView { handle: view }, &Section::from_raw(section))
+    })
+}
+
+unsafe extern "C" fn cb_section_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    section: *mut BNSection,
+) {
+    ffi_wrap!("BinaryDataNotification::section_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.section_removed(&BinaryView { handle: view }, &Section::from_raw(section))
+    })
+}
+
+unsafe extern "C" fn cb_section_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    section: *mut BNSection,
+) {
+    ffi_wrap!("BinaryDataNotification::section_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.section_updated(&BinaryView { handle: view }, &Section::from_raw(section))
+    })
+}
+
+unsafe extern "C" fn cb_component_name_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    previous_name: *mut c_char,
+    component: *mut BNComponent,
+) {
+    ffi_wrap!("BinaryDataNotification::component_name_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.component_name_updated(
+            &BinaryView { handle: view },
+            ffi::CStr::from_ptr(previous_name)
+                .to_string_lossy()
+                .as_ref(),
+            Component::ref_from_raw(&component),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_component_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    component: *mut BNComponent,
+) {
+    ffi_wrap!("BinaryDataNotification::component_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.component_added(
+            &BinaryView { handle: view },
+            Component::ref_from_raw(&component),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_component_moved<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    former_parent: *mut BNComponent,
+    new_parent: *mut BNComponent,
+    component: *mut BNComponent,
+) {
+    ffi_wrap!("BinaryDataNotification::component_moved", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.component_moved(
+            &BinaryView { handle: view },
+            Component::ref_from_raw(&former_parent),
+            Component::ref_from_raw(&new_parent),
+            Component::ref_from_raw(&component),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_component_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    former_parent: *mut BNComponent,
+    component: *mut BNComponent,
+) {
+    ffi_wrap!("BinaryDataNotification::component_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.component_removed(
+            &BinaryView { handle: view },
+            Component::ref_from_raw(&former_parent),
+            Component::ref_from_raw(&component),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_component_function_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    component: *mut BNComponent,
+    func: *mut BNFunction,
+) {
+    ffi_wrap!("BinaryDataNotification::component_function_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.component_function_added(
+            &BinaryView { handle: view },
+            Component::ref_from_raw(&component),
+            &Function { handle: func },
+        )
+    })
+}
+
+unsafe extern "C" fn cb_component_function_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    component: *mut BNComponent,
+    func: *mut BNFunction,
+) {
+    ffi_wrap!("BinaryDataNotification::component_function_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.component_function_removed(
+            &BinaryView { handle: view },
+            Component::ref_from_raw(&component),
+            &Function { handle: func },
+        )
+    })
+}
+
+unsafe extern "C" fn cb_component_data_variable_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    component: *mut BNComponent,
+    var: *mut BNDataVariable,
+) {
+    ffi_wrap!("BinaryDataNotification::component_data_variable_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.component_data_variable_added(
+            &BinaryView { handle: view },
+            Component::ref_from_raw(&component),
+            &*(var as *const DataVariable),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_component_data_variable_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    component: *mut BNComponent,
+    var: *mut BNDataVariable,
+) {
+    ffi_wrap!("BinaryDataNotification::component_data_variable_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.component_data_variable_removed(
+            &BinaryView { handle: view },
+            Component::ref_from_raw(&component),
+            &*(var as *const DataVariable),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_external_library_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    library: *mut BNExternalLibrary,
+) {
+    ffi_wrap!("BinaryDataNotification::external_library_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.external_library_added(
+            &BinaryView { handle: view },
+            ExternalLibrary::ref_from_raw(&library),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_external_library_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    library: *mut BNExternalLibrary,
+) {
+    ffi_wrap!("BinaryDataNotification::external_library_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.external_library_updated(
+            &BinaryView { handle: view },
+            ExternalLibrary::ref_from_raw(&library),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_external_library_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    library: *mut BNExternalLibrary,
+) {
+    ffi_wrap!("BinaryDataNotification::external_library_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.external_library_removed(
+            &BinaryView { handle: view },
+            ExternalLibrary::ref_from_raw(&library),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_external_location_added<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    location: *mut BNExternalLocation,
+) {
+    ffi_wrap!("BinaryDataNotification::external_location_added", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.external_location_added(
+            &BinaryView { handle: view },
+            ExternalLocation::ref_from_raw(&location),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_external_location_updated<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    location: *mut BNExternalLocation,
+) {
+    ffi_wrap!("BinaryDataNotification::external_location_updated", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.external_location_updated(
+            &BinaryView { handle: view },
+            ExternalLocation::ref_from_raw(&location),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_external_location_removed<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    location: *mut BNExternalLocation,
+) {
+    ffi_wrap!("BinaryDataNotification::external_location_removed", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.external_location_removed(
+            &BinaryView { handle: view },
+            ExternalLocation::ref_from_raw(&location),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_type_archive_attached<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    id: *const c_char,
+    path: *const c_char,
+) {
+    ffi_wrap!("BinaryDataNotification::type_archive_attached", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.type_archive_attached(
+            &BinaryView { handle: view },
+            ffi::CStr::from_ptr(id).to_string_lossy().as_ref(),
+            ffi::CStr::from_ptr(path).to_string_lossy().as_ref(),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_type_archive_detached<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    id: *const c_char,
+    path: *const c_char,
+) {
+    ffi_wrap!("BinaryDataNotification::type_archive_detached", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.type_archive_detached(
+            &BinaryView { handle: view },
+            ffi::CStr::from_ptr(id).to_string_lossy().as_ref(),
+            ffi::CStr::from_ptr(path).to_string_lossy().as_ref(),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_type_archive_connected<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    archive: *mut BNTypeArchive,
+) {
+    ffi_wrap!("BinaryDataNotification::type_archive_connected", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.type_archive_connected(
+            &BinaryView { handle: view },
+            TypeArchive::ref_from_raw(&archive),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_type_archive_disconnected<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    archive: *mut BNTypeArchive,
+) {
+    ffi_wrap!("BinaryDataNotification::type_archive_disconnected", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.type_archive_disconnected(
+            &BinaryView { handle: view },
+            TypeArchive::ref_from_raw(&archive),
+        )
+    })
+}
+
+unsafe extern "C" fn cb_rebased<T: BinaryDataNotification>(
+    ctxt: *mut c_void,
+    old_view: *mut BNBinaryView,
+    new_view: *mut BNBinaryView,
+) {
+    ffi_wrap!("BinaryDataNotification::rebased", {
+        let ctxt = &*(ctxt as *const T);
+        ctxt.rebased(
+            &BinaryView { handle: old_view },
+            &BinaryView { handle: new_view },
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Endianness, StringReference, StringType};
+
+    #[test]
+    fn string_reference_range() {
+        let string = StringReference {
+            ty: StringType::Utf16String,
+            start: 0x1000,
+            length: 8,
+        };
+        assert_eq!(string.range(), 0x1000..0x1008);
+        assert_eq!(StringReference::char_width(string.ty), 2);
+    }
+
+    #[test]
+    fn string_reference_decode() {
+        use Endianness::{BigEndian as BE, LittleEndian as LE};
+        use StringType::*;
+
+        let decode = StringReference::decode;
+        assert_eq!(decode(AsciiString, b"hello\x80", LE), "hello\u{fffd}");
+        assert_eq!(decode(Utf8String, b"h\xc3\xa9", LE), "h\u{e9}");
+        assert_eq!(decode(Utf16String, b"h\x00i\x00", LE), "hi");
+        assert_eq!(decode(Utf16String, b"\x00h\x00i", BE), "hi");
+        // invalid code points are replaced
+        let data = b"h\x00\x00\x00\x00\x00\x11\x00";
+        assert_eq!(decode(Utf32String, data, LE), "h\u{fffd}");
+        let data = b"\x00\x00\x00h\x00\x01\xf6\x00";
+        assert_eq!(decode(Utf32String, data, BE), "h\u{1f600}");
+    }
+}
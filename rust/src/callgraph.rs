@@ -0,0 +1,376 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Whole program call graph, built from [Function::callees].
+//!
+//! ```no_run
+//! # use binaryninja::binaryview::BinaryView;
+//! use binaryninja::callgraph::CallGraph;
+//!
+//! # let bv: BinaryView = todo!();
+//! let graph = CallGraph::new(&bv);
+//! for component in graph.strongly_connected_components() {
+//!     if component.len() > 1 {
+//!         println!("mutually recursive: {:?}", component);
+//!     }
+//! }
+//! println!("{}", graph.to_dot());
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+use crate::binaryview::BinaryViewExt;
+use crate::flowgraph::{BranchType, EdgeStyle, FlowGraph, FlowGraphNode};
+use crate::function::Function;
+use crate::rc::*;
+
+pub struct CallGraph {
+    functions: Vec<Ref<Function>>,
+    nodes: HashMap<Ref<Function>, usize>,
+    callees: Vec<Vec<usize>>,
+    callers: Vec<Vec<usize>>,
+}
+
+impl CallGraph {
+    /// Build the call graph of every function in `view`
+    pub fn new<V: BinaryViewExt>(view: &V) -> Self {
+        Self::from_functions(view.functions().iter().map(|func| func.clone()))
+    }
+
+    /// Build the call graph between `functions`, calls to other functions are left out
+    pub fn from_functions<I: IntoIterator<Item = Ref<Function>>>(functions: I) -> Self {
+        let mut graph = Self {
+            functions: Vec::new(),
+            nodes: HashMap::new(),
+            callees: Vec::new(),
+            callers: Vec::new(),
+        };
+
+        for func in functions {
+            if !graph.nodes.contains_key(&func) {
+                graph.nodes.insert(func.clone(), graph.functions.len());
+                graph.functions.push(func);
+            }
+        }
+
+        graph.callees = vec![Vec::new(); graph.functions.len()];
+        graph.callers = vec![Vec::new(); graph.functions.len()];
+        for (caller, func) in graph.functions.iter().enumerate() {
+            for callee in func.callees() {
+                if let Some(&callee) = graph.nodes.get(&callee) {
+                    graph.callees[caller].push(callee);
+                    graph.callers[callee].push(caller);
+                }
+            }
+        }
+
+        graph
+    }
+
+    pub fn functions(&self) -> &[Ref<Function>] {
+        &self.functions
+    }
+
+    pub fn contains(&self, func: &Function) -> bool {
+        self.nodes.contains_key(func)
+    }
+
+    /// Functions called by `func`, empty if `func` isn't part of the graph
+    pub fn callees(&self, func: &Function) -> Vec<&Function> {
+        self.node(func)
+            .map(|node| self.resolve(&self.callees[node]))
+            .unwrap_or_default()
+    }
+
+    /// Functions calling `func`, empty if `func` isn't part of the graph
+    pub fn callers(&self, func: &Function) -> Vec<&Function> {
+        self.node(func)
+            .map(|node| self.resolve(&self.callers[node]))
+            .unwrap_or_default()
+    }
+
+    /// Functions reachable from `root` in depth first preorder, starting with `root`
+    pub fn dfs(&self, root: &Function) -> Vec<&Function> {
+        self.node(root)
+            .map(|root| self.resolve(&dfs(&self.callees, root)))
+            .unwrap_or_default()
+    }
+
+    /// Functions reachable from `root` in breadth first order, starting with `root`
+    pub fn bfs(&self, root: &Function) -> Vec<&Function> {
+        self.node(root)
+            .map(|root| self.resolve(&bfs(&self.callees, root)))
+            .unwrap_or_default()
+    }
+
+    /// Functions reachable from any of `roots`, including the roots themselves
+    pub fn reachable_from<'a, I>(&self, roots: I) -> Vec<&Function>
+    where
+        I: IntoIterator<Item = &'a Function>,
+    {
+        let roots = roots.into_iter().filter_map(|f| self.node(f));
+        self.resolve(&reachable(&self.callees, roots))
+    }
+
+    /// Strongly connected components of the graph, in reverse topological order
+    ///
+    /// Functions that are not part of any cycle are returned as a component of their own.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<&Function>> {
+        strongly_connected_components(&self.callees)
+            .iter()
+            .map(|component| self.resolve(component))
+            .collect()
+    }
+
+    /// Render the graph as a [FlowGraph] with a node per function, labeled with its name
+    pub fn to_flow_graph(&self) -> Ref<FlowGraph> {
+        let graph = FlowGraph::new();
+        let names: Vec<_> = self
+            .functions
+            .iter()
+            .map(|f| f.symbol().full_name())
+            .collect();
+        let nodes: Vec<_> = names
+            .iter()
+            .map(|name| {
+                let node = FlowGraphNode::new(&graph);
+                node.set_lines(vec![name.as_str()]);
+                node
+            })
+            .collect();
+
+        let edge_style = EdgeStyle::default();
+        for (caller, callees) in self.callees.iter().enumerate() {
+            for &callee in callees {
+                nodes[caller].add_outgoing_edge(
+                    BranchType::CallDestination,
+                    &nodes[callee],
+                    &edge_style,
+                );
+            }
+        }
+        for node in &nodes {
+            graph.append(node);
+        }
+
+        graph
+    }
+
+    /// Render the graph in the Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph callgraph {\n");
+        for (node, func) in self.functions.iter().enumerate() {
+            let name = func.symbol().full_name();
+            let label = name.as_str().replace('\\', "\\\\").replace('"', "\\\"");
+            let _ = writeln!(dot, "    n{} [label=\"{}\"];", node, label);
+        }
+        for (caller, callees) in self.callees.iter().enumerate() {
+            for callee in callees {
+                let _ = writeln!(dot, "    n{} -> n{};", caller, callee);
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn node(&self, func: &Function) -> Option<usize> {
+        self.nodes.get(func).copied()
+    }
+
+    fn resolve(&self, nodes: &[usize]) -> Vec<&Function> {
+        nodes.iter().map(|&node| &*self.functions[node]).collect()
+    }
+}
+
+// The traversals below work on the node indices of an adjacency list, `successors[node]` being
+// the nodes `node` has an edge to.
+
+/// Nodes reachable from `root` in depth first preorder, starting with `root`
+fn dfs(successors: &[Vec<usize>], root: usize) -> Vec<usize> {
+    let mut visited = vec![false; successors.len()];
+    let mut order = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+        order.push(node);
+        stack.extend(successors[node].iter().rev().filter(|&&n| !visited[n]));
+    }
+    order
+}
+
+/// Nodes reachable from `root` in breadth first order, starting with `root`
+fn bfs(successors: &[Vec<usize>], root: usize) -> Vec<usize> {
+    let mut visited = vec![false; successors.len()];
+    let mut order = Vec::new();
+    let mut queue = VecDeque::from([root]);
+    visited[root] = true;
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for &successor in &successors[node] {
+            if !visited[successor] {
+                visited[successor] = true;
+                queue.push_back(successor);
+            }
+        }
+    }
+    order
+}
+
+/// Nodes reachable from any of `roots`, including the roots themselves, in increasing order
+fn reachable<I: IntoIterator<Item = usize>>(successors: &[Vec<usize>], roots: I) -> Vec<usize> {
+    let mut visited = vec![false; successors.len()];
+    let mut stack: Vec<usize> = roots.into_iter().collect();
+    while let Some(node) = stack.pop() {
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+        stack.extend(successors[node].iter().filter(|&&n| !visited[n]));
+    }
+    (0..successors.len()).filter(|&n| visited[n]).collect()
+}
+
+/// Strongly connected components, in reverse topological order
+fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    // Tarjan's algorithm, with an explicit stack of (node, next successor) to resume
+    let count = successors.len();
+    let mut index = vec![UNVISITED; count];
+    let mut lowlink = vec![0; count];
+    let mut on_stack = vec![false; count];
+    let mut stack = Vec::new();
+    let mut next_index = 0;
+    let mut components = Vec::new();
+
+    for root in 0..count {
+        if index[root] != UNVISITED {
+            continue;
+        }
+
+        let mut work = vec![(root, 0)];
+        while let Some((node, mut edge)) = work.pop() {
+            if index[node] == UNVISITED {
+                index[node] = next_index;
+                lowlink[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            let mut descend = None;
+            while edge < successors[node].len() {
+                let successor = successors[node][edge];
+                edge += 1;
+                if index[successor] == UNVISITED {
+                    descend = Some(successor);
+                    break;
+                } else if on_stack[successor] {
+                    lowlink[node] = lowlink[node].min(index[successor]);
+                }
+            }
+
+            if let Some(successor) = descend {
+                work.push((node, edge));
+                work.push((successor, 0));
+                continue;
+            }
+
+            if lowlink[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+
+            if let Some(&(parent, _)) = work.last() {
+                lowlink[parent] = lowlink[parent].min(lowlink[node]);
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 0, 2 -> 3 -> 4 -> 4, 1 -> 4 and 5 on its own
+    fn graph() -> Vec<Vec<usize>> {
+        vec![vec![1], vec![2, 4], vec![0, 3], vec![4], vec![4], vec![]]
+    }
+
+    fn sorted(mut component: Vec<usize>) -> Vec<usize> {
+        component.sort();
+        component
+    }
+
+    #[test]
+    fn depth_first() {
+        let graph = graph();
+        assert_eq!(dfs(&graph, 0), [0, 1, 2, 3, 4]);
+        assert_eq!(dfs(&graph, 3), [3, 4]);
+        assert_eq!(dfs(&graph, 5), [5]);
+    }
+
+    #[test]
+    fn breadth_first() {
+        let graph = graph();
+        assert_eq!(bfs(&graph, 0), [0, 1, 2, 4, 3]);
+        assert_eq!(bfs(&graph, 2), [2, 0, 3, 1, 4]);
+        assert_eq!(bfs(&graph, 5), [5]);
+    }
+
+    #[test]
+    fn reachable_nodes() {
+        let graph = graph();
+        assert_eq!(reachable(&graph, [3, 5]), [3, 4, 5]);
+        assert_eq!(reachable(&graph, [1]), [0, 1, 2, 3, 4]);
+        assert_eq!(reachable(&graph, []), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn components() {
+        let components: Vec<_> = strongly_connected_components(&graph())
+            .into_iter()
+            .map(sorted)
+            .collect();
+        assert_eq!(components, [vec![4], vec![3], vec![0, 1, 2], vec![5]]);
+    }
+
+    #[test]
+    fn components_deep_chain() {
+        // a long chain, deep enough to overflow a recursive implementation
+        let count = 100_000;
+        let chain: Vec<Vec<usize>> = (0..count)
+            .map(|n| if n + 1 < count { vec![n + 1] } else { vec![] })
+            .collect();
+        let components = strongly_connected_components(&chain);
+        assert_eq!(components.len(), count);
+        assert!(components
+            .iter()
+            .enumerate()
+            .all(|(i, component)| component == &[count - 1 - i]));
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::c_char;
+use std::mem;
+use std::ptr::NonNull;
+
+use binaryninjacore_sys::*;
+
+use super::{bool_result, RemoteFile, RemoteUser};
+
+use crate::database::Database;
+use crate::rc::{CoreArrayProvider, CoreArrayProviderInner};
+use crate::string::{BnStrCompatible, BnString};
+
+/// A set of local snapshots of a database that are pushed to the server as a single snapshot
+#[repr(transparent)]
+pub struct Changeset {
+    handle: NonNull<BNCollaborationChangeset>,
+}
+
+impl Changeset {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNCollaborationChangeset>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNCollaborationChangeset) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNCollaborationChangeset {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Owning database of the snapshots
+    pub fn database(&self) -> Database {
+        unsafe {
+            Database::from_raw(
+                NonNull::new(BNCollaborationChangesetGetDatabase(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    /// Remote file the changeset is pushed to
+    pub fn file(&self) -> RemoteFile {
+        unsafe {
+            RemoteFile::from_raw(
+                NonNull::new(BNCollaborationChangesetGetFile(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    /// Ids of the local snapshots in the changeset
+    pub fn snapshot_ids(&self) -> Vec<i64> {
+        let mut count = 0;
+        let value = unsafe { BNCollaborationChangesetGetSnapshotIds(self.as_raw(), &mut count) };
+        if value.is_null() {
+            return vec![];
+        }
+        let result = unsafe { std::slice::from_raw_parts(value, count) }.to_vec();
+        unsafe { BNCollaborationFreeSnapshotIdList(value, count) };
+        result
+    }
+
+    /// Author of the changeset, if known
+    pub fn author(&self) -> Option<RemoteUser> {
+        let value = unsafe { BNCollaborationChangesetGetAuthor(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { RemoteUser::from_raw(handle) })
+    }
+
+    /// Name of the snapshot created on the server
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationChangesetGetName(self.as_raw())) }
+    }
+
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) -> Result<(), ()> {
+        let name = name.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNCollaborationChangesetSetName(self.as_raw(), name.as_ref().as_ptr() as *const c_char)
+        })
+    }
+}
+
+impl Clone for Changeset {
+    fn clone(&self) -> Self {
+        unsafe {
+            Self::from_raw(
+                NonNull::new(BNNewCollaborationChangesetReference(self.as_raw())).unwrap(),
+            )
+        }
+    }
+}
+
+impl Drop for Changeset {
+    fn drop(&mut self) {
+        unsafe { BNFreeCollaborationChangeset(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for Changeset {
+    type Raw = *mut BNCollaborationChangeset;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for Changeset {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeCollaborationChangesetList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
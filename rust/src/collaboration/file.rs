@@ -0,0 +1,448 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::{c_char, c_void};
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::time::SystemTime;
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNRemoteFileType as RemoteFileType;
+
+use super::{
+    array_or_err, bool_result, cb_progress, cb_progress_nop, str_ptrs, take_core_buffer,
+    time_from_raw, Remote, RemoteFolder, RemoteProject, RemoteSnapshot,
+};
+
+use crate::database::Database;
+use crate::filemetadata::FileMetadata;
+use crate::project::ProjectFile;
+use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner, Ref};
+use crate::string::{BnStrCompatible, BnString};
+use crate::typearchive::TypeArchive;
+
+/// A file in a [RemoteProject], mirrored by a local [ProjectFile]
+#[repr(transparent)]
+pub struct RemoteFile {
+    handle: NonNull<BNRemoteFile>,
+}
+
+impl RemoteFile {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNRemoteFile>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNRemoteFile) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNRemoteFile {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Get the RemoteFile for a Database, `None` if the database is not a collaboration database
+    pub fn get_for_local_database(database: &Database) -> Result<Option<RemoteFile>, ()> {
+        let mut value = ptr::null_mut();
+        let success =
+            unsafe { BNCollaborationGetRemoteFileForLocalDatabase(database.as_raw(), &mut value) };
+        if !success {
+            return Err(());
+        }
+        Ok(NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) }))
+    }
+
+    /// Get the RemoteFile for a Type Archive, if any
+    pub fn get_for_type_archive(archive: &TypeArchive) -> Option<RemoteFile> {
+        let value = unsafe { BNCollaborationGetRemoteFileForLocalTypeArchive(archive.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) })
+    }
+
+    /// The local file mirroring this one, if the project is open
+    pub fn core_file(&self) -> Option<ProjectFile> {
+        let value = unsafe { BNRemoteFileGetCoreFile(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { ProjectFile::from_raw(handle) })
+    }
+
+    pub fn project(&self) -> RemoteProject {
+        unsafe {
+            RemoteProject::from_raw(NonNull::new(BNRemoteFileGetProject(self.as_raw())).unwrap())
+        }
+    }
+
+    /// Folder containing the file, `None` if at the root of the project
+    pub fn folder(&self) -> Option<RemoteFolder> {
+        let value = unsafe { BNRemoteFileGetFolder(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { RemoteFolder::from_raw(handle) })
+    }
+
+    pub fn remote(&self) -> Remote {
+        unsafe { Remote::from_raw(NonNull::new(BNRemoteFileGetRemote(self.as_raw())).unwrap()) }
+    }
+
+    /// Web api endpoint url
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetUrl(self.as_raw())) }
+    }
+
+    /// Chat log api endpoint url
+    pub fn chat_log_url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetChatLogUrl(self.as_raw())) }
+    }
+
+    /// User positions api endpoint url
+    pub fn user_positions_url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetUserPositionsUrl(self.as_raw())) }
+    }
+
+    /// Unique id
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetId(self.as_raw())) }
+    }
+
+    pub fn file_type(&self) -> RemoteFileType {
+        unsafe { BNRemoteFileGetType(self.as_raw()) }
+    }
+
+    pub fn created(&self) -> SystemTime {
+        time_from_raw(unsafe { BNRemoteFileGetCreated(self.as_raw()) })
+    }
+
+    /// Username of the user who created the file
+    pub fn created_by(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetCreatedBy(self.as_raw())) }
+    }
+
+    pub fn last_modified(&self) -> SystemTime {
+        time_from_raw(unsafe { BNRemoteFileGetLastModified(self.as_raw()) })
+    }
+
+    /// Date of the last snapshot of the file
+    pub fn last_snapshot(&self) -> SystemTime {
+        time_from_raw(unsafe { BNRemoteFileGetLastSnapshot(self.as_raw()) })
+    }
+
+    /// Username of the user who pushed the last snapshot of the file
+    pub fn last_snapshot_by(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetLastSnapshotBy(self.as_raw())) }
+    }
+
+    pub fn last_snapshot_name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetLastSnapshotName(self.as_raw())) }
+    }
+
+    /// Hash of the file contents
+    pub fn hash(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetHash(self.as_raw())) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetName(self.as_raw())) }
+    }
+
+    /// Set the name, the change is sent to the server immediately
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) -> Result<(), ()> {
+        let name = name.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNRemoteFileSetName(self.as_raw(), name.as_ref().as_ptr() as *const c_char)
+        })
+    }
+
+    pub fn description(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetDescription(self.as_raw())) }
+    }
+
+    /// Set the description, the change is sent to the server immediately
+    pub fn set_description<S: BnStrCompatible>(&self, description: S) -> Result<(), ()> {
+        let description = description.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNRemoteFileSetDescription(
+                self.as_raw(),
+                description.as_ref().as_ptr() as *const c_char,
+            )
+        })
+    }
+
+    pub fn metadata(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileGetMetadata(self.as_raw())) }
+    }
+
+    /// Set the metadata, the change is sent to the server immediately
+    pub fn set_metadata<S: BnStrCompatible>(&self, metadata: S) -> Result<(), ()> {
+        let metadata = metadata.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNRemoteFileSetMetadata(self.as_raw(), metadata.as_ref().as_ptr() as *const c_char)
+        })
+    }
+
+    /// Move the file to `folder`, or to the root of the project, the change is sent to the
+    /// server immediately
+    pub fn set_folder(&self, folder: Option<&RemoteFolder>) -> Result<(), ()> {
+        let folder_ptr = folder
+            .map(|f| unsafe { f.as_raw() as *mut _ })
+            .unwrap_or(ptr::null_mut());
+        bool_result(unsafe { BNRemoteFileSetFolder(self.as_raw(), folder_ptr) })
+    }
+
+    /// Size of the raw contents of the file, in bytes
+    pub fn size(&self) -> u64 {
+        unsafe { BNRemoteFileGetSize(self.as_raw()) }
+    }
+
+    pub fn has_pulled_snapshots(&self) -> bool {
+        unsafe { BNRemoteFileHasPulledSnapshots(self.as_raw()) }
+    }
+
+    /// List of the snapshots of the file, see [RemoteFile::pull_snapshots]
+    pub fn snapshots(&self) -> Result<Array<RemoteSnapshot>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNRemoteFileGetSnapshots(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn snapshot_by_id<S: BnStrCompatible>(&self, id: S) -> Option<RemoteSnapshot> {
+        let id = id.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteFileGetSnapshotById(self.as_raw(), id.as_ref().as_ptr() as *const c_char)
+        };
+        NonNull::new(value).map(|handle| unsafe { RemoteSnapshot::from_raw(handle) })
+    }
+
+    /// Pull the list of snapshots from the server
+    pub fn pull_snapshots(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteFilePullSnapshots(self.as_raw(), Some(cb_progress_nop), ptr::null_mut())
+        })
+    }
+
+    pub fn pull_snapshots_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteFilePullSnapshots(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    /// Create a snapshot of the file
+    ///
+    /// * `name` - Name of the snapshot
+    /// * `contents` - Contents of the snapshot, compatible with [crate::database::KeyValueStore]
+    /// * `analysis_cache_contents` - Analysis cache of the snapshot
+    /// * `file_contents` - Contents of the file at the time of the snapshot
+    /// * `parent_ids` - Ids of the parent snapshots
+    pub fn create_snapshot<N, I, P>(
+        &self,
+        name: N,
+        contents: &[u8],
+        analysis_cache_contents: &[u8],
+        file_contents: &[u8],
+        parent_ids: I,
+    ) -> Result<RemoteSnapshot, ()>
+    where
+        N: BnStrCompatible,
+        I: IntoIterator<Item = P>,
+        P: BnStrCompatible,
+    {
+        self.create_snapshot_with_progress(
+            name,
+            contents,
+            analysis_cache_contents,
+            file_contents,
+            parent_ids,
+            |_, _| true,
+        )
+    }
+
+    pub fn create_snapshot_with_progress<N, I, P, F>(
+        &self,
+        name: N,
+        contents: &[u8],
+        analysis_cache_contents: &[u8],
+        file_contents: &[u8],
+        parent_ids: I,
+        mut progress: F,
+    ) -> Result<RemoteSnapshot, ()>
+    where
+        N: BnStrCompatible,
+        I: IntoIterator<Item = P>,
+        P: BnStrCompatible,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let name = name.into_bytes_with_nul();
+        let parent_ids: Vec<_> = parent_ids
+            .into_iter()
+            .map(|id| id.into_bytes_with_nul())
+            .collect();
+        let mut parent_ids_ptr = str_ptrs(&parent_ids);
+        let value = unsafe {
+            BNRemoteFileCreateSnapshot(
+                self.as_raw(),
+                name.as_ref().as_ptr() as *const c_char,
+                contents.as_ptr() as *mut u8,
+                contents.len(),
+                analysis_cache_contents.as_ptr() as *mut u8,
+                analysis_cache_contents.len(),
+                file_contents.as_ptr() as *mut u8,
+                file_contents.len(),
+                parent_ids_ptr.as_mut_ptr(),
+                parent_ids_ptr.len(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteSnapshot::from_raw(handle) })
+            .ok_or(())
+    }
+
+    pub fn delete_snapshot(&self, snapshot: &RemoteSnapshot) -> Result<(), ()> {
+        bool_result(unsafe { BNRemoteFileDeleteSnapshot(self.as_raw(), snapshot.as_raw()) })
+    }
+
+    /// Download the raw contents of the file
+    pub fn download(&self) -> Result<Vec<u8>, ()> {
+        self.download_with_progress(|_, _| true)
+    }
+
+    pub fn download_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<Vec<u8>, ()> {
+        let mut data = ptr::null_mut();
+        let mut size = 0;
+        let success = unsafe {
+            BNRemoteFileDownload(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+                &mut data,
+                &mut size,
+            )
+        };
+        if !success {
+            return Err(());
+        }
+        Ok(unsafe { take_core_buffer(data, size) })
+    }
+
+    /// Positions of the users in the file, as a json string
+    pub fn request_user_positions(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileRequestUserPositions(self.as_raw())) }
+    }
+
+    /// Chat log of the file, as a json string
+    pub fn request_chat_log(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFileRequestChatLog(self.as_raw())) }
+    }
+
+    /// Default path of the local database of the file
+    pub fn default_path(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationDefaultFilePath(self.as_raw())) }
+    }
+
+    /// Download the file and its snapshots to a new database at `db_path`
+    pub fn download_to_database<S: BnStrCompatible>(
+        &self,
+        db_path: S,
+    ) -> Result<Ref<FileMetadata>, ()> {
+        self.download_to_database_with_progress(db_path, |_, _| true)
+    }
+
+    pub fn download_to_database_with_progress<S, F>(
+        &self,
+        db_path: S,
+        mut progress: F,
+    ) -> Result<Ref<FileMetadata>, ()>
+    where
+        S: BnStrCompatible,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let db_path = db_path.into_bytes_with_nul();
+        let value = unsafe {
+            BNCollaborationDownloadFile(
+                self.as_raw(),
+                db_path.as_ref().as_ptr() as *const c_char,
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        };
+        if value.is_null() {
+            return Err(());
+        }
+        Ok(unsafe { Ref::new(FileMetadata::from_raw(value)) })
+    }
+
+    /// Download the database of the file to `db_path`, overwriting an existing one only if
+    /// `force` is set
+    pub fn download_database<S: BnStrCompatible>(&self, db_path: S, force: bool) -> Result<(), ()> {
+        self.download_database_with_progress(db_path, force, |_, _| true)
+    }
+
+    pub fn download_database_with_progress<S, F>(
+        &self,
+        db_path: S,
+        force: bool,
+        mut progress: F,
+    ) -> Result<(), ()>
+    where
+        S: BnStrCompatible,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let db_path = db_path.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNCollaborationDownloadDatabaseForFile(
+                self.as_raw(),
+                db_path.as_ref().as_ptr() as *const c_char,
+                force,
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+}
+
+impl Clone for RemoteFile {
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw(NonNull::new(BNNewRemoteFileReference(self.as_raw())).unwrap()) }
+    }
+}
+
+impl Drop for RemoteFile {
+    fn drop(&mut self) {
+        unsafe { BNFreeRemoteFile(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for RemoteFile {
+    type Raw = *mut BNRemoteFile;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for RemoteFile {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeRemoteFileList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::c_char;
+use std::mem;
+use std::ptr::{self, NonNull};
+
+use binaryninjacore_sys::*;
+
+use super::{bool_result, optional_string, Remote, RemoteProject};
+
+use crate::project::ProjectFolder;
+use crate::rc::{CoreArrayProvider, CoreArrayProviderInner};
+use crate::string::{BnStrCompatible, BnString};
+
+/// A folder in a [RemoteProject], mirrored by a local [ProjectFolder]
+#[repr(transparent)]
+pub struct RemoteFolder {
+    handle: NonNull<BNRemoteFolder>,
+}
+
+impl RemoteFolder {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNRemoteFolder>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNRemoteFolder) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNRemoteFolder {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// The local folder mirroring this one, if the project is open
+    pub fn core_folder(&self) -> Option<ProjectFolder> {
+        let value = unsafe { BNRemoteFolderGetCoreFolder(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { ProjectFolder::from_raw(handle) })
+    }
+
+    pub fn project(&self) -> RemoteProject {
+        unsafe {
+            RemoteProject::from_raw(NonNull::new(BNRemoteFolderGetProject(self.as_raw())).unwrap())
+        }
+    }
+
+    pub fn remote(&self) -> Remote {
+        unsafe { Remote::from_raw(NonNull::new(BNRemoteFolderGetRemote(self.as_raw())).unwrap()) }
+    }
+
+    /// Parent folder, `None` if at the root of the project
+    pub fn parent(&self) -> Result<Option<RemoteFolder>, ()> {
+        let mut value = ptr::null_mut();
+        if !unsafe { BNRemoteFolderGetParent(self.as_raw(), &mut value) } {
+            return Err(());
+        }
+        Ok(NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) }))
+    }
+
+    /// Id of the parent folder, `None` if at the root of the project
+    pub fn parent_id(&self) -> Option<BnString> {
+        let mut value = ptr::null_mut();
+        if !unsafe { BNRemoteFolderGetParentId(self.as_raw(), &mut value) } {
+            return None;
+        }
+        unsafe { optional_string(value) }
+    }
+
+    /// Move the folder into `parent`, or to the root of the project, the change is sent to the
+    /// server immediately
+    pub fn set_parent(&self, parent: Option<&RemoteFolder>) -> Result<(), ()> {
+        let parent_ptr = parent
+            .map(|p| unsafe { p.as_raw() as *mut _ })
+            .unwrap_or(ptr::null_mut());
+        bool_result(unsafe { BNRemoteFolderSetParent(self.as_raw(), parent_ptr) })
+    }
+
+    /// Web api endpoint url
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFolderGetUrl(self.as_raw())) }
+    }
+
+    /// Unique id
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFolderGetId(self.as_raw())) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFolderGetName(self.as_raw())) }
+    }
+
+    /// Set the name, the change is sent to the server immediately
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) -> Result<(), ()> {
+        let name = name.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNRemoteFolderSetName(self.as_raw(), name.as_ref().as_ptr() as *const c_char)
+        })
+    }
+
+    pub fn description(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteFolderGetDescription(self.as_raw())) }
+    }
+
+    /// Set the description, the change is sent to the server immediately
+    pub fn set_description<S: BnStrCompatible>(&self, description: S) -> Result<(), ()> {
+        let description = description.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNRemoteFolderSetDescription(
+                self.as_raw(),
+                description.as_ref().as_ptr() as *const c_char,
+            )
+        })
+    }
+}
+
+impl Clone for RemoteFolder {
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw(NonNull::new(BNNewRemoteFolderReference(self.as_raw())).unwrap()) }
+    }
+}
+
+impl Drop for RemoteFolder {
+    fn drop(&mut self) {
+        unsafe { BNFreeRemoteFolder(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for RemoteFolder {
+    type Raw = *mut BNRemoteFolder;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for RemoteFolder {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeRemoteFolderList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::c_char;
+use std::mem;
+use std::ptr::{self, NonNull};
+
+use binaryninjacore_sys::*;
+
+use super::{bool_result, str_ptrs, Remote};
+
+use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner};
+use crate::string::{BnStrCompatible, BnString};
+
+/// A group of users of a [Remote]
+#[repr(transparent)]
+pub struct RemoteGroup {
+    handle: NonNull<BNCollaborationGroup>,
+}
+
+impl RemoteGroup {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNCollaborationGroup>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNCollaborationGroup) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNCollaborationGroup {
+        &mut *self.handle.as_ptr()
+    }
+
+    pub fn remote(&self) -> Remote {
+        unsafe {
+            Remote::from_raw(NonNull::new(BNCollaborationGroupGetRemote(self.as_raw())).unwrap())
+        }
+    }
+
+    /// Web api endpoint url
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationGroupGetUrl(self.as_raw())) }
+    }
+
+    /// Unique id
+    pub fn id(&self) -> u64 {
+        unsafe { BNCollaborationGroupGetId(self.as_raw()) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationGroupGetName(self.as_raw())) }
+    }
+
+    /// Set the name, the change is sent to the server on [Remote::push_group]
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) {
+        let name = name.into_bytes_with_nul();
+        unsafe {
+            BNCollaborationGroupSetName(self.as_raw(), name.as_ref().as_ptr() as *const c_char)
+        }
+    }
+
+    /// Members of the group, as `(id, username)` pairs
+    pub fn users(&self) -> Result<Vec<(String, String)>, ()> {
+        let mut user_ids = ptr::null_mut();
+        let mut usernames = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            BNCollaborationGroupGetUsers(self.as_raw(), &mut user_ids, &mut usernames, &mut count)
+        };
+        if !success {
+            return Err(());
+        }
+        let user_ids = unsafe { Array::<BnString>::new(user_ids, count, ()) };
+        let usernames = unsafe { Array::<BnString>::new(usernames, count, ()) };
+        Ok(user_ids
+            .iter()
+            .zip(usernames.iter())
+            .map(|(id, username)| (id.to_string(), username.to_string()))
+            .collect())
+    }
+
+    /// Set the members of the group, the change is sent to the server on [Remote::push_group]
+    pub fn set_usernames<I, S>(&self, usernames: I) -> Result<(), ()>
+    where
+        I: IntoIterator<Item = S>,
+        S: BnStrCompatible,
+    {
+        let usernames: Vec<_> = usernames
+            .into_iter()
+            .map(|u| u.into_bytes_with_nul())
+            .collect();
+        let mut usernames_ptr = str_ptrs(&usernames);
+        bool_result(unsafe {
+            BNCollaborationGroupSetUsernames(
+                self.as_raw(),
+                usernames_ptr.as_mut_ptr(),
+                usernames_ptr.len(),
+            )
+        })
+    }
+
+    pub fn contains_user<S: BnStrCompatible>(&self, username: S) -> bool {
+        let username = username.into_bytes_with_nul();
+        unsafe {
+            BNCollaborationGroupContainsUser(
+                self.as_raw(),
+                username.as_ref().as_ptr() as *const c_char,
+            )
+        }
+    }
+}
+
+impl Clone for RemoteGroup {
+    fn clone(&self) -> Self {
+        unsafe {
+            Self::from_raw(NonNull::new(BNNewCollaborationGroupReference(self.as_raw())).unwrap())
+        }
+    }
+}
+
+impl Drop for RemoteGroup {
+    fn drop(&mut self) {
+        unsafe { BNFreeCollaborationGroup(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for RemoteGroup {
+    type Raw = *mut BNCollaborationGroup;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for RemoteGroup {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeCollaborationGroupList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
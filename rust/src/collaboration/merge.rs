@@ -0,0 +1,268 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::c_char;
+use std::mem;
+use std::ptr::NonNull;
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNMergeConflictDataType as MergeConflictDataType;
+
+use super::{bool_result, optional_string};
+
+use crate::database::{Database, Snapshot};
+use crate::filemetadata::FileMetadata;
+use crate::rc::{CoreArrayProvider, CoreArrayProviderInner, Ref};
+use crate::string::{BnStrCompatible, BnString};
+use crate::typearchive::TypeArchive;
+
+/// A conflict between two snapshots of a database, found while merging them
+#[repr(transparent)]
+pub struct AnalysisMergeConflict {
+    handle: NonNull<BNAnalysisMergeConflict>,
+}
+
+impl AnalysisMergeConflict {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNAnalysisMergeConflict>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNAnalysisMergeConflict) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNAnalysisMergeConflict {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Database being merged
+    pub fn database(&self) -> Database {
+        unsafe {
+            Database::from_raw(
+                NonNull::new(BNAnalysisMergeConflictGetDatabase(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    /// Type of the conflicting object, eg: "Function"
+    pub fn conflict_type(&self) -> BnString {
+        unsafe { BnString::from_raw(BNAnalysisMergeConflictGetType(self.as_raw())) }
+    }
+
+    /// Lookup key of the conflicting object
+    pub fn key(&self) -> BnString {
+        unsafe { BnString::from_raw(BNAnalysisMergeConflictGetKey(self.as_raw())) }
+    }
+
+    /// Type of the change in the conflicting object
+    pub fn data_type(&self) -> MergeConflictDataType {
+        unsafe { BNAnalysisMergeConflictGetDataType(self.as_raw()) }
+    }
+
+    /// Json of the object in the common ancestor snapshot, if present there
+    pub fn base(&self) -> Option<BnString> {
+        unsafe { optional_string(BNAnalysisMergeConflictGetBase(self.as_raw())) }
+    }
+
+    /// Json of the object in the first snapshot, if present there
+    pub fn first(&self) -> Option<BnString> {
+        unsafe { optional_string(BNAnalysisMergeConflictGetFirst(self.as_raw())) }
+    }
+
+    /// Json of the object in the second snapshot, if present there
+    pub fn second(&self) -> Option<BnString> {
+        unsafe { optional_string(BNAnalysisMergeConflictGetSecond(self.as_raw())) }
+    }
+
+    /// FileMetadata with the contents of the common ancestor snapshot
+    pub fn base_file(&self) -> Option<Ref<FileMetadata>> {
+        let value = unsafe { BNAnalysisMergeConflictGetBaseFile(self.as_raw()) };
+        (!value.is_null()).then(|| unsafe { Ref::new(FileMetadata::from_raw(value)) })
+    }
+
+    /// FileMetadata with the contents of the first snapshot
+    pub fn first_file(&self) -> Option<Ref<FileMetadata>> {
+        let value = unsafe { BNAnalysisMergeConflictGetFirstFile(self.as_raw()) };
+        (!value.is_null()).then(|| unsafe { Ref::new(FileMetadata::from_raw(value)) })
+    }
+
+    /// FileMetadata with the contents of the second snapshot
+    pub fn second_file(&self) -> Option<Ref<FileMetadata>> {
+        let value = unsafe { BNAnalysisMergeConflictGetSecondFile(self.as_raw()) };
+        (!value.is_null()).then(|| unsafe { Ref::new(FileMetadata::from_raw(value)) })
+    }
+
+    /// Common ancestor snapshot
+    pub fn base_snapshot(&self) -> Option<Snapshot> {
+        let value = unsafe { BNAnalysisMergeConflictGetBaseSnapshot(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { Snapshot::from_raw(handle) })
+    }
+
+    pub fn first_snapshot(&self) -> Option<Snapshot> {
+        let value = unsafe { BNAnalysisMergeConflictGetFirstSnapshot(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { Snapshot::from_raw(handle) })
+    }
+
+    pub fn second_snapshot(&self) -> Option<Snapshot> {
+        let value = unsafe { BNAnalysisMergeConflictGetSecondSnapshot(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { Snapshot::from_raw(handle) })
+    }
+
+    /// Get the string value of the item at `path` in the conflict, eg: "name"
+    pub fn path_item_string<S: BnStrCompatible>(&self, path: S) -> Option<BnString> {
+        let path = path.into_bytes_with_nul();
+        unsafe {
+            optional_string(BNAnalysisMergeConflictGetPathItemString(
+                self.as_raw(),
+                path.as_ref().as_ptr() as *const c_char,
+            ))
+        }
+    }
+
+    /// Resolve the conflict with the json `value`, or by deleting the object if `None`
+    pub fn success<S: BnStrCompatible>(&self, value: Option<S>) -> Result<(), ()> {
+        let value = value.map(|v| v.into_bytes_with_nul());
+        let value_ptr = value
+            .as_ref()
+            .map(|v| v.as_ref().as_ptr() as *const c_char)
+            .unwrap_or(std::ptr::null());
+        bool_result(unsafe { BNAnalysisMergeConflictSuccess(self.as_raw(), value_ptr) })
+    }
+}
+
+impl Clone for AnalysisMergeConflict {
+    fn clone(&self) -> Self {
+        unsafe {
+            Self::from_raw(
+                NonNull::new(BNNewAnalysisMergeConflictReference(self.as_raw())).unwrap(),
+            )
+        }
+    }
+}
+
+impl Drop for AnalysisMergeConflict {
+    fn drop(&mut self) {
+        unsafe { BNFreeAnalysisMergeConflict(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for AnalysisMergeConflict {
+    type Raw = *mut BNAnalysisMergeConflict;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for AnalysisMergeConflict {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeAnalysisMergeConflictList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
+
+/// A conflict between two snapshots of a type archive, found while merging them
+#[repr(transparent)]
+pub struct TypeArchiveMergeConflict {
+    handle: NonNull<BNTypeArchiveMergeConflict>,
+}
+
+impl TypeArchiveMergeConflict {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNTypeArchiveMergeConflict>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNTypeArchiveMergeConflict) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNTypeArchiveMergeConflict {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Type archive being merged
+    pub fn type_archive(&self) -> TypeArchive {
+        unsafe {
+            TypeArchive::from_raw(
+                NonNull::new(BNTypeArchiveMergeConflictGetTypeArchive(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    /// Id of the conflicting type
+    pub fn type_id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTypeArchiveMergeConflictGetTypeId(self.as_raw())) }
+    }
+
+    /// Id of the common ancestor snapshot
+    pub fn base_snapshot_id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTypeArchiveMergeConflictGetBaseSnapshotId(self.as_raw())) }
+    }
+
+    pub fn first_snapshot_id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTypeArchiveMergeConflictGetFirstSnapshotId(self.as_raw())) }
+    }
+
+    pub fn second_snapshot_id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTypeArchiveMergeConflictGetSecondSnapshotId(self.as_raw())) }
+    }
+
+    /// Resolve the conflict with the json `value` of the type, or by deleting it if `None`
+    pub fn success<S: BnStrCompatible>(&self, value: Option<S>) -> Result<(), ()> {
+        let value = value.map(|v| v.into_bytes_with_nul());
+        let value_ptr = value
+            .as_ref()
+            .map(|v| v.as_ref().as_ptr() as *const c_char)
+            .unwrap_or(std::ptr::null());
+        bool_result(unsafe { BNTypeArchiveMergeConflictSuccess(self.as_raw(), value_ptr) })
+    }
+}
+
+impl Clone for TypeArchiveMergeConflict {
+    fn clone(&self) -> Self {
+        unsafe {
+            Self::from_raw(
+                NonNull::new(BNNewTypeArchiveMergeConflictReference(self.as_raw())).unwrap(),
+            )
+        }
+    }
+}
+
+impl Drop for TypeArchiveMergeConflict {
+    fn drop(&mut self) {
+        unsafe { BNFreeTypeArchiveMergeConflict(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for TypeArchiveMergeConflict {
+    type Raw = *mut BNTypeArchiveMergeConflict;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for TypeArchiveMergeConflict {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeTypeArchiveMergeConflictList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
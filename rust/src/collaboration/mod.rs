@@ -0,0 +1,355 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Collaboration with an Enterprise server: remotes, remote projects, files and snapshots, and the
+//! synchronization of local databases with them.
+//!
+//! ```no_run
+//! use binaryninja::collaboration;
+//!
+//! let remote = collaboration::active_remote().expect("No active remote");
+//! remote.connect("user", "token").unwrap();
+//! for project in remote.projects().unwrap().iter() {
+//!     println!("{}", project.name());
+//! }
+//! ```
+
+mod changeset;
+mod file;
+mod folder;
+mod group;
+mod merge;
+mod permission;
+mod project;
+mod remote;
+mod snapshot;
+mod sync;
+mod user;
+
+pub use self::changeset::*;
+pub use self::file::*;
+pub use self::folder::*;
+pub use self::group::*;
+pub use self::merge::*;
+pub use self::permission::*;
+pub use self::project::*;
+pub use self::remote::*;
+pub use self::snapshot::*;
+pub use self::sync::*;
+pub use self::user::*;
+
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CStr};
+use std::ptr::{self, NonNull};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use binaryninjacore_sys::*;
+
+use crate::rc::{Array, CoreArrayProviderInner};
+use crate::string::{BnStrCompatible, BnString};
+
+/// Get the single actively connected Remote (for ux simplification), if any
+pub fn active_remote() -> Option<Remote> {
+    let value = unsafe { BNCollaborationGetActiveRemote() };
+    NonNull::new(value).map(|handle| unsafe { Remote::from_raw(handle) })
+}
+
+/// Set the single actively connected Remote
+pub fn set_active_remote(remote: Option<&Remote>) {
+    let remote_ptr = remote
+        .map(|r| unsafe { r.as_raw() as *mut _ })
+        .unwrap_or(ptr::null_mut());
+    unsafe { BNCollaborationSetActiveRemote(remote_ptr) }
+}
+
+/// Load the list of known Remotes from the local Settings
+pub fn load_remotes() -> Result<(), ()> {
+    bool_result(unsafe { BNCollaborationLoadRemotes() })
+}
+
+/// List of known/connected Remotes
+pub fn known_remotes() -> Array<Remote> {
+    let mut count = 0;
+    let value = unsafe { BNCollaborationGetRemotes(&mut count) };
+    assert!(!value.is_null());
+    unsafe { Array::new(value, count, ()) }
+}
+
+/// Get Remote by unique `id`
+pub fn get_remote_by_id<S: BnStrCompatible>(id: S) -> Option<Remote> {
+    let id = id.into_bytes_with_nul();
+    let value = unsafe { BNCollaborationGetRemoteById(id.as_ref().as_ptr() as *const c_char) };
+    NonNull::new(value).map(|handle| unsafe { Remote::from_raw(handle) })
+}
+
+/// Get Remote by `address`
+pub fn get_remote_by_address<S: BnStrCompatible>(address: S) -> Option<Remote> {
+    let address = address.into_bytes_with_nul();
+    let value =
+        unsafe { BNCollaborationGetRemoteByAddress(address.as_ref().as_ptr() as *const c_char) };
+    NonNull::new(value).map(|handle| unsafe { Remote::from_raw(handle) })
+}
+
+/// Get Remote by `name`
+pub fn get_remote_by_name<S: BnStrCompatible>(name: S) -> Option<Remote> {
+    let name = name.into_bytes_with_nul();
+    let value = unsafe { BNCollaborationGetRemoteByName(name.as_ref().as_ptr() as *const c_char) };
+    NonNull::new(value).map(|handle| unsafe { Remote::from_raw(handle) })
+}
+
+/// Remove a Remote from the list of known remotes (saved to Settings)
+pub fn remove_known_remote(remote: &Remote) {
+    unsafe { BNCollaborationRemoveRemote(remote.as_raw()) }
+}
+
+/// Save the list of known Remotes to local Settings
+pub fn save_remotes() {
+    unsafe { BNCollaborationSaveRemotes() }
+}
+
+/// Store data in the system keychain under `key`
+pub fn store_data_in_keychain<K, I, DK, DV>(key: K, data: I) -> Result<(), ()>
+where
+    K: BnStrCompatible,
+    I: IntoIterator<Item = (DK, DV)>,
+    DK: BnStrCompatible,
+    DV: BnStrCompatible,
+{
+    let key = key.into_bytes_with_nul();
+    let success = with_str_pairs(data, |keys, values, count| unsafe {
+        BNCollaborationStoreDataInKeychain(
+            key.as_ref().as_ptr() as *const c_char,
+            keys,
+            values,
+            count,
+        )
+    });
+    bool_result(success)
+}
+
+/// Whether the system keychain has data stored under `key`
+pub fn has_data_in_keychain<K: BnStrCompatible>(key: K) -> bool {
+    let key = key.into_bytes_with_nul();
+    unsafe { BNCollaborationHasDataInKeychain(key.as_ref().as_ptr() as *const c_char) }
+}
+
+/// Get the data stored in the system keychain under `key`
+pub fn get_data_from_keychain<K: BnStrCompatible>(key: K) -> HashMap<String, String> {
+    let key = key.into_bytes_with_nul();
+    let mut keys = ptr::null_mut();
+    let mut values = ptr::null_mut();
+    let count = unsafe {
+        BNCollaborationGetDataFromKeychain(
+            key.as_ref().as_ptr() as *const c_char,
+            &mut keys,
+            &mut values,
+        )
+    };
+    if count == 0 {
+        return HashMap::new();
+    }
+    let keys = unsafe { Array::<BnString>::new(keys, count, ()) };
+    let values = unsafe { Array::<BnString>::new(values, count, ()) };
+    keys.iter()
+        .zip(values.iter())
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Delete the data stored in the system keychain under `key`
+pub fn delete_data_from_keychain<K: BnStrCompatible>(key: K) -> Result<(), ()> {
+    let key = key.into_bytes_with_nul();
+    bool_result(unsafe {
+        BNCollaborationDeleteDataFromKeychain(key.as_ref().as_ptr() as *const c_char)
+    })
+}
+
+pub(crate) fn str_ptrs<S: AsRef<[u8]>>(strings: &[S]) -> Vec<*const c_char> {
+    strings
+        .iter()
+        .map(|s| s.as_ref().as_ptr() as *const c_char)
+        .collect()
+}
+
+/// Calls `func` with the raw keys and values of `pairs`, eg: the extra fields sent to the server on push
+pub(crate) fn with_str_pairs<I, K, V, R>(
+    pairs: I,
+    func: impl FnOnce(*mut *const c_char, *mut *const c_char, usize) -> R,
+) -> R
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: BnStrCompatible,
+    V: BnStrCompatible,
+{
+    let (keys, values): (Vec<_>, Vec<_>) = pairs
+        .into_iter()
+        .map(|(k, v)| (k.into_bytes_with_nul(), v.into_bytes_with_nul()))
+        .unzip();
+    let mut keys_ptr = str_ptrs(&keys);
+    let mut values_ptr = str_ptrs(&values);
+    func(
+        keys_ptr.as_mut_ptr(),
+        values_ptr.as_mut_ptr(),
+        keys_ptr.len(),
+    )
+}
+
+/// Copy a buffer returned by the core, that is freed like a string
+pub(crate) unsafe fn take_core_buffer(data: *mut u8, size: usize) -> Vec<u8> {
+    if data.is_null() {
+        return vec![];
+    }
+    let result = std::slice::from_raw_parts(data, size).to_vec();
+    BNFreeString(data as *mut c_char);
+    result
+}
+
+pub(crate) unsafe fn optional_string(value: *mut c_char) -> Option<BnString> {
+    (!value.is_null()).then(|| BnString::from_raw(value))
+}
+
+pub(crate) fn time_from_raw(timestamp: i64) -> SystemTime {
+    match u64::try_from(timestamp) {
+        Ok(secs) => UNIX_EPOCH + Duration::from_secs(secs),
+        Err(_) => UNIX_EPOCH - Duration::from_secs(timestamp.unsigned_abs()),
+    }
+}
+
+pub(crate) fn bool_result(value: bool) -> Result<(), ()> {
+    if value {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
+
+pub(crate) unsafe fn array_or_err<P: CoreArrayProviderInner>(
+    value: *mut P::Raw,
+    count: usize,
+    context: P::Context,
+) -> Result<Array<P>, ()> {
+    if value.is_null() {
+        Err(())
+    } else {
+        Ok(Array::new(value, count, context))
+    }
+}
+
+pub(crate) unsafe fn c_str_to_string(value: *const c_char) -> String {
+    CStr::from_ptr(value).to_string_lossy().into_owned()
+}
+
+pub(crate) unsafe extern "C" fn cb_progress<F: FnMut(usize, usize) -> bool>(
+    ctxt: *mut c_void,
+    progress: usize,
+    total: usize,
+) -> bool {
+    ffi_wrap!("collaboration::progress", {
+        let ctxt: &mut F = &mut *(ctxt as *mut F);
+        ctxt(progress, total)
+    })
+}
+
+pub(crate) unsafe extern "C" fn cb_progress_nop(
+    _ctxt: *mut c_void,
+    _progress: usize,
+    _total: usize,
+) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    /// Address and credentials of the server used by the tests, eg: a locally hosted Enterprise
+    /// stand-in. The tests that need a server are skipped if they are not set.
+    fn test_server() -> Option<(String, String, String)> {
+        Some((
+            env::var("BN_TEST_REMOTE_ADDRESS").ok()?,
+            env::var("BN_TEST_REMOTE_USERNAME").ok()?,
+            env::var("BN_TEST_REMOTE_PASSWORD").ok()?,
+        ))
+    }
+
+    fn unique_name(prefix: &str) -> String {
+        let unique_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        format!("{prefix}_{unique_id}")
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(time_from_raw(0), UNIX_EPOCH);
+        assert_eq!(time_from_raw(60), UNIX_EPOCH + Duration::from_secs(60));
+        assert_eq!(time_from_raw(-60), UNIX_EPOCH - Duration::from_secs(60));
+    }
+
+    #[test]
+    fn str_pairs() {
+        let pairs = [("name", "value"), ("other", "")];
+        let (keys, values) = with_str_pairs(pairs, |keys, values, count| unsafe {
+            let read = |strings: *mut *const c_char| -> Vec<String> {
+                (0..count)
+                    .map(|i| c_str_to_string(*strings.add(i)))
+                    .collect()
+            };
+            (read(keys), read(values))
+        });
+        assert_eq!(keys, ["name", "other"]);
+        assert_eq!(values, ["value", ""]);
+    }
+
+    #[test]
+    fn remote_project_folder() {
+        let Some((address, username, password)) = test_server() else {
+            return;
+        };
+        crate::headless::init();
+
+        let remote = Remote::new(unique_name("test_remote"), &address).unwrap();
+        let token = remote
+            .request_authentication_token(&username, &password)
+            .unwrap();
+        remote.connect(&username, token).unwrap();
+        assert!(remote.is_connected());
+        assert_eq!(remote.username().as_str(), username);
+
+        // create a project and a folder in it
+        let project_name = unique_name("test_project");
+        let project = remote
+            .create_project(&project_name, "created by the rust tests")
+            .unwrap();
+        assert_eq!(project.name().as_str(), project_name);
+        remote.pull_projects().unwrap();
+        assert!(remote.project_by_name(&project_name).is_some());
+
+        let folder = project.create_folder("folder", "", None).unwrap();
+        assert_eq!(folder.name().as_str(), "folder");
+        project.pull_folders().unwrap();
+        assert_eq!(project.folders().unwrap().len(), 1);
+
+        // and clean up
+        remote.delete_project(&project).unwrap();
+        remote.pull_projects().unwrap();
+        assert!(remote.project_by_name(&project_name).is_none());
+        remote.disconnect().unwrap();
+        remove_known_remote(&remote);
+    }
+}
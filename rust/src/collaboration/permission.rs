@@ -0,0 +1,148 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::mem;
+use std::ptr::NonNull;
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNCollaborationPermissionLevel as PermissionLevel;
+
+use super::{Remote, RemoteProject};
+
+use crate::rc::{CoreArrayProvider, CoreArrayProviderInner};
+use crate::string::BnString;
+
+/// A permission granted to a user or a group on a [RemoteProject]
+#[repr(transparent)]
+pub struct Permission {
+    handle: NonNull<BNCollaborationPermission>,
+}
+
+impl Permission {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNCollaborationPermission>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNCollaborationPermission) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNCollaborationPermission {
+        &mut *self.handle.as_ptr()
+    }
+
+    pub fn project(&self) -> RemoteProject {
+        unsafe {
+            RemoteProject::from_raw(
+                NonNull::new(BNCollaborationPermissionGetProject(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    pub fn remote(&self) -> Remote {
+        unsafe {
+            Remote::from_raw(
+                NonNull::new(BNCollaborationPermissionGetRemote(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    /// Unique id
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationPermissionGetId(self.as_raw())) }
+    }
+
+    /// Web api endpoint url
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationPermissionGetUrl(self.as_raw())) }
+    }
+
+    /// Id of the group the permission is granted to, `None` for a user permission
+    pub fn group_id(&self) -> Option<u64> {
+        let value = unsafe { BNCollaborationPermissionGetGroupId(self.as_raw()) };
+        (value != 0).then_some(value)
+    }
+
+    /// Name of the group the permission is granted to, empty for a user permission
+    pub fn group_name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationPermissionGetGroupName(self.as_raw())) }
+    }
+
+    /// Id of the user the permission is granted to, empty for a group permission
+    pub fn user_id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationPermissionGetUserId(self.as_raw())) }
+    }
+
+    /// Username of the user the permission is granted to, empty for a group permission
+    pub fn username(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationPermissionGetUsername(self.as_raw())) }
+    }
+
+    pub fn level(&self) -> PermissionLevel {
+        unsafe { BNCollaborationPermissionGetLevel(self.as_raw()) }
+    }
+
+    /// Set the level, the change is only sent to the server on
+    /// [RemoteProject::push_permission]
+    pub fn set_level(&self, level: PermissionLevel) {
+        unsafe { BNCollaborationPermissionSetLevel(self.as_raw(), level) }
+    }
+
+    pub fn can_view(&self) -> bool {
+        unsafe { BNCollaborationPermissionCanView(self.as_raw()) }
+    }
+
+    pub fn can_edit(&self) -> bool {
+        unsafe { BNCollaborationPermissionCanEdit(self.as_raw()) }
+    }
+
+    pub fn can_admin(&self) -> bool {
+        unsafe { BNCollaborationPermissionCanAdmin(self.as_raw()) }
+    }
+}
+
+impl Clone for Permission {
+    fn clone(&self) -> Self {
+        unsafe {
+            Self::from_raw(
+                NonNull::new(BNNewCollaborationPermissionReference(self.as_raw())).unwrap(),
+            )
+        }
+    }
+}
+
+impl Drop for Permission {
+    fn drop(&mut self) {
+        unsafe { BNFreeCollaborationPermission(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for Permission {
+    type Raw = *mut BNCollaborationPermission;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for Permission {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeCollaborationPermissionList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
@@ -0,0 +1,621 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::{c_char, c_void};
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::time::SystemTime;
+
+use binaryninjacore_sys::*;
+
+use super::{
+    array_or_err, bool_result, cb_progress, cb_progress_nop, time_from_raw, with_str_pairs,
+    Permission, PermissionLevel, Remote, RemoteFile, RemoteFileType, RemoteFolder,
+};
+
+use crate::database::Database;
+use crate::project::Project;
+use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner};
+use crate::string::{BnStrCompatible, BnString};
+use crate::typearchive::TypeArchive;
+
+/// A project on a [Remote], mirrored by a local [Project] once opened
+#[repr(transparent)]
+pub struct RemoteProject {
+    handle: NonNull<BNRemoteProject>,
+}
+
+impl RemoteProject {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNRemoteProject>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNRemoteProject) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNRemoteProject {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Get the RemoteProject for a Database, `None` if the database is not a collaboration
+    /// database
+    pub fn get_for_local_database(database: &Database) -> Result<Option<RemoteProject>, ()> {
+        let mut value = ptr::null_mut();
+        let success = unsafe {
+            BNCollaborationGetRemoteProjectForLocalDatabase(database.as_raw(), &mut value)
+        };
+        if !success {
+            return Err(());
+        }
+        Ok(NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) }))
+    }
+
+    /// Get the RemoteProject for a Type Archive, if any
+    pub fn get_for_type_archive(archive: &TypeArchive) -> Option<RemoteProject> {
+        let value = unsafe { BNCollaborationGetRemoteProjectForLocalTypeArchive(archive.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) })
+    }
+
+    /// The local project mirroring this one, only available once opened
+    pub fn core_project(&self) -> Option<Project> {
+        let value = unsafe { BNRemoteProjectGetCoreProject(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { Project::from_raw(handle) })
+    }
+
+    pub fn is_open(&self) -> bool {
+        unsafe { BNRemoteProjectIsOpen(self.as_raw()) }
+    }
+
+    /// Open the project, creating or updating the local project mirroring it
+    pub fn open(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectOpen(self.as_raw(), Some(cb_progress_nop), ptr::null_mut())
+        })
+    }
+
+    pub fn open_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectOpen(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    pub fn close(&self) {
+        unsafe { BNRemoteProjectClose(self.as_raw()) }
+    }
+
+    /// Default path of the local project mirroring this one
+    pub fn default_path(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationDefaultProjectPath(self.as_raw())) }
+    }
+
+    pub fn remote(&self) -> Remote {
+        unsafe { Remote::from_raw(NonNull::new(BNRemoteProjectGetRemote(self.as_raw())).unwrap()) }
+    }
+
+    /// Web api endpoint url
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteProjectGetUrl(self.as_raw())) }
+    }
+
+    pub fn created(&self) -> SystemTime {
+        time_from_raw(unsafe { BNRemoteProjectGetCreated(self.as_raw()) })
+    }
+
+    pub fn last_modified(&self) -> SystemTime {
+        time_from_raw(unsafe { BNRemoteProjectGetLastModified(self.as_raw()) })
+    }
+
+    /// Unique id
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteProjectGetId(self.as_raw())) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteProjectGetName(self.as_raw())) }
+    }
+
+    /// Set the name, the change is sent to the server immediately
+    pub fn set_name<S: BnStrCompatible>(&self, name: S) -> Result<(), ()> {
+        let name = name.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNRemoteProjectSetName(self.as_raw(), name.as_ref().as_ptr() as *const c_char)
+        })
+    }
+
+    pub fn description(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteProjectGetDescription(self.as_raw())) }
+    }
+
+    /// Set the description, the change is sent to the server immediately
+    pub fn set_description<S: BnStrCompatible>(&self, description: S) -> Result<(), ()> {
+        let description = description.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNRemoteProjectSetDescription(
+                self.as_raw(),
+                description.as_ref().as_ptr() as *const c_char,
+            )
+        })
+    }
+
+    /// Number of files in the project, as reported by the server
+    pub fn received_file_count(&self) -> u64 {
+        unsafe { BNRemoteProjectGetReceivedFileCount(self.as_raw()) }
+    }
+
+    /// Number of folders in the project, as reported by the server
+    pub fn received_folder_count(&self) -> u64 {
+        unsafe { BNRemoteProjectGetReceivedFolderCount(self.as_raw()) }
+    }
+
+    pub fn has_pulled_files(&self) -> bool {
+        unsafe { BNRemoteProjectHasPulledFiles(self.as_raw()) }
+    }
+
+    pub fn has_pulled_folders(&self) -> bool {
+        unsafe { BNRemoteProjectHasPulledFolders(self.as_raw()) }
+    }
+
+    pub fn has_pulled_group_permissions(&self) -> bool {
+        unsafe { BNRemoteProjectHasPulledGroupPermissions(self.as_raw()) }
+    }
+
+    pub fn has_pulled_user_permissions(&self) -> bool {
+        unsafe { BNRemoteProjectHasPulledUserPermissions(self.as_raw()) }
+    }
+
+    /// Whether the current user is an administrator of the project
+    pub fn is_admin(&self) -> bool {
+        unsafe { BNRemoteProjectIsAdmin(self.as_raw()) }
+    }
+
+    /// List of the files of the project, see [RemoteProject::pull_files]
+    pub fn files(&self) -> Result<Array<RemoteFile>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNRemoteProjectGetFiles(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn file_by_id<S: BnStrCompatible>(&self, id: S) -> Option<RemoteFile> {
+        let id = id.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteProjectGetFileById(self.as_raw(), id.as_ref().as_ptr() as *const c_char)
+        };
+        NonNull::new(value).map(|handle| unsafe { RemoteFile::from_raw(handle) })
+    }
+
+    pub fn file_by_name<S: BnStrCompatible>(&self, name: S) -> Option<RemoteFile> {
+        let name = name.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteProjectGetFileByName(self.as_raw(), name.as_ref().as_ptr() as *const c_char)
+        };
+        NonNull::new(value).map(|handle| unsafe { RemoteFile::from_raw(handle) })
+    }
+
+    /// Pull the list of files from the server
+    pub fn pull_files(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectPullFiles(self.as_raw(), Some(cb_progress_nop), ptr::null_mut())
+        })
+    }
+
+    pub fn pull_files_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectPullFiles(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    /// Upload a file to the project
+    ///
+    /// * `filename` - Name of the file on disk
+    /// * `contents` - Contents of the file
+    /// * `name` - Displayed name of the file
+    /// * `description` - Description of the file
+    /// * `folder` - Folder to place the file in
+    /// * `file_type` - Type of the file
+    pub fn create_file<FN, N, D>(
+        &self,
+        filename: FN,
+        contents: &[u8],
+        name: N,
+        description: D,
+        folder: Option<&RemoteFolder>,
+        file_type: RemoteFileType,
+    ) -> Result<RemoteFile, ()>
+    where
+        FN: BnStrCompatible,
+        N: BnStrCompatible,
+        D: BnStrCompatible,
+    {
+        self.create_file_with_progress(
+            filename,
+            contents,
+            name,
+            description,
+            folder,
+            file_type,
+            |_, _| true,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_file_with_progress<FN, N, D, F>(
+        &self,
+        filename: FN,
+        contents: &[u8],
+        name: N,
+        description: D,
+        folder: Option<&RemoteFolder>,
+        file_type: RemoteFileType,
+        mut progress: F,
+    ) -> Result<RemoteFile, ()>
+    where
+        FN: BnStrCompatible,
+        N: BnStrCompatible,
+        D: BnStrCompatible,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let filename = filename.into_bytes_with_nul();
+        let name = name.into_bytes_with_nul();
+        let description = description.into_bytes_with_nul();
+        let folder_ptr = folder
+            .map(|f| unsafe { f.as_raw() as *mut _ })
+            .unwrap_or(ptr::null_mut());
+        let value = unsafe {
+            BNRemoteProjectCreateFile(
+                self.as_raw(),
+                filename.as_ref().as_ptr() as *const c_char,
+                contents.as_ptr() as *mut u8,
+                contents.len(),
+                name.as_ref().as_ptr() as *const c_char,
+                description.as_ref().as_ptr() as *const c_char,
+                folder_ptr,
+                file_type,
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteFile::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Push the changes of a file to the server, `extra_fields` are sent along with the request
+    pub fn push_file<I, K, V>(&self, file: &RemoteFile, extra_fields: I) -> Result<(), ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: BnStrCompatible,
+    {
+        bool_result(with_str_pairs(extra_fields, |keys, values, count| unsafe {
+            BNRemoteProjectPushFile(self.as_raw(), file.as_raw(), keys, values, count)
+        }))
+    }
+
+    pub fn delete_file(&self, file: &RemoteFile) -> Result<(), ()> {
+        bool_result(unsafe { BNRemoteProjectDeleteFile(self.as_raw(), file.as_raw()) })
+    }
+
+    /// List of the folders of the project, see [RemoteProject::pull_folders]
+    pub fn folders(&self) -> Result<Array<RemoteFolder>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNRemoteProjectGetFolders(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn folder_by_id<S: BnStrCompatible>(&self, id: S) -> Option<RemoteFolder> {
+        let id = id.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteProjectGetFolderById(self.as_raw(), id.as_ref().as_ptr() as *const c_char)
+        };
+        NonNull::new(value).map(|handle| unsafe { RemoteFolder::from_raw(handle) })
+    }
+
+    /// Pull the list of folders from the server
+    pub fn pull_folders(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectPullFolders(self.as_raw(), Some(cb_progress_nop), ptr::null_mut())
+        })
+    }
+
+    pub fn pull_folders_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectPullFolders(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    /// Create a folder in the project, inside of `parent` or at the root of the project
+    pub fn create_folder<N: BnStrCompatible, D: BnStrCompatible>(
+        &self,
+        name: N,
+        description: D,
+        parent: Option<&RemoteFolder>,
+    ) -> Result<RemoteFolder, ()> {
+        self.create_folder_with_progress(name, description, parent, |_, _| true)
+    }
+
+    pub fn create_folder_with_progress<N, D, F>(
+        &self,
+        name: N,
+        description: D,
+        parent: Option<&RemoteFolder>,
+        mut progress: F,
+    ) -> Result<RemoteFolder, ()>
+    where
+        N: BnStrCompatible,
+        D: BnStrCompatible,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let name = name.into_bytes_with_nul();
+        let description = description.into_bytes_with_nul();
+        let parent_ptr = parent
+            .map(|p| unsafe { p.as_raw() as *mut _ })
+            .unwrap_or(ptr::null_mut());
+        let value = unsafe {
+            BNRemoteProjectCreateFolder(
+                self.as_raw(),
+                name.as_ref().as_ptr() as *const c_char,
+                description.as_ref().as_ptr() as *const c_char,
+                parent_ptr,
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteFolder::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Push the changes of a folder to the server, `extra_fields` are sent along with the request
+    pub fn push_folder<I, K, V>(&self, folder: &RemoteFolder, extra_fields: I) -> Result<(), ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: BnStrCompatible,
+    {
+        bool_result(with_str_pairs(extra_fields, |keys, values, count| unsafe {
+            BNRemoteProjectPushFolder(self.as_raw(), folder.as_raw(), keys, values, count)
+        }))
+    }
+
+    pub fn delete_folder(&self, folder: &RemoteFolder) -> Result<(), ()> {
+        bool_result(unsafe { BNRemoteProjectDeleteFolder(self.as_raw(), folder.as_raw()) })
+    }
+
+    /// Permissions granted to groups, see [RemoteProject::pull_group_permissions]
+    pub fn group_permissions(&self) -> Result<Array<Permission>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNRemoteProjectGetGroupPermissions(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    /// Permissions granted to users, see [RemoteProject::pull_user_permissions]
+    pub fn user_permissions(&self) -> Result<Array<Permission>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNRemoteProjectGetUserPermissions(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn permission_by_id<S: BnStrCompatible>(&self, id: S) -> Option<Permission> {
+        let id = id.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteProjectGetPermissionById(self.as_raw(), id.as_ref().as_ptr() as *const c_char)
+        };
+        NonNull::new(value).map(|handle| unsafe { Permission::from_raw(handle) })
+    }
+
+    /// Pull the list of group permissions from the server
+    pub fn pull_group_permissions(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectPullGroupPermissions(
+                self.as_raw(),
+                Some(cb_progress_nop),
+                ptr::null_mut(),
+            )
+        })
+    }
+
+    pub fn pull_group_permissions_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectPullGroupPermissions(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    /// Pull the list of user permissions from the server
+    pub fn pull_user_permissions(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectPullUserPermissions(
+                self.as_raw(),
+                Some(cb_progress_nop),
+                ptr::null_mut(),
+            )
+        })
+    }
+
+    pub fn pull_user_permissions_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemoteProjectPullUserPermissions(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    /// Grant a permission on the project to the group `group_id`
+    pub fn create_group_permission(
+        &self,
+        group_id: i64,
+        level: PermissionLevel,
+    ) -> Result<Permission, ()> {
+        self.create_group_permission_with_progress(group_id, level, |_, _| true)
+    }
+
+    pub fn create_group_permission_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        group_id: i64,
+        level: PermissionLevel,
+        mut progress: F,
+    ) -> Result<Permission, ()> {
+        let value = unsafe {
+            BNRemoteProjectCreateGroupPermission(
+                self.as_raw(),
+                group_id,
+                level,
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { Permission::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Grant a permission on the project to the user `user_id`
+    pub fn create_user_permission<S: BnStrCompatible>(
+        &self,
+        user_id: S,
+        level: PermissionLevel,
+    ) -> Result<Permission, ()> {
+        self.create_user_permission_with_progress(user_id, level, |_, _| true)
+    }
+
+    pub fn create_user_permission_with_progress<S, F>(
+        &self,
+        user_id: S,
+        level: PermissionLevel,
+        mut progress: F,
+    ) -> Result<Permission, ()>
+    where
+        S: BnStrCompatible,
+        F: FnMut(usize, usize) -> bool,
+    {
+        let user_id = user_id.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteProjectCreateUserPermission(
+                self.as_raw(),
+                user_id.as_ref().as_ptr() as *const c_char,
+                level,
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { Permission::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Push the changes of a permission to the server, `extra_fields` are sent along with the
+    /// request
+    pub fn push_permission<I, K, V>(
+        &self,
+        permission: &Permission,
+        extra_fields: I,
+    ) -> Result<(), ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: BnStrCompatible,
+    {
+        bool_result(with_str_pairs(extra_fields, |keys, values, count| unsafe {
+            BNRemoteProjectPushPermission(self.as_raw(), permission.as_raw(), keys, values, count)
+        }))
+    }
+
+    pub fn delete_permission(&self, permission: &Permission) -> Result<(), ()> {
+        bool_result(unsafe { BNRemoteProjectDeletePermission(self.as_raw(), permission.as_raw()) })
+    }
+
+    pub fn can_user_view<S: BnStrCompatible>(&self, username: S) -> bool {
+        let username = username.into_bytes_with_nul();
+        unsafe {
+            BNRemoteProjectCanUserView(self.as_raw(), username.as_ref().as_ptr() as *const c_char)
+        }
+    }
+
+    pub fn can_user_edit<S: BnStrCompatible>(&self, username: S) -> bool {
+        let username = username.into_bytes_with_nul();
+        unsafe {
+            BNRemoteProjectCanUserEdit(self.as_raw(), username.as_ref().as_ptr() as *const c_char)
+        }
+    }
+
+    pub fn can_user_admin<S: BnStrCompatible>(&self, username: S) -> bool {
+        let username = username.into_bytes_with_nul();
+        unsafe {
+            BNRemoteProjectCanUserAdmin(self.as_raw(), username.as_ref().as_ptr() as *const c_char)
+        }
+    }
+}
+
+impl Clone for RemoteProject {
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw(NonNull::new(BNNewRemoteProjectReference(self.as_raw())).unwrap()) }
+    }
+}
+
+impl Drop for RemoteProject {
+    fn drop(&mut self) {
+        unsafe { BNFreeRemoteProject(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for RemoteProject {
+    type Raw = *mut BNRemoteProject;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for RemoteProject {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeRemoteProjectList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
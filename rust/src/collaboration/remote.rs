@@ -0,0 +1,593 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::{c_char, c_void};
+use std::mem;
+use std::ptr::{self, NonNull};
+
+use binaryninjacore_sys::*;
+
+use super::{
+    array_or_err, bool_result, cb_progress, cb_progress_nop, optional_string, with_str_pairs,
+    RemoteGroup, RemoteProject, RemoteUser,
+};
+
+use crate::database::Database;
+use crate::project::Project;
+use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner};
+use crate::string::{BnStrCompatible, BnString};
+use crate::typearchive::TypeArchive;
+
+/// A connection to an Enterprise server
+#[repr(transparent)]
+pub struct Remote {
+    handle: NonNull<BNRemote>,
+}
+
+impl Remote {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNRemote>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNRemote) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNRemote {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Create a Remote and add it to the list of known remotes (saved to Settings)
+    pub fn new<N: BnStrCompatible, A: BnStrCompatible>(name: N, address: A) -> Result<Self, ()> {
+        let name = name.into_bytes_with_nul();
+        let address = address.into_bytes_with_nul();
+        let result = unsafe {
+            BNCollaborationCreateRemote(
+                name.as_ref().as_ptr() as *const c_char,
+                address.as_ref().as_ptr() as *const c_char,
+            )
+        };
+        NonNull::new(result)
+            .map(|handle| unsafe { Self::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Get the Remote for a Database, `None` if the database is not a collaboration database
+    pub fn get_for_local_database(database: &Database) -> Result<Option<Remote>, ()> {
+        let mut value = ptr::null_mut();
+        if !unsafe { BNCollaborationGetRemoteForLocalDatabase(database.as_raw(), &mut value) } {
+            return Err(());
+        }
+        Ok(NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) }))
+    }
+
+    /// Get the Remote for a Type Archive, if any
+    pub fn get_for_type_archive(archive: &TypeArchive) -> Option<Remote> {
+        let value = unsafe { BNCollaborationGetRemoteForLocalTypeArchive(archive.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) })
+    }
+
+    /// Unique id of the Remote, only available after the metadata is loaded
+    pub fn unique_id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteGetUniqueId(self.as_raw())) }
+    }
+
+    /// Assigned name of the Remote
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteGetName(self.as_raw())) }
+    }
+
+    /// Address of the server
+    pub fn address(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteGetAddress(self.as_raw())) }
+    }
+
+    /// Whether the metadata of the server (id, version, ...) has been loaded
+    pub fn has_loaded_metadata(&self) -> bool {
+        unsafe { BNRemoteHasLoadedMetadata(self.as_raw()) }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        unsafe { BNRemoteIsConnected(self.as_raw()) }
+    }
+
+    /// Username used to connect to the server
+    pub fn username(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteGetUsername(self.as_raw())) }
+    }
+
+    /// Token used to connect to the server
+    pub fn token(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteGetToken(self.as_raw())) }
+    }
+
+    /// Version of the server protocol, only available after the metadata is loaded
+    pub fn server_version(&self) -> i32 {
+        unsafe { BNRemoteGetServerVersion(self.as_raw()) }
+    }
+
+    /// Build id of the server, only available after the metadata is loaded
+    pub fn server_build_id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRemoteGetServerBuildId(self.as_raw())) }
+    }
+
+    /// List of the authentication backends supported by the server, as `(id, name)` pairs
+    pub fn auth_backends(&self) -> Result<Vec<(String, String)>, ()> {
+        let mut backend_ids = ptr::null_mut();
+        let mut backend_names = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            BNRemoteGetAuthBackends(
+                self.as_raw(),
+                &mut backend_ids,
+                &mut backend_names,
+                &mut count,
+            )
+        };
+        if !success {
+            return Err(());
+        }
+        let backend_ids = unsafe { Array::<BnString>::new(backend_ids, count, ()) };
+        let backend_names = unsafe { Array::<BnString>::new(backend_names, count, ()) };
+        Ok(backend_ids
+            .iter()
+            .zip(backend_names.iter())
+            .map(|(id, name)| (id.to_string(), name.to_string()))
+            .collect())
+    }
+
+    pub fn has_pulled_projects(&self) -> bool {
+        unsafe { BNRemoteHasPulledProjects(self.as_raw()) }
+    }
+
+    pub fn has_pulled_users(&self) -> bool {
+        unsafe { BNRemoteHasPulledUsers(self.as_raw()) }
+    }
+
+    pub fn has_pulled_groups(&self) -> bool {
+        unsafe { BNRemoteHasPulledGroups(self.as_raw()) }
+    }
+
+    /// Whether the current user is an administrator of the server
+    pub fn is_admin(&self) -> bool {
+        unsafe { BNRemoteIsAdmin(self.as_raw()) }
+    }
+
+    /// Whether the server is an Enterprise server, as opposed to a Collaboration server
+    pub fn is_enterprise(&self) -> bool {
+        unsafe { BNRemoteIsEnterprise(self.as_raw()) }
+    }
+
+    /// Load the metadata of the server, such as its id and version
+    pub fn load_metadata(&self) -> Result<(), ()> {
+        bool_result(unsafe { BNRemoteLoadMetadata(self.as_raw()) })
+    }
+
+    /// Request an authentication token using a `username` and `password`
+    pub fn request_authentication_token<U: BnStrCompatible, P: BnStrCompatible>(
+        &self,
+        username: U,
+        password: P,
+    ) -> Option<BnString> {
+        let username = username.into_bytes_with_nul();
+        let password = password.into_bytes_with_nul();
+        unsafe {
+            optional_string(BNRemoteRequestAuthenticationToken(
+                self.as_raw(),
+                username.as_ref().as_ptr() as *const c_char,
+                password.as_ref().as_ptr() as *const c_char,
+            ))
+        }
+    }
+
+    /// Connect to the server with a `username` and a `token`, see
+    /// [Remote::request_authentication_token]
+    pub fn connect<U: BnStrCompatible, T: BnStrCompatible>(
+        &self,
+        username: U,
+        token: T,
+    ) -> Result<(), ()> {
+        let username = username.into_bytes_with_nul();
+        let token = token.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNRemoteConnect(
+                self.as_raw(),
+                username.as_ref().as_ptr() as *const c_char,
+                token.as_ref().as_ptr() as *const c_char,
+            )
+        })
+    }
+
+    pub fn disconnect(&self) -> Result<(), ()> {
+        bool_result(unsafe { BNRemoteDisconnect(self.as_raw()) })
+    }
+
+    /// List of the projects of the server, see [Remote::pull_projects]
+    pub fn projects(&self) -> Result<Array<RemoteProject>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNRemoteGetProjects(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn project_by_id<S: BnStrCompatible>(&self, id: S) -> Option<RemoteProject> {
+        let id = id.into_bytes_with_nul();
+        let value =
+            unsafe { BNRemoteGetProjectById(self.as_raw(), id.as_ref().as_ptr() as *const c_char) };
+        NonNull::new(value).map(|handle| unsafe { RemoteProject::from_raw(handle) })
+    }
+
+    pub fn project_by_name<S: BnStrCompatible>(&self, name: S) -> Option<RemoteProject> {
+        let name = name.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteGetProjectByName(self.as_raw(), name.as_ref().as_ptr() as *const c_char)
+        };
+        NonNull::new(value).map(|handle| unsafe { RemoteProject::from_raw(handle) })
+    }
+
+    /// Pull the list of projects from the server
+    pub fn pull_projects(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemotePullProjects(self.as_raw(), Some(cb_progress_nop), ptr::null_mut())
+        })
+    }
+
+    /// Pull the list of projects from the server, aborted if `progress` returns `false`
+    pub fn pull_projects_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemotePullProjects(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    pub fn create_project<N: BnStrCompatible, D: BnStrCompatible>(
+        &self,
+        name: N,
+        description: D,
+    ) -> Result<RemoteProject, ()> {
+        let name = name.into_bytes_with_nul();
+        let description = description.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteCreateProject(
+                self.as_raw(),
+                name.as_ref().as_ptr() as *const c_char,
+                description.as_ref().as_ptr() as *const c_char,
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteProject::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Upload a local project and all its files to the server
+    pub fn import_local_project(&self, project: &Project) -> Result<RemoteProject, ()> {
+        let value = unsafe {
+            BNRemoteImportLocalProject(
+                self.as_raw(),
+                project.as_raw(),
+                Some(cb_progress_nop),
+                ptr::null_mut(),
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteProject::from_raw(handle) })
+            .ok_or(())
+    }
+
+    pub fn import_local_project_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        project: &Project,
+        mut progress: F,
+    ) -> Result<RemoteProject, ()> {
+        let value = unsafe {
+            BNRemoteImportLocalProject(
+                self.as_raw(),
+                project.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteProject::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Push the changes of a project to the server, `extra_fields` are sent along with the
+    /// request
+    pub fn push_project<I, K, V>(&self, project: &RemoteProject, extra_fields: I) -> Result<(), ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: BnStrCompatible,
+    {
+        bool_result(with_str_pairs(extra_fields, |keys, values, count| unsafe {
+            BNRemotePushProject(self.as_raw(), project.as_raw(), keys, values, count)
+        }))
+    }
+
+    pub fn delete_project(&self, project: &RemoteProject) -> Result<(), ()> {
+        bool_result(unsafe { BNRemoteDeleteProject(self.as_raw(), project.as_raw()) })
+    }
+
+    /// List of the groups of the server, see [Remote::pull_groups]
+    pub fn groups(&self) -> Result<Array<RemoteGroup>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNRemoteGetGroups(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn group_by_id(&self, id: u64) -> Option<RemoteGroup> {
+        let value = unsafe { BNRemoteGetGroupById(self.as_raw(), id) };
+        NonNull::new(value).map(|handle| unsafe { RemoteGroup::from_raw(handle) })
+    }
+
+    pub fn group_by_name<S: BnStrCompatible>(&self, name: S) -> Option<RemoteGroup> {
+        let name = name.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteGetGroupByName(self.as_raw(), name.as_ref().as_ptr() as *const c_char)
+        };
+        NonNull::new(value).map(|handle| unsafe { RemoteGroup::from_raw(handle) })
+    }
+
+    /// Search the groups whose name starts with `prefix`, returns `(id, name)` pairs
+    pub fn search_groups<S: BnStrCompatible>(&self, prefix: S) -> Result<Vec<(u64, String)>, ()> {
+        let prefix = prefix.into_bytes_with_nul();
+        let mut group_ids = ptr::null_mut();
+        let mut group_names = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            BNRemoteSearchGroups(
+                self.as_raw(),
+                prefix.as_ref().as_ptr() as *const c_char,
+                &mut group_ids,
+                &mut group_names,
+                &mut count,
+            )
+        };
+        if !success {
+            return Err(());
+        }
+        let group_names = unsafe { Array::<BnString>::new(group_names, count, ()) };
+        let result = unsafe { std::slice::from_raw_parts(group_ids, count) }
+            .iter()
+            .zip(group_names.iter())
+            .map(|(id, name)| (*id, name.to_string()))
+            .collect();
+        unsafe { BNCollaborationFreeIdList(group_ids, count) };
+        Ok(result)
+    }
+
+    /// Pull the list of groups from the server
+    pub fn pull_groups(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemotePullGroups(self.as_raw(), Some(cb_progress_nop), ptr::null_mut())
+        })
+    }
+
+    pub fn pull_groups_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemotePullGroups(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    /// Create a group with the given members
+    pub fn create_group<N, I, U>(&self, name: N, usernames: I) -> Result<RemoteGroup, ()>
+    where
+        N: BnStrCompatible,
+        I: IntoIterator<Item = U>,
+        U: BnStrCompatible,
+    {
+        let name = name.into_bytes_with_nul();
+        let usernames: Vec<_> = usernames
+            .into_iter()
+            .map(|u| u.into_bytes_with_nul())
+            .collect();
+        let mut usernames_ptr = super::str_ptrs(&usernames);
+        let value = unsafe {
+            BNRemoteCreateGroup(
+                self.as_raw(),
+                name.as_ref().as_ptr() as *const c_char,
+                usernames_ptr.as_mut_ptr(),
+                usernames_ptr.len(),
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteGroup::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Push the changes of a group to the server, `extra_fields` are sent along with the request
+    pub fn push_group<I, K, V>(&self, group: &RemoteGroup, extra_fields: I) -> Result<(), ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: BnStrCompatible,
+    {
+        bool_result(with_str_pairs(extra_fields, |keys, values, count| unsafe {
+            BNRemotePushGroup(self.as_raw(), group.as_raw(), keys, values, count)
+        }))
+    }
+
+    pub fn delete_group(&self, group: &RemoteGroup) -> Result<(), ()> {
+        bool_result(unsafe { BNRemoteDeleteGroup(self.as_raw(), group.as_raw()) })
+    }
+
+    /// List of the users of the server, see [Remote::pull_users]
+    pub fn users(&self) -> Result<Array<RemoteUser>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNRemoteGetUsers(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn user_by_id<S: BnStrCompatible>(&self, id: S) -> Option<RemoteUser> {
+        let id = id.into_bytes_with_nul();
+        let value =
+            unsafe { BNRemoteGetUserById(self.as_raw(), id.as_ref().as_ptr() as *const c_char) };
+        NonNull::new(value).map(|handle| unsafe { RemoteUser::from_raw(handle) })
+    }
+
+    pub fn user_by_username<S: BnStrCompatible>(&self, username: S) -> Option<RemoteUser> {
+        let username = username.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteGetUserByUsername(self.as_raw(), username.as_ref().as_ptr() as *const c_char)
+        };
+        NonNull::new(value).map(|handle| unsafe { RemoteUser::from_raw(handle) })
+    }
+
+    /// The user connected to the server, if any
+    pub fn current_user(&self) -> Option<RemoteUser> {
+        let value = unsafe { BNRemoteGetCurrentUser(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { RemoteUser::from_raw(handle) })
+    }
+
+    /// Search the users whose username starts with `prefix`, returns `(id, username)` pairs
+    pub fn search_users<S: BnStrCompatible>(&self, prefix: S) -> Result<Vec<(String, String)>, ()> {
+        let prefix = prefix.into_bytes_with_nul();
+        let mut user_ids = ptr::null_mut();
+        let mut usernames = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            BNRemoteSearchUsers(
+                self.as_raw(),
+                prefix.as_ref().as_ptr() as *const c_char,
+                &mut user_ids,
+                &mut usernames,
+                &mut count,
+            )
+        };
+        if !success {
+            return Err(());
+        }
+        let user_ids = unsafe { Array::<BnString>::new(user_ids, count, ()) };
+        let usernames = unsafe { Array::<BnString>::new(usernames, count, ()) };
+        Ok(user_ids
+            .iter()
+            .zip(usernames.iter())
+            .map(|(id, username)| (id.to_string(), username.to_string()))
+            .collect())
+    }
+
+    /// Pull the list of users from the server
+    pub fn pull_users(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemotePullUsers(self.as_raw(), Some(cb_progress_nop), ptr::null_mut())
+        })
+    }
+
+    pub fn pull_users_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNRemotePullUsers(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    /// Create a user, only available to administrators
+    ///
+    /// * `group_ids` - Ids of the groups the user is a member of
+    /// * `user_permission_ids` - Ids of the permissions granted to the user
+    pub fn create_user<U, E, P>(
+        &self,
+        username: U,
+        email: E,
+        is_active: bool,
+        password: P,
+        group_ids: &[u64],
+        user_permission_ids: &[u64],
+    ) -> Result<RemoteUser, ()>
+    where
+        U: BnStrCompatible,
+        E: BnStrCompatible,
+        P: BnStrCompatible,
+    {
+        let username = username.into_bytes_with_nul();
+        let email = email.into_bytes_with_nul();
+        let password = password.into_bytes_with_nul();
+        let value = unsafe {
+            BNRemoteCreateUser(
+                self.as_raw(),
+                username.as_ref().as_ptr() as *const c_char,
+                email.as_ref().as_ptr() as *const c_char,
+                is_active,
+                password.as_ref().as_ptr() as *const c_char,
+                group_ids.as_ptr(),
+                group_ids.len(),
+                user_permission_ids.as_ptr(),
+                user_permission_ids.len(),
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteUser::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Push the changes of a user to the server, `extra_fields` are sent along with the request
+    pub fn push_user<I, K, V>(&self, user: &RemoteUser, extra_fields: I) -> Result<(), ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: BnStrCompatible,
+    {
+        bool_result(with_str_pairs(extra_fields, |keys, values, count| unsafe {
+            BNRemotePushUser(self.as_raw(), user.as_raw(), keys, values, count)
+        }))
+    }
+}
+
+impl Clone for Remote {
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw(NonNull::new(BNNewRemoteReference(self.as_raw())).unwrap()) }
+    }
+}
+
+impl Drop for Remote {
+    fn drop(&mut self) {
+        unsafe { BNFreeRemote(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for Remote {
+    type Raw = *mut BNRemote;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for Remote {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeRemoteList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
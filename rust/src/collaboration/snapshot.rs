@@ -0,0 +1,490 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::{c_char, c_void};
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::time::SystemTime;
+
+use binaryninjacore_sys::*;
+
+use super::{
+    array_or_err, bool_result, cb_progress, cb_progress_nop, optional_string, take_core_buffer,
+    time_from_raw, Remote, RemoteFile, RemoteProject,
+};
+
+use crate::database::{Database, Snapshot};
+use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner};
+use crate::string::{BnStrCompatible, BnString};
+use crate::typearchive::TypeArchive;
+
+type DownloadFunction = unsafe extern "C" fn(
+    *mut BNCollaborationSnapshot,
+    BNProgressFunction,
+    *mut c_void,
+    *mut *mut u8,
+    *mut usize,
+) -> bool;
+
+/// A snapshot of a [RemoteFile], mirrored by a local [Snapshot] of the database of the file
+#[repr(transparent)]
+pub struct RemoteSnapshot {
+    handle: NonNull<BNCollaborationSnapshot>,
+}
+
+impl RemoteSnapshot {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNCollaborationSnapshot>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNCollaborationSnapshot) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNCollaborationSnapshot {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Get the RemoteSnapshot mirrored by a local snapshot, if any
+    pub fn get_for_local_snapshot(snapshot: &Snapshot) -> Result<Option<RemoteSnapshot>, ()> {
+        let mut value = ptr::null_mut();
+        if !unsafe { BNCollaborationGetRemoteSnapshotFromLocal(snapshot.as_raw(), &mut value) } {
+            return Err(());
+        }
+        Ok(NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) }))
+    }
+
+    /// Get the RemoteSnapshot mirrored by the snapshot `snapshot_id` of a Type Archive, if any
+    pub fn get_for_type_archive<S: BnStrCompatible>(
+        archive: &TypeArchive,
+        snapshot_id: S,
+    ) -> Option<RemoteSnapshot> {
+        let snapshot_id = snapshot_id.into_bytes_with_nul();
+        let value = unsafe {
+            BNCollaborationGetRemoteSnapshotFromLocalTypeArchive(
+                archive.as_raw(),
+                snapshot_id.as_ref().as_ptr() as *const c_char,
+            )
+        };
+        NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) })
+    }
+
+    /// The local snapshot of `database` mirroring this one, if any
+    pub fn local_snapshot(&self, database: &Database) -> Result<Option<Snapshot>, ()> {
+        let mut value = ptr::null_mut();
+        let success = unsafe {
+            BNCollaborationGetLocalSnapshotFromRemote(self.as_raw(), database.as_raw(), &mut value)
+        };
+        if !success {
+            return Err(());
+        }
+        Ok(NonNull::new(value).map(|handle| unsafe { Snapshot::from_raw(handle) }))
+    }
+
+    /// Id of the snapshot of `archive` mirroring this one, if any
+    pub fn local_type_archive_snapshot(&self, archive: &TypeArchive) -> Option<BnString> {
+        unsafe {
+            optional_string(BNCollaborationGetLocalSnapshotFromRemoteTypeArchive(
+                self.as_raw(),
+                archive.as_raw(),
+            ))
+        }
+    }
+
+    /// Record that the local `snapshot` mirrors this one
+    pub fn assign_local_snapshot(&self, snapshot: &Snapshot) -> Result<(), ()> {
+        bool_result(unsafe { BNCollaborationAssignSnapshotMap(snapshot.as_raw(), self.as_raw()) })
+    }
+
+    pub fn file(&self) -> RemoteFile {
+        unsafe {
+            RemoteFile::from_raw(
+                NonNull::new(BNCollaborationSnapshotGetFile(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    pub fn project(&self) -> RemoteProject {
+        unsafe {
+            RemoteProject::from_raw(
+                NonNull::new(BNCollaborationSnapshotGetProject(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    pub fn remote(&self) -> Remote {
+        unsafe {
+            Remote::from_raw(NonNull::new(BNCollaborationSnapshotGetRemote(self.as_raw())).unwrap())
+        }
+    }
+
+    /// Web api endpoint url
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetUrl(self.as_raw())) }
+    }
+
+    /// Unique id
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetId(self.as_raw())) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetName(self.as_raw())) }
+    }
+
+    /// First line of the name
+    pub fn title(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetTitle(self.as_raw())) }
+    }
+
+    /// Remaining lines of the name
+    pub fn description(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetDescription(self.as_raw())) }
+    }
+
+    /// Id of the author
+    pub fn author(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetAuthor(self.as_raw())) }
+    }
+
+    pub fn author_username(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetAuthorUsername(self.as_raw())) }
+    }
+
+    pub fn created(&self) -> SystemTime {
+        time_from_raw(unsafe { BNCollaborationSnapshotGetCreated(self.as_raw()) })
+    }
+
+    pub fn last_modified(&self) -> SystemTime {
+        time_from_raw(unsafe { BNCollaborationSnapshotGetLastModified(self.as_raw()) })
+    }
+
+    /// Hash of the snapshot contents
+    pub fn hash(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetHash(self.as_raw())) }
+    }
+
+    /// Hash of the file contents at the time of the snapshot
+    pub fn snapshot_file_hash(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationSnapshotGetSnapshotFileHash(self.as_raw())) }
+    }
+
+    pub fn has_pulled_undo_entries(&self) -> bool {
+        unsafe { BNCollaborationSnapshotHasPulledUndoEntries(self.as_raw()) }
+    }
+
+    /// Whether the snapshot is finalized, and can no longer receive undo entries
+    pub fn is_finalized(&self) -> bool {
+        unsafe { BNCollaborationSnapshotIsFinalized(self.as_raw()) }
+    }
+
+    pub fn parent_ids(&self) -> Result<Array<BnString>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNCollaborationSnapshotGetParentIds(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn child_ids(&self) -> Result<Array<BnString>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNCollaborationSnapshotGetChildIds(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    /// Build id of the core that generated the analysis cache
+    pub fn analysis_cache_build_id(&self) -> u64 {
+        unsafe { BNCollaborationSnapshotGetAnalysisCacheBuildId(self.as_raw()) }
+    }
+
+    pub fn parents(&self) -> Result<Array<RemoteSnapshot>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNCollaborationSnapshotGetParents(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn children(&self) -> Result<Array<RemoteSnapshot>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNCollaborationSnapshotGetChildren(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    /// List of the undo entries of the snapshot, see [RemoteSnapshot::pull_undo_entries]
+    pub fn undo_entries(&self) -> Result<Array<RemoteUndoEntry>, ()> {
+        let mut count = 0;
+        let value = unsafe { BNCollaborationSnapshotGetUndoEntries(self.as_raw(), &mut count) };
+        unsafe { array_or_err(value, count, ()) }
+    }
+
+    pub fn undo_entry_by_id(&self, id: u64) -> Option<RemoteUndoEntry> {
+        let value = unsafe { BNCollaborationSnapshotGetUndoEntryById(self.as_raw(), id) };
+        NonNull::new(value).map(|handle| unsafe { RemoteUndoEntry::from_raw(handle) })
+    }
+
+    /// Pull the list of undo entries from the server
+    pub fn pull_undo_entries(&self) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNCollaborationSnapshotPullUndoEntries(
+                self.as_raw(),
+                Some(cb_progress_nop),
+                ptr::null_mut(),
+            )
+        })
+    }
+
+    pub fn pull_undo_entries_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        mut progress: F,
+    ) -> Result<(), ()> {
+        bool_result(unsafe {
+            BNCollaborationSnapshotPullUndoEntries(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        })
+    }
+
+    /// Create an undo entry in the snapshot, the snapshot must not be finalized
+    pub fn create_undo_entry<S: BnStrCompatible>(
+        &self,
+        parent: Option<u64>,
+        data: S,
+    ) -> Result<RemoteUndoEntry, ()> {
+        let data = data.into_bytes_with_nul();
+        let value = unsafe {
+            BNCollaborationSnapshotCreateUndoEntry(
+                self.as_raw(),
+                parent.is_some(),
+                parent.unwrap_or(0),
+                data.as_ref().as_ptr() as *const c_char,
+            )
+        };
+        NonNull::new(value)
+            .map(|handle| unsafe { RemoteUndoEntry::from_raw(handle) })
+            .ok_or(())
+    }
+
+    /// Finalize the snapshot, after which no undo entries can be added
+    pub fn finalize(&self) -> Result<(), ()> {
+        bool_result(unsafe { BNCollaborationSnapshotFinalize(self.as_raw()) })
+    }
+
+    fn download_with<F: FnMut(usize, usize) -> bool>(
+        &self,
+        function: DownloadFunction,
+        mut progress: F,
+    ) -> Result<Vec<u8>, ()> {
+        let mut data = ptr::null_mut();
+        let mut size = 0;
+        let success = unsafe {
+            function(
+                self.as_raw(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+                &mut data,
+                &mut size,
+            )
+        };
+        if !success {
+            return Err(());
+        }
+        Ok(unsafe { take_core_buffer(data, size) })
+    }
+
+    /// Download the contents of the file at the time of the snapshot
+    pub fn download_snapshot_file(&self) -> Result<Vec<u8>, ()> {
+        self.download_with(BNCollaborationSnapshotDownloadSnapshotFile, |_, _| true)
+    }
+
+    pub fn download_snapshot_file_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        progress: F,
+    ) -> Result<Vec<u8>, ()> {
+        self.download_with(BNCollaborationSnapshotDownloadSnapshotFile, progress)
+    }
+
+    /// Download the contents of the snapshot, compatible with [crate::database::KeyValueStore]
+    pub fn download(&self) -> Result<Vec<u8>, ()> {
+        self.download_with(BNCollaborationSnapshotDownload, |_, _| true)
+    }
+
+    pub fn download_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        progress: F,
+    ) -> Result<Vec<u8>, ()> {
+        self.download_with(BNCollaborationSnapshotDownload, progress)
+    }
+
+    /// Download the analysis cache of the snapshot, compatible with
+    /// [crate::database::KeyValueStore]
+    pub fn download_analysis_cache(&self) -> Result<Vec<u8>, ()> {
+        self.download_with(BNCollaborationSnapshotDownloadAnalysisCache, |_, _| true)
+    }
+
+    pub fn download_analysis_cache_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        progress: F,
+    ) -> Result<Vec<u8>, ()> {
+        self.download_with(BNCollaborationSnapshotDownloadAnalysisCache, progress)
+    }
+}
+
+impl Clone for RemoteSnapshot {
+    fn clone(&self) -> Self {
+        unsafe {
+            Self::from_raw(
+                NonNull::new(BNNewCollaborationSnapshotReference(self.as_raw())).unwrap(),
+            )
+        }
+    }
+}
+
+impl Drop for RemoteSnapshot {
+    fn drop(&mut self) {
+        unsafe { BNFreeCollaborationSnapshot(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for RemoteSnapshot {
+    type Raw = *mut BNCollaborationSnapshot;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for RemoteSnapshot {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeCollaborationSnapshotList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
+
+/// An undo entry of a [RemoteSnapshot]
+#[repr(transparent)]
+pub struct RemoteUndoEntry {
+    handle: NonNull<BNCollaborationUndoEntry>,
+}
+
+impl RemoteUndoEntry {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNCollaborationUndoEntry>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNCollaborationUndoEntry) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNCollaborationUndoEntry {
+        &mut *self.handle.as_ptr()
+    }
+
+    pub fn snapshot(&self) -> RemoteSnapshot {
+        unsafe {
+            RemoteSnapshot::from_raw(
+                NonNull::new(BNCollaborationUndoEntryGetSnapshot(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    pub fn file(&self) -> RemoteFile {
+        unsafe {
+            RemoteFile::from_raw(
+                NonNull::new(BNCollaborationUndoEntryGetFile(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    pub fn project(&self) -> RemoteProject {
+        unsafe {
+            RemoteProject::from_raw(
+                NonNull::new(BNCollaborationUndoEntryGetProject(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    pub fn remote(&self) -> Remote {
+        unsafe {
+            Remote::from_raw(
+                NonNull::new(BNCollaborationUndoEntryGetRemote(self.as_raw())).unwrap(),
+            )
+        }
+    }
+
+    /// Web api endpoint url
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationUndoEntryGetUrl(self.as_raw())) }
+    }
+
+    /// Unique id
+    pub fn id(&self) -> u64 {
+        unsafe { BNCollaborationUndoEntryGetId(self.as_raw()) }
+    }
+
+    /// Id of the parent undo entry, if any
+    pub fn parent_id(&self) -> Option<u64> {
+        let mut value = 0;
+        unsafe { BNCollaborationUndoEntryGetParentId(self.as_raw(), &mut value) }.then_some(value)
+    }
+
+    pub fn parent(&self) -> Option<RemoteUndoEntry> {
+        let value = unsafe { BNCollaborationUndoEntryGetParent(self.as_raw()) };
+        NonNull::new(value).map(|handle| unsafe { Self::from_raw(handle) })
+    }
+
+    /// Serialized data of the undo entry
+    pub fn data(&self) -> Result<BnString, ()> {
+        let mut value = ptr::null_mut();
+        if !unsafe { BNCollaborationUndoEntryGetData(self.as_raw(), &mut value) } {
+            return Err(());
+        }
+        unsafe { optional_string(value) }.ok_or(())
+    }
+}
+
+impl Clone for RemoteUndoEntry {
+    fn clone(&self) -> Self {
+        unsafe {
+            Self::from_raw(
+                NonNull::new(BNNewCollaborationUndoEntryReference(self.as_raw())).unwrap(),
+            )
+        }
+    }
+}
+
+impl Drop for RemoteUndoEntry {
+    fn drop(&mut self) {
+        unsafe { BNFreeCollaborationUndoEntry(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for RemoteUndoEntry {
+    type Raw = *mut BNCollaborationUndoEntry;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for RemoteUndoEntry {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeCollaborationUndoEntryList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
@@ -0,0 +1,578 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void};
+use std::ptr::{self, NonNull};
+
+use binaryninjacore_sys::*;
+
+use super::{
+    bool_result, c_str_to_string, cb_progress, cb_progress_nop, optional_string,
+    AnalysisMergeConflict, Changeset, RemoteFile, RemoteFolder, RemoteProject,
+    TypeArchiveMergeConflict,
+};
+
+use crate::database::{Database, Snapshot};
+use crate::filemetadata::FileMetadata;
+use crate::project::ProjectFile;
+use crate::string::{BnStrCompatible, BnString};
+use crate::typearchive::TypeArchive;
+
+/// Resolve the conflicts found while merging snapshots of a database
+pub trait DatabaseConflictHandler {
+    /// Receives the conflicts indexed by their key, each conflict should be resolved with
+    /// [AnalysisMergeConflict::success]. Return `false` to abort the merge.
+    fn handle_conflicts(&mut self, conflicts: HashMap<String, AnalysisMergeConflict>) -> bool;
+}
+
+impl<F> DatabaseConflictHandler for F
+where
+    F: FnMut(HashMap<String, AnalysisMergeConflict>) -> bool,
+{
+    fn handle_conflicts(&mut self, conflicts: HashMap<String, AnalysisMergeConflict>) -> bool {
+        self(conflicts)
+    }
+}
+
+/// Resolve the conflicts found while merging snapshots of a type archive
+pub trait TypeArchiveConflictHandler {
+    /// Each conflict should be resolved with [TypeArchiveMergeConflict::success]. Return `false`
+    /// to abort the merge.
+    fn handle_conflicts(&mut self, conflicts: Vec<TypeArchiveMergeConflict>) -> bool;
+}
+
+impl<F> TypeArchiveConflictHandler for F
+where
+    F: FnMut(Vec<TypeArchiveMergeConflict>) -> bool,
+{
+    fn handle_conflicts(&mut self, conflicts: Vec<TypeArchiveMergeConflict>) -> bool {
+        self(conflicts)
+    }
+}
+
+/// Name a [Changeset] before it is pushed to the server, see [Changeset::set_name]
+pub trait NameChangeset {
+    /// Return `false` to abort the push
+    fn name_changeset(&mut self, changeset: &Changeset) -> bool;
+}
+
+impl<F> NameChangeset for F
+where
+    F: FnMut(&Changeset) -> bool,
+{
+    fn name_changeset(&mut self, changeset: &Changeset) -> bool {
+        self(changeset)
+    }
+}
+
+/// Merge all snapshots of `database` with those of `file` on the server, then push the result
+///
+/// Changesets are named with their default name, use [sync_database_with_progress] to name them.
+pub fn sync_database<C: DatabaseConflictHandler>(
+    database: &Database,
+    file: &RemoteFile,
+    conflict_handler: C,
+) -> Result<(), ()> {
+    sync_database_with_progress(
+        database,
+        file,
+        conflict_handler,
+        |_: &Changeset| true,
+        |_, _| true,
+    )
+}
+
+pub fn sync_database_with_progress<C, N, F>(
+    database: &Database,
+    file: &RemoteFile,
+    mut conflict_handler: C,
+    mut name_changeset: N,
+    mut progress: F,
+) -> Result<(), ()>
+where
+    C: DatabaseConflictHandler,
+    N: NameChangeset,
+    F: FnMut(usize, usize) -> bool,
+{
+    bool_result(unsafe {
+        BNCollaborationSyncDatabase(
+            database.as_raw(),
+            file.as_raw(),
+            Some(cb_database_conflict_handler::<C>),
+            &mut conflict_handler as *mut C as *mut c_void,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+            Some(cb_name_changeset::<N>),
+            &mut name_changeset as *mut N as *mut c_void,
+        )
+    })
+}
+
+/// Pull the new snapshots of `file` from the server and merge them into `database`, returns the
+/// number of snapshots pulled
+pub fn pull_database<C: DatabaseConflictHandler>(
+    database: &Database,
+    file: &RemoteFile,
+    conflict_handler: C,
+) -> Result<usize, ()> {
+    pull_database_with_progress(
+        database,
+        file,
+        conflict_handler,
+        |_: &Changeset| true,
+        |_, _| true,
+    )
+}
+
+pub fn pull_database_with_progress<C, N, F>(
+    database: &Database,
+    file: &RemoteFile,
+    mut conflict_handler: C,
+    mut name_changeset: N,
+    mut progress: F,
+) -> Result<usize, ()>
+where
+    C: DatabaseConflictHandler,
+    N: NameChangeset,
+    F: FnMut(usize, usize) -> bool,
+{
+    let mut count = 0;
+    let success = unsafe {
+        BNCollaborationPullDatabase(
+            database.as_raw(),
+            file.as_raw(),
+            &mut count,
+            Some(cb_database_conflict_handler::<C>),
+            &mut conflict_handler as *mut C as *mut c_void,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+            Some(cb_name_changeset::<N>),
+            &mut name_changeset as *mut N as *mut c_void,
+        )
+    };
+    bool_result(success).map(|_| count)
+}
+
+/// Push the local snapshots of `database` to `file` on the server, returns the number of
+/// snapshots pushed
+pub fn push_database(database: &Database, file: &RemoteFile) -> Result<usize, ()> {
+    let mut count = 0;
+    let success = unsafe {
+        BNCollaborationPushDatabase(
+            database.as_raw(),
+            file.as_raw(),
+            &mut count,
+            Some(cb_progress_nop),
+            ptr::null_mut(),
+        )
+    };
+    bool_result(success).map(|_| count)
+}
+
+pub fn push_database_with_progress<F: FnMut(usize, usize) -> bool>(
+    database: &Database,
+    file: &RemoteFile,
+    mut progress: F,
+) -> Result<usize, ()> {
+    let mut count = 0;
+    let success = unsafe {
+        BNCollaborationPushDatabase(
+            database.as_raw(),
+            file.as_raw(),
+            &mut count,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+        )
+    };
+    bool_result(success).map(|_| count)
+}
+
+/// Merge all the leaf snapshots of `database` into a single one
+pub fn merge_database<C: DatabaseConflictHandler>(
+    database: &Database,
+    conflict_handler: C,
+) -> Result<(), ()> {
+    merge_database_with_progress(database, conflict_handler, |_, _| true)
+}
+
+pub fn merge_database_with_progress<C, F>(
+    database: &Database,
+    mut conflict_handler: C,
+    mut progress: F,
+) -> Result<(), ()>
+where
+    C: DatabaseConflictHandler,
+    F: FnMut(usize, usize) -> bool,
+{
+    bool_result(unsafe {
+        BNCollaborationMergeDatabase(
+            database.as_raw(),
+            Some(cb_database_conflict_handler::<C>),
+            &mut conflict_handler as *mut C as *mut c_void,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+        )
+    })
+}
+
+/// Merge the snapshots `first` and `second` of a database, returns the resulting snapshot
+pub fn merge_snapshots<C: DatabaseConflictHandler>(
+    first: &Snapshot,
+    second: &Snapshot,
+    conflict_handler: C,
+) -> Result<Snapshot, ()> {
+    merge_snapshots_with_progress(first, second, conflict_handler, |_, _| true)
+}
+
+pub fn merge_snapshots_with_progress<C, F>(
+    first: &Snapshot,
+    second: &Snapshot,
+    mut conflict_handler: C,
+    mut progress: F,
+) -> Result<Snapshot, ()>
+where
+    C: DatabaseConflictHandler,
+    F: FnMut(usize, usize) -> bool,
+{
+    let value = unsafe {
+        BNCollaborationMergeSnapshots(
+            first.as_raw(),
+            second.as_raw(),
+            Some(cb_database_conflict_handler::<C>),
+            &mut conflict_handler as *mut C as *mut c_void,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+        )
+    };
+    NonNull::new(value)
+        .map(|handle| unsafe { Snapshot::from_raw(handle) })
+        .ok_or(())
+}
+
+/// Upload the database of `metadata` as a new file in `project`, inside `folder` or at the root
+/// of the project
+pub fn upload_database(
+    metadata: &FileMetadata,
+    project: &RemoteProject,
+    folder: Option<&RemoteFolder>,
+) -> Result<RemoteFile, ()> {
+    upload_database_with_progress(metadata, project, folder, |_: &Changeset| true, |_, _| true)
+}
+
+pub fn upload_database_with_progress<N, F>(
+    metadata: &FileMetadata,
+    project: &RemoteProject,
+    folder: Option<&RemoteFolder>,
+    mut name_changeset: N,
+    mut progress: F,
+) -> Result<RemoteFile, ()>
+where
+    N: NameChangeset,
+    F: FnMut(usize, usize) -> bool,
+{
+    let folder_ptr = folder
+        .map(|f| unsafe { f.as_raw() as *mut _ })
+        .unwrap_or(ptr::null_mut());
+    let value = unsafe {
+        BNCollaborationUploadDatabase(
+            metadata.handle,
+            project.as_raw(),
+            folder_ptr,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+            Some(cb_name_changeset::<N>),
+            &mut name_changeset as *mut N as *mut c_void,
+        )
+    };
+    NonNull::new(value)
+        .map(|handle| unsafe { RemoteFile::from_raw(handle) })
+        .ok_or(())
+}
+
+/// Whether `database` is synchronized with a file on a server
+pub fn is_collaboration_database(database: &Database) -> bool {
+    unsafe { BNCollaborationIsCollaborationDatabase(database.as_raw()) }
+}
+
+/// Print debug information about the snapshots of `database` to the log
+pub fn dump_database(database: &Database) -> Result<(), ()> {
+    bool_result(unsafe { BNCollaborationDumpDatabase(database.as_raw()) })
+}
+
+/// Ignore `snapshot` of `database` when pushing to the server
+pub fn ignore_snapshot(database: &Database, snapshot: &Snapshot) -> Result<(), ()> {
+    bool_result(unsafe { BNCollaborationIgnoreSnapshot(database.as_raw(), snapshot.as_raw()) })
+}
+
+pub fn is_snapshot_ignored(database: &Database, snapshot: &Snapshot) -> bool {
+    unsafe { BNCollaborationIsSnapshotIgnored(database.as_raw(), snapshot.as_raw()) }
+}
+
+/// Username of the author of `snapshot` of `database`, if known
+pub fn snapshot_author(database: &Database, snapshot: &Snapshot) -> Result<Option<BnString>, ()> {
+    let mut value = ptr::null_mut();
+    let success = unsafe {
+        BNCollaborationGetSnapshotAuthor(database.as_raw(), snapshot.as_raw(), &mut value)
+    };
+    bool_result(success).map(|_| unsafe { optional_string(value) })
+}
+
+pub fn set_snapshot_author<S: BnStrCompatible>(
+    database: &Database,
+    snapshot: &Snapshot,
+    author: S,
+) -> Result<(), ()> {
+    let author = author.into_bytes_with_nul();
+    bool_result(unsafe {
+        BNCollaborationSetSnapshotAuthor(
+            database.as_raw(),
+            snapshot.as_raw(),
+            author.as_ref().as_ptr() as *const c_char,
+        )
+    })
+}
+
+/// Merge all snapshots of `archive` with those of `file` on the server, then push the result
+pub fn sync_type_archive<C: TypeArchiveConflictHandler>(
+    archive: &TypeArchive,
+    file: &RemoteFile,
+    conflict_handler: C,
+) -> Result<(), ()> {
+    sync_type_archive_with_progress(archive, file, conflict_handler, |_, _| true)
+}
+
+pub fn sync_type_archive_with_progress<C, F>(
+    archive: &TypeArchive,
+    file: &RemoteFile,
+    mut conflict_handler: C,
+    mut progress: F,
+) -> Result<(), ()>
+where
+    C: TypeArchiveConflictHandler,
+    F: FnMut(usize, usize) -> bool,
+{
+    bool_result(unsafe {
+        BNCollaborationSyncTypeArchive(
+            archive.as_raw(),
+            file.as_raw(),
+            Some(cb_type_archive_conflict_handler::<C>),
+            &mut conflict_handler as *mut C as *mut c_void,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+        )
+    })
+}
+
+/// Pull the new snapshots of `file` from the server and merge them into `archive`, returns the
+/// number of snapshots pulled
+pub fn pull_type_archive<C: TypeArchiveConflictHandler>(
+    archive: &TypeArchive,
+    file: &RemoteFile,
+    conflict_handler: C,
+) -> Result<usize, ()> {
+    pull_type_archive_with_progress(archive, file, conflict_handler, |_, _| true)
+}
+
+pub fn pull_type_archive_with_progress<C, F>(
+    archive: &TypeArchive,
+    file: &RemoteFile,
+    mut conflict_handler: C,
+    mut progress: F,
+) -> Result<usize, ()>
+where
+    C: TypeArchiveConflictHandler,
+    F: FnMut(usize, usize) -> bool,
+{
+    let mut count = 0;
+    let success = unsafe {
+        BNCollaborationPullTypeArchive(
+            archive.as_raw(),
+            file.as_raw(),
+            &mut count,
+            Some(cb_type_archive_conflict_handler::<C>),
+            &mut conflict_handler as *mut C as *mut c_void,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+        )
+    };
+    bool_result(success).map(|_| count)
+}
+
+/// Push the local snapshots of `archive` to `file` on the server, returns the number of
+/// snapshots pushed
+pub fn push_type_archive(archive: &TypeArchive, file: &RemoteFile) -> Result<usize, ()> {
+    push_type_archive_with_progress(archive, file, |_, _| true)
+}
+
+pub fn push_type_archive_with_progress<F: FnMut(usize, usize) -> bool>(
+    archive: &TypeArchive,
+    file: &RemoteFile,
+    mut progress: F,
+) -> Result<usize, ()> {
+    let mut count = 0;
+    let success = unsafe {
+        BNCollaborationPushTypeArchive(
+            archive.as_raw(),
+            file.as_raw(),
+            &mut count,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+        )
+    };
+    bool_result(success).map(|_| count)
+}
+
+/// Download the type archive of `file` to `location`
+pub fn download_type_archive<S: BnStrCompatible>(
+    file: &RemoteFile,
+    location: S,
+) -> Result<TypeArchive, ()> {
+    download_type_archive_with_progress(file, location, |_, _| true)
+}
+
+pub fn download_type_archive_with_progress<S, F>(
+    file: &RemoteFile,
+    location: S,
+    mut progress: F,
+) -> Result<TypeArchive, ()>
+where
+    S: BnStrCompatible,
+    F: FnMut(usize, usize) -> bool,
+{
+    let location = location.into_bytes_with_nul();
+    let mut value = ptr::null_mut();
+    let success = unsafe {
+        BNCollaborationDownloadTypeArchive(
+            file.as_raw(),
+            location.as_ref().as_ptr() as *const c_char,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+            &mut value,
+        )
+    };
+    if !success {
+        return Err(());
+    }
+    NonNull::new(value)
+        .map(|handle| unsafe { TypeArchive::from_raw(handle) })
+        .ok_or(())
+}
+
+/// Upload `archive` as a new file in `project`, inside `folder` or at the root of the project,
+/// optionally mirrored by the existing local `core_file`
+pub fn upload_type_archive(
+    archive: &TypeArchive,
+    project: &RemoteProject,
+    folder: Option<&RemoteFolder>,
+    core_file: Option<&ProjectFile>,
+) -> Result<RemoteFile, ()> {
+    upload_type_archive_with_progress(archive, project, folder, core_file, |_, _| true)
+}
+
+pub fn upload_type_archive_with_progress<F: FnMut(usize, usize) -> bool>(
+    archive: &TypeArchive,
+    project: &RemoteProject,
+    folder: Option<&RemoteFolder>,
+    core_file: Option<&ProjectFile>,
+    mut progress: F,
+) -> Result<RemoteFile, ()> {
+    let folder_ptr = folder
+        .map(|f| unsafe { f.as_raw() as *mut _ })
+        .unwrap_or(ptr::null_mut());
+    let core_file_ptr = core_file
+        .map(|f| unsafe { f.as_raw() as *mut _ })
+        .unwrap_or(ptr::null_mut());
+    let mut value = ptr::null_mut();
+    let success = unsafe {
+        BNCollaborationUploadTypeArchive(
+            archive.as_raw(),
+            project.as_raw(),
+            folder_ptr,
+            Some(cb_progress::<F>),
+            &mut progress as *mut F as *mut c_void,
+            core_file_ptr,
+            &mut value,
+        )
+    };
+    if !success {
+        return Err(());
+    }
+    NonNull::new(value)
+        .map(|handle| unsafe { RemoteFile::from_raw(handle) })
+        .ok_or(())
+}
+
+/// Whether `archive` is synchronized with a file on a server
+pub fn is_collaboration_type_archive(archive: &TypeArchive) -> bool {
+    unsafe { BNCollaborationIsCollaborationTypeArchive(archive.as_raw()) }
+}
+
+/// Whether the snapshot `snapshot_id` of `archive` is ignored when pushing to the server
+pub fn is_type_archive_snapshot_ignored<S: BnStrCompatible>(
+    archive: &TypeArchive,
+    snapshot_id: S,
+) -> bool {
+    let snapshot_id = snapshot_id.into_bytes_with_nul();
+    unsafe {
+        BNCollaborationIsTypeArchiveSnapshotIgnored(
+            archive.as_raw(),
+            snapshot_id.as_ref().as_ptr() as *const c_char,
+        )
+    }
+}
+
+unsafe extern "C" fn cb_database_conflict_handler<C: DatabaseConflictHandler>(
+    ctxt: *mut c_void,
+    keys: *mut *const c_char,
+    conflicts: *mut *mut BNAnalysisMergeConflict,
+    count: usize,
+) -> bool {
+    let ctxt: &mut C = &mut *(ctxt as *mut C);
+    let keys = std::slice::from_raw_parts(keys, count);
+    let conflicts = std::slice::from_raw_parts(conflicts, count);
+    let conflicts = keys
+        .iter()
+        .zip(conflicts.iter())
+        .map(|(key, conflict)| {
+            (
+                c_str_to_string(*key),
+                AnalysisMergeConflict::from_raw(NonNull::new(*conflict).unwrap()),
+            )
+        })
+        .collect();
+    ctxt.handle_conflicts(conflicts)
+}
+
+unsafe extern "C" fn cb_type_archive_conflict_handler<C: TypeArchiveConflictHandler>(
+    ctxt: *mut c_void,
+    conflicts: *mut *mut BNTypeArchiveMergeConflict,
+    count: usize,
+) -> bool {
+    let ctxt: &mut C = &mut *(ctxt as *mut C);
+    let conflicts = std::slice::from_raw_parts(conflicts, count)
+        .iter()
+        .map(|conflict| TypeArchiveMergeConflict::from_raw(NonNull::new(*conflict).unwrap()))
+        .collect();
+    ctxt.handle_conflicts(conflicts)
+}
+
+unsafe extern "C" fn cb_name_changeset<N: NameChangeset>(
+    ctxt: *mut c_void,
+    changeset: *mut BNCollaborationChangeset,
+) -> bool {
+    let ctxt: &mut N = &mut *(ctxt as *mut N);
+    let changeset = Changeset::from_raw(NonNull::new(changeset).unwrap());
+    ctxt.name_changeset(&changeset)
+}
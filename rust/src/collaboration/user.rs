@@ -0,0 +1,134 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::c_char;
+use std::mem;
+use std::ptr::NonNull;
+
+use binaryninjacore_sys::*;
+
+use super::{bool_result, Remote};
+
+use crate::rc::{CoreArrayProvider, CoreArrayProviderInner};
+use crate::string::{BnStrCompatible, BnString};
+
+/// A user of a [Remote]
+#[repr(transparent)]
+pub struct RemoteUser {
+    handle: NonNull<BNCollaborationUser>,
+}
+
+impl RemoteUser {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNCollaborationUser>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNCollaborationUser) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNCollaborationUser {
+        &mut *self.handle.as_ptr()
+    }
+
+    pub fn remote(&self) -> Remote {
+        unsafe {
+            Remote::from_raw(NonNull::new(BNCollaborationUserGetRemote(self.as_raw())).unwrap())
+        }
+    }
+
+    /// Web api endpoint url
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationUserGetUrl(self.as_raw())) }
+    }
+
+    /// Unique id
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationUserGetId(self.as_raw())) }
+    }
+
+    pub fn username(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationUserGetUsername(self.as_raw())) }
+    }
+
+    /// Set the username, the change is sent to the server on [Remote::push_user]
+    pub fn set_username<S: BnStrCompatible>(&self, username: S) -> Result<(), ()> {
+        let username = username.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNCollaborationUserSetUsername(
+                self.as_raw(),
+                username.as_ref().as_ptr() as *const c_char,
+            )
+        })
+    }
+
+    pub fn email(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationUserGetEmail(self.as_raw())) }
+    }
+
+    /// Set the email, the change is sent to the server on [Remote::push_user]
+    pub fn set_email<S: BnStrCompatible>(&self, email: S) -> Result<(), ()> {
+        let email = email.into_bytes_with_nul();
+        bool_result(unsafe {
+            BNCollaborationUserSetEmail(self.as_raw(), email.as_ref().as_ptr() as *const c_char)
+        })
+    }
+
+    /// Date of the last login, as reported by the server
+    pub fn last_login(&self) -> BnString {
+        unsafe { BnString::from_raw(BNCollaborationUserGetLastLogin(self.as_raw())) }
+    }
+
+    /// Whether the account is active and the user can log in
+    pub fn is_active(&self) -> bool {
+        unsafe { BNCollaborationUserIsActive(self.as_raw()) }
+    }
+
+    /// Enable or disable the account, the change is sent to the server on [Remote::push_user]
+    pub fn set_is_active(&self, is_active: bool) -> Result<(), ()> {
+        bool_result(unsafe { BNCollaborationUserSetIsActive(self.as_raw(), is_active) })
+    }
+}
+
+impl Clone for RemoteUser {
+    fn clone(&self) -> Self {
+        unsafe {
+            Self::from_raw(NonNull::new(BNNewCollaborationUserReference(self.as_raw())).unwrap())
+        }
+    }
+}
+
+impl Drop for RemoteUser {
+    fn drop(&mut self) {
+        unsafe { BNFreeCollaborationUser(self.as_raw()) }
+    }
+}
+
+impl CoreArrayProvider for RemoteUser {
+    type Raw = *mut BNCollaborationUser;
+    type Context = ();
+    type Wrapped<'a> = &'a Self;
+}
+
+unsafe impl CoreArrayProviderInner for RemoteUser {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeCollaborationUserList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::ref_from_raw(raw)
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Customize how data variables are displayed in linear view.
+//!
+//! A [DataRenderer] is asked, for every data variable being displayed, whether it wants to render it
+//! through [DataRenderer::is_valid_for_data], the first one to accept it provides the lines through
+//! [DataRenderer::get_lines_for_data].
+//!
+//! Type specific renderers (registered with [register_type_specific]) are consulted before
+//! generic renderers (registered with [register_generic]).
+//!
+//! ```no_run
+//! use binaryninja::binaryview::BinaryView;
+//! use binaryninja::datarenderer::{register_type_specific, DataRenderer, TypeContext};
+//! use binaryninja::disassembly::{DisassemblyTextLine, InstructionTextToken, InstructionTextTokenContents};
+//! use binaryninja::types::Type;
+//!
+//! struct GuidRenderer;
+//!
+//! impl DataRenderer for GuidRenderer {
+//!     fn is_valid_for_data(&self, _view: &BinaryView, _addr: u64, ty: &Type, _context: &[TypeContext]) -> bool {
+//!         ty.registered_name().map(|n| n.name().to_string() == "GUID").unwrap_or(false)
+//!     }
+//!
+//!     fn get_lines_for_data(
+//!         &self,
+//!         _view: &BinaryView,
+//!         _addr: u64,
+//!         _ty: &Type,
+//!         prefix: &[InstructionTextToken],
+//!         _width: usize,
+//!         _context: &[TypeContext],
+//!     ) -> Vec<DisassemblyTextLine> {
+//!         let mut tokens: Vec<_> = prefix.to_vec();
+//!         tokens.push(InstructionTextToken::new("{...}", InstructionTextTokenContents::Text));
+//!         vec![tokens.into()]
+//!     }
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn CorePluginInit() -> bool {
+//!     register_type_specific(GuidRenderer);
+//!     true
+//! }
+//! ```
+
+use binaryninjacore_sys::*;
+
+use std::ffi::c_void;
+use std::{mem, ptr, slice};
+
+use crate::binaryview::BinaryView;
+use crate::disassembly::{DisassemblyTextLine, InstructionTextToken};
+use crate::rc::*;
+use crate::types::Type;
+
+/// A type in which the data being rendered is nested, and the offset of the data inside of it
+#[repr(transparent)]
+pub struct TypeContext(BNTypeContext);
+
+impl TypeContext {
+    pub fn ty(&self) -> &Type {
+        unsafe { mem::transmute(&self.0.type_) }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.0.offset
+    }
+
+    unsafe fn slice_from_raw<'a>(raw: *mut BNTypeContext, count: usize) -> &'a [Self] {
+        if raw.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(raw as *const Self, count)
+        }
+    }
+}
+
+/// The trait required for custom data renderers. See [register_generic] and
+/// [register_type_specific] for registration.
+pub trait DataRenderer: 'static + Sync {
+    /// Whether this renderer handles the data at `addr` of type `ty`, `context` is the stack
+    /// of types the data is nested in, outermost first
+    fn is_valid_for_data(
+        &self,
+        view: &BinaryView,
+        addr: u64,
+        ty: &Type,
+        context: &[TypeContext],
+    ) -> bool;
+
+    /// The lines displayed for the data at `addr`, `prefix` are the tokens that would be displayed
+    /// before the data (eg: the type and name of the variable) and should be included in the first line
+    fn get_lines_for_data(
+        &self,
+        view: &BinaryView,
+        addr: u64,
+        ty: &Type,
+        prefix: &[InstructionTextToken],
+        width: usize,
+        context: &[TypeContext],
+    ) -> Vec<DisassemblyTextLine>;
+}
+
+fn create_data_renderer<R: DataRenderer>(renderer: R) -> *mut BNDataRenderer {
+    extern "C" fn cb_free_object<R: DataRenderer>(ctxt: *mut c_void) {
+        ffi_wrap!("DataRenderer::free_object", unsafe {
+            drop(Box::from_raw(ctxt as *mut R));
+        })
+    }
+
+    extern "C" fn cb_is_valid_for_data<R: DataRenderer>(
+        ctxt: *mut c_void,
+        view: *mut BNBinaryView,
+        addr: u64,
+        ty: *mut BNType,
+        type_ctx: *mut BNTypeContext,
+        ctx_count: usize,
+    ) -> bool {
+        ffi_wrap!("DataRenderer::is_valid_for_data", unsafe {
+            let renderer = &*(ctxt as *const R);
+            let view = BinaryView { handle: view };
+            let ty = Type { handle: ty };
+            let context = TypeContext::slice_from_raw(type_ctx, ctx_count);
+
+            renderer.is_valid_for_data(&view, addr, &ty, context)
+        })
+    }
+
+    extern "C" fn cb_get_lines_for_data<R: DataRenderer>(
+        ctxt: *mut c_void,
+        view: *mut BNBinaryView,
+        addr: u64,
+        ty: *mut BNType,
+        prefix: *const BNInstructionTextToken,
+        prefix_count: usize,
+        width: usize,
+        count: *mut usize,
+        type_ctx: *mut BNTypeContext,
+        ctx_count: usize,
+    ) -> *mut BNDisassemblyTextLine {
+        ffi_wrap!("DataRenderer::get_lines_for_data", unsafe {
+            let renderer = &*(ctxt as *const R);
+            let view = BinaryView { handle: view };
+            let ty = Type { handle: ty };
+            let prefix = if prefix.is_null() {
+                &[]
+            } else {
+                slice::from_raw_parts(prefix as *const InstructionTextToken, prefix_count)
+            };
+            let context = TypeContext::slice_from_raw(type_ctx, ctx_count);
+
+            let lines: Box<[DisassemblyTextLine]> = renderer
+                .get_lines_for_data(&view, addr, &ty, prefix, width, context)
+                .into();
+            *count = lines.len();
+            // SAFETY DisassemblyTextLine is repr(transparent), freed on [cb_free_lines]
+            Box::leak(lines).as_mut_ptr() as *mut BNDisassemblyTextLine
+        })
+    }
+
+    extern "C" fn cb_free_lines(
+        _ctxt: *mut c_void,
+        lines: *mut BNDisassemblyTextLine,
+        count: usize,
+    ) {
+        ffi_wrap!("DataRenderer::free_lines", unsafe {
+            let lines = ptr::slice_from_raw_parts_mut(lines as *mut DisassemblyTextLine, count);
+            drop(Box::from_raw(lines));
+        })
+    }
+
+    let ctxt = Box::into_raw(Box::new(renderer));
+    let mut callbacks = BNCustomDataRenderer {
+        context: ctxt as *mut c_void,
+        freeObject: Some(cb_free_object::<R>),
+        isValidForData: Some(cb_is_valid_for_data::<R>),
+        getLinesForData: Some(cb_get_lines_for_data::<R>),
+        freeLines: Some(cb_free_lines),
+    };
+    unsafe { BNCreateDataRenderer(&mut callbacks) }
+}
+
+/// Register a renderer that is consulted for data of any type
+pub fn register_generic<R: DataRenderer>(renderer: R) {
+    let renderer = create_data_renderer(renderer);
+    unsafe { BNRegisterGenericDataRenderer(BNGetDataRendererContainer(), renderer) }
+}
+
+/// Register a renderer that is consulted, before the generic ones, for data of specific types
+pub fn register_type_specific<R: DataRenderer>(renderer: R) {
+    let renderer = create_data_renderer(renderer);
+    unsafe { BNRegisterTypeSpecificDataRenderer(BNGetDataRendererContainer(), renderer) }
+}
+
+/// Render the data at `addr` using the registered renderers, falling back to the default
+/// rendering of the core, this is useful to delegate the rendering of nested data
+pub fn render_lines_for_data(
+    view: &BinaryView,
+    addr: u64,
+    ty: &Type,
+    prefix: &[InstructionTextToken],
+    width: usize,
+    context: &[TypeContext],
+) -> Array<DisassemblyTextLine> {
+    let mut count = 0;
+    let lines = unsafe {
+        BNRenderLinesForData(
+            view.handle,
+            addr,
+            ty.handle,
+            prefix.as_ptr() as *const BNInstructionTextToken,
+            prefix.len(),
+            width,
+            &mut count,
+            context.as_ptr() as *mut BNTypeContext,
+            context.len(),
+        )
+    };
+    assert!(!lines.is_null());
+    unsafe { Array::new(lines, count, ()) }
+}
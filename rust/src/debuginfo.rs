@@ -75,6 +75,7 @@ use crate::{
     platform::Platform,
     rc::*,
     string::{raw_to_string, BnStrCompatible, BnString},
+    typecontainer::TypeContainer,
     types::{DataVariableAndName, NameAndType, NamedTypedVariable, Type},
 };
 
@@ -417,6 +418,15 @@ impl DebugInfo {
         result
     }
 
+    /// Type container for the types provided by a named DebugInfoParser
+    pub fn type_container<S: BnStrCompatible>(&self, parser_name: S) -> TypeContainer {
+        let parser_name = parser_name.into_bytes_with_nul();
+        let container = unsafe {
+            BNGetDebugInfoTypeContainer(self.handle, parser_name.as_ref().as_ptr() as *const _)
+        };
+        unsafe { TypeContainer::from_raw(ptr::NonNull::new(container).unwrap()) }
+    }
+
     /// A generator of all types provided by DebugInfoParsers
     pub fn types(&self) -> Vec<Ref<NameAndType>> {
         let mut count: usize = 0;
@@ -16,13 +16,18 @@
 
 use binaryninjacore_sys::*;
 
-use crate::string::BnString;
+use crate::architecture::{Architecture, CoreArchitecture};
+use crate::basicblock::{BasicBlock, BlockContext};
+use crate::function::{Function, NativeBlock};
+use crate::string::{BnStrCompatible, BnString};
+use crate::types::StackVariableReference;
+use crate::{hlil, llil, mlil};
 use crate::{BN_FULL_CONFIDENCE, BN_INVALID_EXPR};
 
 use crate::rc::*;
 
 use std::convert::From;
-use std::ffi::CStr;
+use std::ffi::{c_char, CStr};
 use std::mem;
 use std::ptr;
 
@@ -516,3 +521,332 @@ unsafe impl RefCountable for DisassemblySettings {
         BNFreeDisassemblySettings(handle.handle);
     }
 }
+
+/// Renders the disassembly or IL of a function into lines, the same way the UI does
+///
+/// For IL renderers, addresses are instruction indices of the IL function.
+///
+/// ```no_run
+/// # use binaryninja::disassembly::{DisassemblySettings, DisassemblyTextRenderer};
+/// # use binaryninja::function::Function;
+/// # fn print(func: &Function) {
+/// let settings = DisassemblySettings::new();
+/// let renderer = DisassemblyTextRenderer::from_function(func, Some(&settings));
+/// for block in &func.basic_blocks() {
+///     renderer.set_basic_block(Some(&*block));
+///     let mut addr = block.raw_start();
+///     while addr < block.raw_end() {
+///         let Some((len, lines)) = renderer.disassembly_text(addr) else {
+///             break;
+///         };
+///         for line in &lines {
+///             println!("{:#x}: {}", line.addr(), line);
+///         }
+///         addr += len.max(1) as u64;
+///     }
+/// }
+/// # }
+/// ```
+#[derive(PartialEq, Eq, Hash)]
+pub struct DisassemblyTextRenderer {
+    pub(crate) handle: *mut BNDisassemblyTextRenderer,
+}
+
+unsafe impl Send for DisassemblyTextRenderer {}
+unsafe impl Sync for DisassemblyTextRenderer {}
+
+impl DisassemblyTextRenderer {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNDisassemblyTextRenderer) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    pub fn from_function(func: &Function, settings: Option<&DisassemblySettings>) -> Ref<Self> {
+        let settings = settings.map_or(ptr::null_mut(), |s| s.handle);
+        unsafe { Self::ref_from_raw(BNCreateDisassemblyTextRenderer(func.handle, settings)) }
+    }
+
+    pub fn from_llil<A, M, F>(
+        func: &llil::Function<A, M, F>,
+        settings: Option<&DisassemblySettings>,
+    ) -> Ref<Self>
+    where
+        A: Architecture,
+        M: llil::FunctionMutability,
+        F: llil::FunctionForm,
+    {
+        let settings = settings.map_or(ptr::null_mut(), |s| s.handle);
+        unsafe {
+            Self::ref_from_raw(BNCreateLowLevelILDisassemblyTextRenderer(
+                func.handle,
+                settings,
+            ))
+        }
+    }
+
+    pub fn from_mlil(
+        func: &mlil::MediumLevelILFunction,
+        settings: Option<&DisassemblySettings>,
+    ) -> Ref<Self> {
+        let settings = settings.map_or(ptr::null_mut(), |s| s.handle);
+        unsafe {
+            Self::ref_from_raw(BNCreateMediumLevelILDisassemblyTextRenderer(
+                func.handle,
+                settings,
+            ))
+        }
+    }
+
+    pub fn from_hlil(
+        func: &hlil::HighLevelILFunction,
+        settings: Option<&DisassemblySettings>,
+    ) -> Ref<Self> {
+        let settings = settings.map_or(ptr::null_mut(), |s| s.handle);
+        unsafe {
+            Self::ref_from_raw(BNCreateHighLevelILDisassemblyTextRenderer(
+                func.handle,
+                settings,
+            ))
+        }
+    }
+
+    pub fn function(&self) -> Ref<Function> {
+        unsafe { Function::from_raw(BNGetDisassemblyTextRendererFunction(self.handle)) }
+    }
+
+    /// The LLIL function being rendered, [None] for non-LLIL renderers
+    pub fn llil(&self) -> Option<Ref<llil::RegularFunction<CoreArchitecture>>> {
+        let llil = unsafe { BNGetDisassemblyTextRendererLowLevelILFunction(self.handle) };
+        (!llil.is_null()).then(|| unsafe { llil::RegularFunction::from_raw(self.arch(), llil) })
+    }
+
+    /// The MLIL function being rendered, [None] for non-MLIL renderers
+    pub fn mlil(&self) -> Option<Ref<mlil::MediumLevelILFunction>> {
+        let mlil = unsafe { BNGetDisassemblyTextRendererMediumLevelILFunction(self.handle) };
+        (!mlil.is_null()).then(|| unsafe { mlil::MediumLevelILFunction::ref_from_raw(mlil) })
+    }
+
+    /// The HLIL function being rendered, [None] for non-HLIL renderers
+    pub fn hlil(&self) -> Option<Ref<hlil::HighLevelILFunction>> {
+        let hlil = unsafe { BNGetDisassemblyTextRendererHighLevelILFunction(self.handle) };
+        (!hlil.is_null()).then(|| unsafe { hlil::HighLevelILFunction::ref_from_raw(hlil, true) })
+    }
+
+    pub fn basic_block(&self) -> Option<Ref<BasicBlock<NativeBlock>>> {
+        let block = unsafe { BNGetDisassemblyTextRendererBasicBlock(self.handle) };
+        (!block.is_null())
+            .then(|| unsafe { Ref::new(BasicBlock::from_raw(block, NativeBlock::new())) })
+    }
+
+    /// Set the block being rendered, used to annotate the lines with its properties
+    pub fn set_basic_block<C: BlockContext>(&self, block: Option<&BasicBlock<C>>) {
+        let block = block.map_or(ptr::null_mut(), |b| b.handle);
+        unsafe { BNSetDisassemblyTextRendererBasicBlock(self.handle, block) }
+    }
+
+    pub fn arch(&self) -> CoreArchitecture {
+        unsafe { CoreArchitecture::from_raw(BNGetDisassemblyTextRendererArchitecture(self.handle)) }
+    }
+
+    pub fn set_arch(&self, arch: CoreArchitecture) {
+        unsafe { BNSetDisassemblyTextRendererArchitecture(self.handle, arch.0) }
+    }
+
+    pub fn settings(&self) -> Ref<DisassemblySettings> {
+        unsafe {
+            Ref::new(DisassemblySettings {
+                handle: BNGetDisassemblyTextRendererSettings(self.handle),
+            })
+        }
+    }
+
+    pub fn set_settings(&self, settings: Option<&DisassemblySettings>) {
+        let settings = settings.map_or(ptr::null_mut(), |s| s.handle);
+        unsafe { BNSetDisassemblyTextRendererSettings(self.handle, settings) }
+    }
+
+    pub fn is_il(&self) -> bool {
+        unsafe { BNIsILDisassemblyTextRenderer(self.handle) }
+    }
+
+    pub fn has_data_flow(&self) -> bool {
+        unsafe { BNDisassemblyTextRendererHasDataFlow(self.handle) }
+    }
+
+    /// Annotation tokens shown after the instruction at `addr`, eg: possible values
+    pub fn instruction_annotations(&self, addr: u64) -> Array<InstructionTextToken> {
+        let mut count = 0;
+        unsafe {
+            let tokens =
+                BNGetDisassemblyTextRendererInstructionAnnotations(self.handle, addr, &mut count);
+            Array::new(tokens, count, ())
+        }
+    }
+
+    /// The text of the instruction at `addr` alone, along with the length of the instruction
+    pub fn instruction_text(&self, addr: u64) -> Option<(usize, Array<DisassemblyTextLine>)> {
+        let mut len = 0;
+        let mut lines = ptr::null_mut();
+        let mut count = 0;
+        unsafe {
+            BNGetDisassemblyTextRendererInstructionText(
+                self.handle,
+                addr,
+                &mut len,
+                &mut lines,
+                &mut count,
+            )
+            .then(|| (len, Array::new(lines, count, ())))
+        }
+    }
+
+    /// The lines of the instruction at `addr` with symbols, comments and annotations, as shown
+    /// in the UI, along with the length of the instruction
+    pub fn disassembly_text(&self, addr: u64) -> Option<(usize, Array<DisassemblyTextLine>)> {
+        let mut len = 0;
+        let mut lines = ptr::null_mut();
+        let mut count = 0;
+        unsafe {
+            BNGetDisassemblyTextRendererLines(self.handle, addr, &mut len, &mut lines, &mut count)
+                .then(|| (len, Array::new(lines, count, ())))
+        }
+    }
+
+    /// Add the symbols, comments and annotations to `lines`, the text of the instruction at `addr`
+    pub fn post_process_lines<S: BnStrCompatible>(
+        &self,
+        addr: u64,
+        len: usize,
+        lines: &[DisassemblyTextLine],
+        indent_spaces: S,
+    ) -> Array<DisassemblyTextLine> {
+        let indent_spaces = indent_spaces.into_bytes_with_nul();
+        let mut count = 0;
+        unsafe {
+            let lines = BNPostProcessDisassemblyTextRendererLines(
+                self.handle,
+                addr,
+                len,
+                lines.as_ptr() as *mut BNDisassemblyTextLine,
+                lines.len(),
+                &mut count,
+                indent_spaces.as_ref().as_ptr() as *const c_char,
+            );
+            Array::new(lines, count, ())
+        }
+    }
+
+    pub fn reset_deduplicated_comments(&self) {
+        unsafe { BNResetDisassemblyTextRendererDeduplicatedComments(self.handle) }
+    }
+
+    /// The tokens of the symbol referenced at `addr`, [None] if there is no symbol
+    pub fn symbol_tokens(
+        &self,
+        addr: u64,
+        size: usize,
+        operand: usize,
+    ) -> Option<Array<InstructionTextToken>> {
+        let mut tokens = ptr::null_mut();
+        let mut count = 0;
+        unsafe {
+            BNGetDisassemblyTextRendererSymbolTokens(
+                self.handle,
+                addr,
+                size,
+                operand,
+                &mut tokens,
+                &mut count,
+            )
+            .then(|| Array::new(tokens, count, ()))
+        }
+    }
+
+    pub fn stack_variable_reference_tokens(
+        &self,
+        stack_ref: &StackVariableReference,
+    ) -> Array<InstructionTextToken> {
+        let mut raw_ref = stack_ref.raw();
+        let mut count = 0;
+        unsafe {
+            let tokens = BNGetDisassemblyTextRendererStackVariableReferenceTokens(
+                self.handle,
+                &mut raw_ref,
+                &mut count,
+            );
+            Array::new(tokens, count, ())
+        }
+    }
+
+    pub fn is_integer_token(token_type: InstructionTextTokenType) -> bool {
+        unsafe { BNIsIntegerToken(token_type) }
+    }
+
+    /// The tokens displaying the integer `token`, using the display type chosen by the user
+    pub fn integer_tokens(
+        &self,
+        token: &InstructionTextToken,
+        arch: Option<CoreArchitecture>,
+        addr: u64,
+    ) -> Array<InstructionTextToken> {
+        let arch = arch.map_or(ptr::null_mut(), |a| a.0);
+        let mut count = 0;
+        unsafe {
+            let tokens = BNGetDisassemblyTextRendererIntegerTokens(
+                self.handle,
+                &token.0 as *const BNInstructionTextToken as *mut _,
+                arch,
+                addr,
+                &mut count,
+            );
+            Array::new(tokens, count, ())
+        }
+    }
+
+    /// Wrap `comment` on lines following `line`
+    pub fn wrap_comment<C: BnStrCompatible, L: BnStrCompatible, I: BnStrCompatible>(
+        &self,
+        line: &DisassemblyTextLine,
+        comment: C,
+        has_auto_annotations: bool,
+        leading_spaces: L,
+        indent_spaces: I,
+    ) -> Array<DisassemblyTextLine> {
+        let comment = comment.into_bytes_with_nul();
+        let leading_spaces = leading_spaces.into_bytes_with_nul();
+        let indent_spaces = indent_spaces.into_bytes_with_nul();
+        let mut count = 0;
+        unsafe {
+            let lines = BNDisassemblyTextRendererWrapComment(
+                self.handle,
+                &line.0,
+                &mut count,
+                comment.as_ref().as_ptr() as *const c_char,
+                has_auto_annotations,
+                leading_spaces.as_ref().as_ptr() as *const c_char,
+                indent_spaces.as_ref().as_ptr() as *const c_char,
+            );
+            Array::new(lines, count, ())
+        }
+    }
+}
+
+impl ToOwned for DisassemblyTextRenderer {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for DisassemblyTextRenderer {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewDisassemblyTextRendererReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeDisassemblyTextRenderer(handle.handle);
+    }
+}
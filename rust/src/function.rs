@@ -22,7 +22,9 @@ use crate::{
     component::Component,
     disassembly::{DisassemblySettings, DisassemblyTextLine},
     flowgraph::FlowGraph,
-    hlil, llil,
+    hlil,
+    languagerepresentation::LanguageRepresentationFunction,
+    llil,
     mlil::{self, FunctionGraphType},
     platform::Platform,
     references::CodeReference,
@@ -34,14 +36,15 @@ use crate::{
         MergedVariable, NamedTypedVariable, QualifiedName, RegisterStackAdjustment, RegisterValue,
         RegisterValueType, StackVariableReference, Type, UnresolvedIndirectBranches, Variable,
     },
+    workflow::Workflow,
 };
 use crate::{databuffer::DataBuffer, disassembly::InstructionTextToken, rc::*};
 pub use binaryninjacore_sys::BNAnalysisSkipReason as AnalysisSkipReason;
 pub use binaryninjacore_sys::BNFunctionAnalysisSkipOverride as FunctionAnalysisSkipOverride;
 pub use binaryninjacore_sys::BNFunctionUpdateType as FunctionUpdateType;
 
+use std::{collections::HashSet, ffi::c_char, hash::Hash, ops::Range};
 use std::{fmt, mem};
-use std::{ffi::c_char, hash::Hash, ops::Range};
 
 pub struct Location {
     pub arch: Option<CoreArchitecture>,
@@ -301,6 +304,19 @@ impl Function {
         (!hlil.is_null()).then(|| unsafe { hlil::HighLevelILFunction::ref_from_raw(hlil, true) })
     }
 
+    /// Representation of the function in the high level language shown by the decompiler views
+    pub fn language_representation(&self) -> Option<Ref<LanguageRepresentationFunction>> {
+        let repr = unsafe { BNGetFunctionLanguageRepresentation(self.handle) };
+        (!repr.is_null()).then(|| unsafe { LanguageRepresentationFunction::ref_from_raw(repr) })
+    }
+
+    pub fn language_representation_if_available(
+        &self,
+    ) -> Option<Ref<LanguageRepresentationFunction>> {
+        let repr = unsafe { BNGetFunctionLanguageRepresentationIfAvailable(self.handle) };
+        (!repr.is_null()).then(|| unsafe { LanguageRepresentationFunction::ref_from_raw(repr) })
+    }
+
     /// MediumLevelILFunction used to represent Function mapped medium level IL
     pub fn mapped_medium_level_il(&self) -> Result<Ref<mlil::MediumLevelILFunction>, ()> {
         let mlil = unsafe { BNGetFunctionMappedMediumLevelIL(self.handle) };
@@ -1926,6 +1942,94 @@ impl Function {
         unsafe { BNReanalyzeFunction(self.handle, update_type) }
     }
 
+    /// Assemble `code` and write it at `addr`, see [BinaryViewExt::assemble_and_write]
+    pub fn assemble_and_write(
+        &self,
+        addr: u64,
+        code: &str,
+        arch: Option<CoreArchitecture>,
+    ) -> Result<usize, String> {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view().assemble_and_write(&arch, addr, code)
+    }
+
+    pub fn is_never_branch_patch_available(
+        &self,
+        addr: u64,
+        arch: Option<CoreArchitecture>,
+    ) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view().is_never_branch_patch_available(&arch, addr)
+    }
+
+    pub fn is_always_branch_patch_available(
+        &self,
+        addr: u64,
+        arch: Option<CoreArchitecture>,
+    ) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view().is_always_branch_patch_available(&arch, addr)
+    }
+
+    pub fn is_invert_branch_patch_available(
+        &self,
+        addr: u64,
+        arch: Option<CoreArchitecture>,
+    ) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view().is_invert_branch_patch_available(&arch, addr)
+    }
+
+    pub fn is_skip_and_return_zero_patch_available(
+        &self,
+        addr: u64,
+        arch: Option<CoreArchitecture>,
+    ) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view()
+            .is_skip_and_return_zero_patch_available(&arch, addr)
+    }
+
+    pub fn is_skip_and_return_value_patch_available(
+        &self,
+        addr: u64,
+        arch: Option<CoreArchitecture>,
+    ) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view()
+            .is_skip_and_return_value_patch_available(&arch, addr)
+    }
+
+    /// Replace the instruction at `addr` with NOPs, see [BinaryViewExt::convert_to_nop]
+    pub fn convert_to_nop(&self, addr: u64, arch: Option<CoreArchitecture>) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view().convert_to_nop(&arch, addr)
+    }
+
+    /// Make the conditional branch at `addr` always taken, see [BinaryViewExt::always_branch]
+    pub fn always_branch(&self, addr: u64, arch: Option<CoreArchitecture>) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view().always_branch(&arch, addr)
+    }
+
+    /// Invert the condition of the branch at `addr`, see [BinaryViewExt::invert_branch]
+    pub fn invert_branch(&self, addr: u64, arch: Option<CoreArchitecture>) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view().invert_branch(&arch, addr)
+    }
+
+    /// Replace the call at `addr` with an instruction returning `value`, see
+    /// [BinaryViewExt::skip_and_return_value]
+    pub fn skip_and_return_value(
+        &self,
+        addr: u64,
+        value: u64,
+        arch: Option<CoreArchitecture>,
+    ) -> bool {
+        let arch = arch.unwrap_or_else(|| self.arch());
+        self.view().skip_and_return_value(&arch, addr, value)
+    }
+
     /// Generate internal debug reports for a variety of analysis.
     /// Current list of possible values include:
     ///
@@ -1980,6 +2084,66 @@ impl Function {
         self.view().get_code_refs(self.start())
     }
 
+    /// Returns the start addresses of the functions called by this function, see
+    /// [Function::call_sites] for the addresses of the calls
+    pub fn callee_addresses(&self) -> Vec<u64> {
+        let view = self.view();
+        let mut result = Vec::new();
+        for site in &self.call_sites() {
+            let mut src = BNReferenceSource {
+                func: self.handle,
+                arch: site.architecture().0,
+                addr: site.address,
+            };
+            let mut count = 0;
+            unsafe {
+                let callees = BNGetCallees(view.handle, &mut src, &mut count);
+                if callees.is_null() {
+                    continue;
+                }
+                result.extend_from_slice(std::slice::from_raw_parts(callees, count));
+                BNFreeAddressList(callees);
+            }
+        }
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+
+    /// Returns the functions called by this function, each one once
+    pub fn callees(&self) -> Vec<Ref<Function>> {
+        let view = self.view();
+        self.callee_addresses()
+            .into_iter()
+            .filter_map(|addr| {
+                view.functions_at(addr)
+                    .iter()
+                    .next()
+                    .map(|func| func.clone())
+            })
+            .collect()
+    }
+
+    /// Returns the functions calling this function, each one once
+    pub fn callers(&self) -> Vec<Ref<Function>> {
+        let refs = unsafe {
+            let mut count = 0;
+            let refs = BNGetCallers(self.view().handle, self.start(), &mut count);
+            assert!(!refs.is_null());
+            Array::<CodeReference>::new(refs, count, ())
+        };
+
+        let mut seen = HashSet::new();
+        let mut result: Vec<Ref<Function>> = Vec::new();
+        for code_ref in &refs {
+            let func = code_ref.function().to_owned();
+            if seen.insert(func.clone()) {
+                result.push(func);
+            }
+        }
+        result
+    }
+
     /// Calling convention used by the function
     pub fn calling_convention(&self) -> Option<Conf<Ref<CallingConvention<CoreArchitecture>>>> {
         let result = unsafe { BNGetFunctionCallingConvention(self.handle) };
@@ -2146,6 +2310,24 @@ impl Function {
         assert!(!result.is_null());
         unsafe{ Array::new(result, count, ()) }
     }
+
+    /// The [Workflow] used to analyze this function
+    pub fn workflow(&self) -> Option<Ref<Workflow>> {
+        let workflow = unsafe { BNGetWorkflowForFunction(self.handle) };
+        (!workflow.is_null()).then(|| unsafe { Workflow::from_raw(workflow) })
+    }
+
+    /// Send a JSON encoded `request` to the [Workflow] of this function, returning the JSON
+    /// encoded response
+    pub fn post_workflow_request<S: BnStrCompatible>(&self, request: S) -> BnString {
+        let request = request.into_bytes_with_nul();
+        unsafe {
+            BnString::from_raw(BNPostWorkflowRequestForFunction(
+                self.handle,
+                request.as_ref().as_ptr() as *const c_char,
+            ))
+        }
+    }
 }
 
 impl fmt::Debug for Function {
@@ -0,0 +1,528 @@
+use binaryninjacore_sys::*;
+
+use crate::architecture::CoreArchitecture;
+use crate::function::Function;
+use crate::mlil::get_raw_float;
+use crate::rc::Ref;
+use crate::types::{SSAVariable, Variable};
+use crate::BN_INVALID_OPERAND;
+
+use super::operation::*;
+use super::{HighLevelILFunction, HighLevelILLiftedInstruction, HighLevelILLiftedInstructionKind};
+
+impl HighLevelILFunction {
+    /// Create an empty function, to be filled with [HighLevelILFunction::add_expr] and
+    /// [HighLevelILFunction::set_root], then finished with [HighLevelILFunction::finalize]
+    pub fn new(arch: CoreArchitecture, function: &Function) -> Ref<Self> {
+        let handle = unsafe { BNCreateHighLevelILFunction(arch.0, function.handle) };
+        assert!(!handle.is_null());
+        unsafe {
+            Ref::new(Self {
+                handle,
+                full_ast: true,
+            })
+        }
+    }
+
+    /// Current IL Address
+    pub fn current_address(&self) -> u64 {
+        unsafe { BNHighLevelILGetCurrentAddress(self.handle) }
+    }
+
+    /// Set the current IL Address
+    pub fn set_current_address(&self, value: u64, arch: Option<CoreArchitecture>) {
+        let arch = arch
+            .map(|x| x.0)
+            .unwrap_or_else(|| self.get_function().arch().0);
+        unsafe { BNHighLevelILSetCurrentAddress(self.handle, arch, value) }
+    }
+
+    /// Add the expression `expr` and all its operands to the function, returning the index of
+    /// the new expression
+    ///
+    /// The `function` and `index` of `expr` and its operands are ignored, so instructions lifted
+    /// from this function can be modified and added back, and new instructions can be built from
+    /// scratch. The new expression is located at `expr.address`.
+    ///
+    /// Fails if `expr` contains a float constant that is neither 4 nor 8 bytes wide.
+    pub fn add_expr(&self, expr: &HighLevelILLiftedInstruction) -> Result<usize, ()> {
+        use BNHighLevelILOperation::*;
+        use HighLevelILLiftedInstructionKind as Lifted;
+
+        let (operation, operands) = match &expr.kind {
+            Lifted::Nop => (HLIL_NOP, [0; 5]),
+            Lifted::Break => (HLIL_BREAK, [0; 5]),
+            Lifted::Continue => (HLIL_CONTINUE, [0; 5]),
+            Lifted::Noret => (HLIL_NORET, [0; 5]),
+            Lifted::Unreachable => (HLIL_UNREACHABLE, [0; 5]),
+            Lifted::Bp => (HLIL_BP, [0; 5]),
+            Lifted::Undef => (HLIL_UNDEF, [0; 5]),
+            Lifted::Unimpl => (HLIL_UNIMPL, [0; 5]),
+            Lifted::Adc(op) => (HLIL_ADC, self.binary_op_carry_operands(op)?),
+            Lifted::Sbb(op) => (HLIL_SBB, self.binary_op_carry_operands(op)?),
+            Lifted::Rlc(op) => (HLIL_RLC, self.binary_op_carry_operands(op)?),
+            Lifted::Rrc(op) => (HLIL_RRC, self.binary_op_carry_operands(op)?),
+            Lifted::Add(op) => (HLIL_ADD, self.binary_op_operands(op)?),
+            Lifted::Sub(op) => (HLIL_SUB, self.binary_op_operands(op)?),
+            Lifted::And(op) => (HLIL_AND, self.binary_op_operands(op)?),
+            Lifted::Or(op) => (HLIL_OR, self.binary_op_operands(op)?),
+            Lifted::Xor(op) => (HLIL_XOR, self.binary_op_operands(op)?),
+            Lifted::Lsl(op) => (HLIL_LSL, self.binary_op_operands(op)?),
+            Lifted::Lsr(op) => (HLIL_LSR, self.binary_op_operands(op)?),
+            Lifted::Asr(op) => (HLIL_ASR, self.binary_op_operands(op)?),
+            Lifted::Rol(op) => (HLIL_ROL, self.binary_op_operands(op)?),
+            Lifted::Ror(op) => (HLIL_ROR, self.binary_op_operands(op)?),
+            Lifted::Mul(op) => (HLIL_MUL, self.binary_op_operands(op)?),
+            Lifted::MuluDp(op) => (HLIL_MULU_DP, self.binary_op_operands(op)?),
+            Lifted::MulsDp(op) => (HLIL_MULS_DP, self.binary_op_operands(op)?),
+            Lifted::Divu(op) => (HLIL_DIVU, self.binary_op_operands(op)?),
+            Lifted::DivuDp(op) => (HLIL_DIVU_DP, self.binary_op_operands(op)?),
+            Lifted::Divs(op) => (HLIL_DIVS, self.binary_op_operands(op)?),
+            Lifted::DivsDp(op) => (HLIL_DIVS_DP, self.binary_op_operands(op)?),
+            Lifted::Modu(op) => (HLIL_MODU, self.binary_op_operands(op)?),
+            Lifted::ModuDp(op) => (HLIL_MODU_DP, self.binary_op_operands(op)?),
+            Lifted::Mods(op) => (HLIL_MODS, self.binary_op_operands(op)?),
+            Lifted::ModsDp(op) => (HLIL_MODS_DP, self.binary_op_operands(op)?),
+            Lifted::CmpE(op) => (HLIL_CMP_E, self.binary_op_operands(op)?),
+            Lifted::CmpNe(op) => (HLIL_CMP_NE, self.binary_op_operands(op)?),
+            Lifted::CmpSlt(op) => (HLIL_CMP_SLT, self.binary_op_operands(op)?),
+            Lifted::CmpUlt(op) => (HLIL_CMP_ULT, self.binary_op_operands(op)?),
+            Lifted::CmpSle(op) => (HLIL_CMP_SLE, self.binary_op_operands(op)?),
+            Lifted::CmpUle(op) => (HLIL_CMP_ULE, self.binary_op_operands(op)?),
+            Lifted::CmpSge(op) => (HLIL_CMP_SGE, self.binary_op_operands(op)?),
+            Lifted::CmpUge(op) => (HLIL_CMP_UGE, self.binary_op_operands(op)?),
+            Lifted::CmpSgt(op) => (HLIL_CMP_SGT, self.binary_op_operands(op)?),
+            Lifted::CmpUgt(op) => (HLIL_CMP_UGT, self.binary_op_operands(op)?),
+            Lifted::TestBit(op) => (HLIL_TEST_BIT, self.binary_op_operands(op)?),
+            Lifted::AddOverflow(op) => (HLIL_ADD_OVERFLOW, self.binary_op_operands(op)?),
+            Lifted::Fadd(op) => (HLIL_FADD, self.binary_op_operands(op)?),
+            Lifted::Fsub(op) => (HLIL_FSUB, self.binary_op_operands(op)?),
+            Lifted::Fmul(op) => (HLIL_FMUL, self.binary_op_operands(op)?),
+            Lifted::Fdiv(op) => (HLIL_FDIV, self.binary_op_operands(op)?),
+            Lifted::FcmpE(op) => (HLIL_FCMP_E, self.binary_op_operands(op)?),
+            Lifted::FcmpNe(op) => (HLIL_FCMP_NE, self.binary_op_operands(op)?),
+            Lifted::FcmpLt(op) => (HLIL_FCMP_LT, self.binary_op_operands(op)?),
+            Lifted::FcmpLe(op) => (HLIL_FCMP_LE, self.binary_op_operands(op)?),
+            Lifted::FcmpGe(op) => (HLIL_FCMP_GE, self.binary_op_operands(op)?),
+            Lifted::FcmpGt(op) => (HLIL_FCMP_GT, self.binary_op_operands(op)?),
+            Lifted::FcmpO(op) => (HLIL_FCMP_O, self.binary_op_operands(op)?),
+            Lifted::FcmpUo(op) => (HLIL_FCMP_UO, self.binary_op_operands(op)?),
+            Lifted::ArrayIndex(op) => (
+                HLIL_ARRAY_INDEX,
+                [
+                    self.expr_operand(&op.src)?,
+                    self.expr_operand(&op.index)?,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::ArrayIndexSsa(op) => (
+                HLIL_ARRAY_INDEX_SSA,
+                [
+                    self.expr_operand(&op.src)?,
+                    op.src_memory,
+                    self.expr_operand(&op.index)?,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::Assign(op) => (
+                HLIL_ASSIGN,
+                [
+                    self.expr_operand(&op.dest)?,
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::AssignMemSsa(op) => (
+                HLIL_ASSIGN_MEM_SSA,
+                [
+                    self.expr_operand(&op.dest)?,
+                    op.dest_memory,
+                    self.expr_operand(&op.src)?,
+                    op.src_memory,
+                    0,
+                ],
+            ),
+            Lifted::AssignUnpack(op) => {
+                let [count, list] = self.expr_list(&op.dest)?;
+                (
+                    HLIL_ASSIGN_UNPACK,
+                    [count, list, self.expr_operand(&op.src)?, 0, 0],
+                )
+            }
+            Lifted::AssignUnpackMemSsa(op) => {
+                let [count, list] = self.expr_list(&op.dest)?;
+                (
+                    HLIL_ASSIGN_UNPACK_MEM_SSA,
+                    [
+                        count,
+                        list,
+                        op.dest_memory,
+                        self.expr_operand(&op.src)?,
+                        op.src_memory,
+                    ],
+                )
+            }
+            Lifted::Block(op) => {
+                let [count, list] = self.expr_list(&op.body)?;
+                (HLIL_BLOCK, [count, list, 0, 0, 0])
+            }
+            Lifted::Call(op) => (HLIL_CALL, self.call_operands(op)?),
+            Lifted::Tailcall(op) => (HLIL_TAILCALL, self.call_operands(op)?),
+            Lifted::CallSsa(op) => {
+                let dest = self.expr_operand(&op.dest)?;
+                let [count, list] = self.expr_list(&op.params)?;
+                (
+                    HLIL_CALL_SSA,
+                    [dest, count, list, op.dest_memory, op.src_memory],
+                )
+            }
+            Lifted::Case(op) => {
+                let [count, list] = self.expr_list(&op.values)?;
+                (HLIL_CASE, [count, list, self.expr_operand(&op.body)?, 0, 0])
+            }
+            Lifted::Const(op) => (HLIL_CONST, [op.constant, 0, 0, 0, 0]),
+            Lifted::ConstPtr(op) => (HLIL_CONST_PTR, [op.constant, 0, 0, 0, 0]),
+            Lifted::Import(op) => (HLIL_IMPORT, [op.constant, 0, 0, 0, 0]),
+            Lifted::ConstData(op) => {
+                let value = op.constant_data.value();
+                (
+                    HLIL_CONST_DATA,
+                    [
+                        value.state.into_raw_value() as u64,
+                        value.value as u64,
+                        0,
+                        0,
+                        0,
+                    ],
+                )
+            }
+            Lifted::Deref(op) => (HLIL_DEREF, self.unary_op_operands(op)?),
+            Lifted::AddressOf(op) => (HLIL_ADDRESS_OF, self.unary_op_operands(op)?),
+            Lifted::Neg(op) => (HLIL_NEG, self.unary_op_operands(op)?),
+            Lifted::Not(op) => (HLIL_NOT, self.unary_op_operands(op)?),
+            Lifted::Sx(op) => (HLIL_SX, self.unary_op_operands(op)?),
+            Lifted::Zx(op) => (HLIL_ZX, self.unary_op_operands(op)?),
+            Lifted::LowPart(op) => (HLIL_LOW_PART, self.unary_op_operands(op)?),
+            Lifted::BoolToInt(op) => (HLIL_BOOL_TO_INT, self.unary_op_operands(op)?),
+            Lifted::UnimplMem(op) => (HLIL_UNIMPL_MEM, self.unary_op_operands(op)?),
+            Lifted::Fsqrt(op) => (HLIL_FSQRT, self.unary_op_operands(op)?),
+            Lifted::Fneg(op) => (HLIL_FNEG, self.unary_op_operands(op)?),
+            Lifted::Fabs(op) => (HLIL_FABS, self.unary_op_operands(op)?),
+            Lifted::FloatToInt(op) => (HLIL_FLOAT_TO_INT, self.unary_op_operands(op)?),
+            Lifted::IntToFloat(op) => (HLIL_INT_TO_FLOAT, self.unary_op_operands(op)?),
+            Lifted::FloatConv(op) => (HLIL_FLOAT_CONV, self.unary_op_operands(op)?),
+            Lifted::RoundToInt(op) => (HLIL_ROUND_TO_INT, self.unary_op_operands(op)?),
+            Lifted::Floor(op) => (HLIL_FLOOR, self.unary_op_operands(op)?),
+            Lifted::Ceil(op) => (HLIL_CEIL, self.unary_op_operands(op)?),
+            Lifted::Ftrunc(op) => (HLIL_FTRUNC, self.unary_op_operands(op)?),
+            Lifted::DerefFieldSsa(op) => (
+                HLIL_DEREF_FIELD_SSA,
+                [
+                    self.expr_operand(&op.src)?,
+                    op.src_memory,
+                    op.offset,
+                    get_raw_member_index(op.member_index),
+                    0,
+                ],
+            ),
+            Lifted::DerefSsa(op) => (
+                HLIL_DEREF_SSA,
+                [self.expr_operand(&op.src)?, op.src_memory, 0, 0, 0],
+            ),
+            Lifted::ExternPtr(op) => (HLIL_EXTERN_PTR, [op.constant, op.offset, 0, 0, 0]),
+            Lifted::FloatConst(op) => (
+                HLIL_FLOAT_CONST,
+                [get_raw_float(op.constant, expr.size)?, 0, 0, 0, 0],
+            ),
+            Lifted::For(op) => (
+                HLIL_FOR,
+                [
+                    self.expr_operand(&op.init)?,
+                    self.expr_operand(&op.condition)?,
+                    self.expr_operand(&op.update)?,
+                    self.expr_operand(&op.body)?,
+                    0,
+                ],
+            ),
+            Lifted::ForSsa(op) => (
+                HLIL_FOR_SSA,
+                [
+                    self.expr_operand(&op.init)?,
+                    self.expr_operand(&op.condition_phi)?,
+                    self.expr_operand(&op.condition)?,
+                    self.expr_operand(&op.update)?,
+                    self.expr_operand(&op.body)?,
+                ],
+            ),
+            Lifted::Goto(op) => (HLIL_GOTO, [op.target.target, 0, 0, 0, 0]),
+            Lifted::Label(op) => (HLIL_LABEL, [op.target.target, 0, 0, 0, 0]),
+            Lifted::If(op) => (
+                HLIL_IF,
+                [
+                    self.expr_operand(&op.condition)?,
+                    self.expr_operand(&op.cond_true)?,
+                    self.expr_operand(&op.cond_false)?,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::Intrinsic(op) => {
+                let [count, list] = self.expr_list(&op.params)?;
+                (HLIL_INTRINSIC, [op.intrinsic.1 as u64, count, list, 0, 0])
+            }
+            Lifted::IntrinsicSsa(op) => {
+                let [count, list] = self.expr_list(&op.params)?;
+                (
+                    HLIL_INTRINSIC_SSA,
+                    [
+                        op.intrinsic.1 as u64,
+                        count,
+                        list,
+                        op.dest_memory,
+                        op.src_memory,
+                    ],
+                )
+            }
+            Lifted::Jump(op) => (HLIL_JUMP, [self.expr_operand(&op.dest)?, 0, 0, 0, 0]),
+            Lifted::MemPhi(op) => {
+                let [count, list] = self.operand_list(&op.src);
+                (HLIL_MEM_PHI, [op.dest, count, list, 0, 0])
+            }
+            Lifted::Ret(op) => {
+                let [count, list] = self.expr_list(&op.src)?;
+                (HLIL_RET, [count, list, 0, 0, 0])
+            }
+            Lifted::Split(op) => (
+                HLIL_SPLIT,
+                [
+                    self.expr_operand(&op.high)?,
+                    self.expr_operand(&op.low)?,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::StructField(op) => (HLIL_STRUCT_FIELD, self.struct_field_operands(op)?),
+            Lifted::DerefField(op) => (HLIL_DEREF_FIELD, self.struct_field_operands(op)?),
+            Lifted::Switch(op) => {
+                let condition = self.expr_operand(&op.condition)?;
+                let default = self.expr_operand(&op.default)?;
+                let [count, list] = self.expr_list(&op.cases)?;
+                (HLIL_SWITCH, [condition, default, count, list, 0])
+            }
+            Lifted::Syscall(op) => {
+                let [count, list] = self.expr_list(&op.params)?;
+                (HLIL_SYSCALL, [count, list, 0, 0, 0])
+            }
+            Lifted::SyscallSsa(op) => {
+                let [count, list] = self.expr_list(&op.params)?;
+                (
+                    HLIL_SYSCALL_SSA,
+                    [count, list, op.dest_memory, op.src_memory, 0],
+                )
+            }
+            Lifted::Trap(op) => (HLIL_TRAP, [op.vector, 0, 0, 0, 0]),
+            Lifted::VarDeclare(op) => (HLIL_VAR_DECLARE, [op.var.to_identifier(), 0, 0, 0, 0]),
+            Lifted::Var(op) => (HLIL_VAR, [op.var.to_identifier(), 0, 0, 0, 0]),
+            Lifted::VarInit(op) => (
+                HLIL_VAR_INIT,
+                [
+                    op.dest.to_identifier(),
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::VarInitSsa(op) => (
+                HLIL_VAR_INIT_SSA,
+                [
+                    op.dest.variable.to_identifier(),
+                    op.dest.version as u64,
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::VarPhi(op) => {
+                let [count, list] = self.ssa_var_list(&op.src);
+                (
+                    HLIL_VAR_PHI,
+                    [
+                        op.dest.variable.to_identifier(),
+                        op.dest.version as u64,
+                        count,
+                        list,
+                        0,
+                    ],
+                )
+            }
+            Lifted::VarSsa(op) => (
+                HLIL_VAR_SSA,
+                [
+                    op.var.variable.to_identifier(),
+                    op.var.version as u64,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::While(op) => (HLIL_WHILE, self.while_operands(op)?),
+            Lifted::DoWhile(op) => (
+                HLIL_DO_WHILE,
+                [
+                    self.expr_operand(&op.body)?,
+                    self.expr_operand(&op.condition)?,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::WhileSsa(op) => (HLIL_WHILE_SSA, self.while_ssa_operands(op)?),
+            Lifted::DoWhileSsa(op) => (HLIL_DO_WHILE_SSA, self.while_ssa_operands(op)?),
+        };
+
+        let [a, b, c, d, e] = operands;
+        let index = unsafe {
+            BNHighLevelILAddExprWithLocation(
+                self.handle,
+                operation,
+                expr.address,
+                BN_INVALID_OPERAND,
+                expr.size,
+                a,
+                b,
+                c,
+                d,
+                e,
+            )
+        };
+        Ok(index)
+    }
+
+    /// Replace the expression `expr` with the expression `new_expr`, in place
+    pub fn replace_expr(&self, expr: usize, new_expr: usize) {
+        unsafe { BNReplaceHighLevelILExpr(self.handle, expr, new_expr) }
+    }
+
+    /// Set the expression `expr` as the root of the AST of the function
+    pub fn set_root(&self, expr: usize) {
+        unsafe { BNSetHighLevelILRootExpr(self.handle, expr) }
+    }
+
+    /// ends the function and computes the list of basic blocks.
+    pub fn finalize(&self) {
+        unsafe { BNFinalizeHighLevelILFunction(self.handle) }
+    }
+
+    /// Generate SSA form given the current HLIL
+    ///
+    /// * `aliases` - list of variables known to be aliased
+    pub fn generate_ssa_form(&self, aliases: impl IntoIterator<Item = Variable>) {
+        let mut aliases: Box<[_]> = aliases.into_iter().map(|x| x.raw()).collect();
+        let (aliases_ptr, aliases_len) = if aliases.is_empty() {
+            (core::ptr::null_mut(), 0)
+        } else {
+            (aliases.as_mut_ptr(), aliases.len())
+        };
+        unsafe { BNGenerateHighLevelILSSAForm(self.handle, aliases_ptr, aliases_len) }
+    }
+
+    fn expr_operand(&self, expr: &HighLevelILLiftedInstruction) -> Result<u64, ()> {
+        Ok(self.add_expr(expr)? as u64)
+    }
+
+    /// Returns the number of operands and the index of the list
+    fn operand_list(&self, operands: &[u64]) -> [u64; 2] {
+        let mut operands = operands.to_vec();
+        let list = unsafe {
+            BNHighLevelILAddOperandList(self.handle, operands.as_mut_ptr(), operands.len())
+        };
+        [operands.len() as u64, list as u64]
+    }
+
+    fn expr_list(&self, exprs: &[HighLevelILLiftedInstruction]) -> Result<[u64; 2], ()> {
+        let exprs: Vec<u64> = exprs
+            .iter()
+            .map(|expr| self.expr_operand(expr))
+            .collect::<Result<_, _>>()?;
+        Ok(self.operand_list(&exprs))
+    }
+
+    fn ssa_var_list(&self, vars: &[SSAVariable]) -> [u64; 2] {
+        let vars: Vec<u64> = vars
+            .iter()
+            .flat_map(|var| [var.variable.to_identifier(), var.version as u64])
+            .collect();
+        self.operand_list(&vars)
+    }
+
+    fn unary_op_operands(&self, op: &LiftedUnaryOp) -> Result<[u64; 5], ()> {
+        Ok([self.expr_operand(&op.src)?, 0, 0, 0, 0])
+    }
+
+    fn binary_op_operands(&self, op: &LiftedBinaryOp) -> Result<[u64; 5], ()> {
+        Ok([
+            self.expr_operand(&op.left)?,
+            self.expr_operand(&op.right)?,
+            0,
+            0,
+            0,
+        ])
+    }
+
+    fn binary_op_carry_operands(&self, op: &LiftedBinaryOpCarry) -> Result<[u64; 5], ()> {
+        Ok([
+            self.expr_operand(&op.left)?,
+            self.expr_operand(&op.right)?,
+            self.expr_operand(&op.carry)?,
+            0,
+            0,
+        ])
+    }
+
+    fn call_operands(&self, op: &LiftedCall) -> Result<[u64; 5], ()> {
+        let dest = self.expr_operand(&op.dest)?;
+        let [count, list] = self.expr_list(&op.params)?;
+        Ok([dest, count, list, 0, 0])
+    }
+
+    fn struct_field_operands(&self, op: &LiftedStructField) -> Result<[u64; 5], ()> {
+        Ok([
+            self.expr_operand(&op.src)?,
+            op.offset,
+            get_raw_member_index(op.member_index),
+            0,
+            0,
+        ])
+    }
+
+    fn while_operands(&self, op: &LiftedWhile) -> Result<[u64; 5], ()> {
+        Ok([
+            self.expr_operand(&op.condition)?,
+            self.expr_operand(&op.body)?,
+            0,
+            0,
+            0,
+        ])
+    }
+
+    fn while_ssa_operands(&self, op: &LiftedWhileSsa) -> Result<[u64; 5], ()> {
+        Ok([
+            self.expr_operand(&op.condition_phi)?,
+            self.expr_operand(&op.condition)?,
+            self.expr_operand(&op.body)?,
+            0,
+            0,
+        ])
+    }
+}
+
+fn get_raw_member_index(idx: Option<usize>) -> u64 {
+    idx.map(|idx| idx as u64).unwrap_or(u64::MAX)
+}
@@ -1,17 +1,23 @@
 use std::hash::{Hash, Hasher};
 
 use binaryninjacore_sys::BNFreeHighLevelILFunction;
+use binaryninjacore_sys::BNFreeILInstructionList;
 use binaryninjacore_sys::BNGetHighLevelILBasicBlockList;
 use binaryninjacore_sys::BNGetHighLevelILIndexForInstruction;
 use binaryninjacore_sys::BNGetHighLevelILInstructionCount;
 use binaryninjacore_sys::BNGetHighLevelILOwnerFunction;
 use binaryninjacore_sys::BNGetHighLevelILRootExpr;
 use binaryninjacore_sys::BNGetHighLevelILSSAForm;
+use binaryninjacore_sys::BNGetMediumLevelILExprCount;
+use binaryninjacore_sys::BNGetMediumLevelILExprIndexFromHighLevelIL;
+use binaryninjacore_sys::BNGetMediumLevelILExprIndexesFromHighLevelIL;
+use binaryninjacore_sys::BNGetMediumLevelILForHighLevelILFunction;
 use binaryninjacore_sys::BNHighLevelILFunction;
 use binaryninjacore_sys::BNNewHighLevelILFunctionReference;
 
 use crate::basicblock::BasicBlock;
 use crate::function::Function;
+use crate::mlil::MediumLevelILFunction;
 use crate::rc::{Array, Ref, RefCountable};
 
 use super::{HighLevelILBlock, HighLevelILInstruction, HighLevelILLiftedInstruction};
@@ -97,6 +103,34 @@ impl HighLevelILFunction {
         }
     }
 
+    /// The medium level IL this function was lifted from
+    pub fn medium_level_il(&self) -> Option<Ref<MediumLevelILFunction>> {
+        let mlil = unsafe { BNGetMediumLevelILForHighLevelILFunction(self.handle) };
+        (!mlil.is_null()).then(|| unsafe { Ref::new(MediumLevelILFunction { handle: mlil }) })
+    }
+
+    /// Index of the [HighLevelILFunction::medium_level_il] expression for the expression at
+    /// `expr_idx`
+    pub fn medium_level_il_expr_index(&self, expr_idx: usize) -> Option<usize> {
+        let mlil = self.medium_level_il()?;
+        let result = unsafe { BNGetMediumLevelILExprIndexFromHighLevelIL(self.handle, expr_idx) };
+        (result < unsafe { BNGetMediumLevelILExprCount(mlil.handle) }).then_some(result)
+    }
+
+    /// Indices of all the [HighLevelILFunction::medium_level_il] expressions for the expression
+    /// at `expr_idx`
+    pub fn medium_level_il_expr_indexes(&self, expr_idx: usize) -> Vec<usize> {
+        let mut count = 0;
+        unsafe {
+            let exprs =
+                BNGetMediumLevelILExprIndexesFromHighLevelIL(self.handle, expr_idx, &mut count);
+            assert!(!exprs.is_null());
+            let result = core::slice::from_raw_parts(exprs, count).to_vec();
+            BNFreeILInstructionList(exprs);
+            result
+        }
+    }
+
     pub fn basic_blocks(&self) -> Array<BasicBlock<HighLevelILBlock>> {
         let mut count = 0;
         let blocks = unsafe { BNGetHighLevelILBasicBlockList(self.handle, &mut count) };
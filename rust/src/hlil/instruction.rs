@@ -2,6 +2,7 @@ use binaryninjacore_sys::BNGetHighLevelILByIndex;
 use binaryninjacore_sys::BNHighLevelILOperation;
 
 use crate::architecture::CoreIntrinsic;
+use crate::mlil::MediumLevelILInstruction;
 use crate::operand_iter::OperandIter;
 use crate::rc::Ref;
 use crate::types::{ConstantData, RegisterValue, RegisterValueType, SSAVariable, Variable};
@@ -883,6 +884,25 @@ impl HighLevelILInstruction {
         }
     }
 
+    /// The [HighLevelILFunction::medium_level_il] expression for this expression
+    pub fn medium_level_il(&self) -> Option<MediumLevelILInstruction> {
+        let mlil = self.function.medium_level_il()?;
+        let expr_idx = self.function.medium_level_il_expr_index(self.index)?;
+        Some(mlil.instruction_from_idx(expr_idx))
+    }
+
+    /// All the [HighLevelILFunction::medium_level_il] expressions for this expression
+    pub fn medium_level_il_exprs(&self) -> Vec<MediumLevelILInstruction> {
+        let Some(mlil) = self.function.medium_level_il() else {
+            return vec![];
+        };
+        self.function
+            .medium_level_il_expr_indexes(self.index)
+            .into_iter()
+            .map(|expr_idx| mlil.instruction_from_idx(expr_idx))
+            .collect()
+    }
+
     fn lift_operand(&self, expr_idx: usize) -> Box<HighLevelILLiftedInstruction> {
         Box::new(self.function.lifted_instruction_from_idx(expr_idx))
     }
@@ -1,4 +1,5 @@
 mod block;
+mod builder;
 mod function;
 mod instruction;
 mod lift;
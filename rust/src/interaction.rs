@@ -19,10 +19,13 @@ use binaryninjacore_sys::*;
 use std::ffi::CStr;
 use std::os::raw::{c_char, c_void};
 use std::path::PathBuf;
+use std::ptr;
+use std::slice;
 
 use crate::binaryview::BinaryView;
-use crate::rc::Ref;
-use crate::string::{BnStrCompatible, BnString};
+use crate::flowgraph::FlowGraph;
+use crate::rc::{Ref, RefCountable};
+use crate::string::{raw_to_string, str_from_raw, BnStrCompatible, BnString};
 
 pub fn get_text_line_input(prompt: &str, title: &str) -> Option<String> {
     let mut value: *mut libc::c_char = std::ptr::null_mut();
@@ -585,3 +588,846 @@ pub fn run_progress_dialog<F: Fn(Box<dyn Fn(usize, usize) -> Result<(), ()>>)>(
         Err(())
     }
 }
+
+pub type ReportType = BNReportType;
+
+/// A set of reports shown together, see [InteractionHandler::show_report_collection]
+#[derive(PartialEq, Eq, Hash)]
+pub struct ReportCollection {
+    handle: *mut BNReportCollection,
+}
+
+unsafe impl Send for ReportCollection {}
+unsafe impl Sync for ReportCollection {}
+
+impl ReportCollection {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNReportCollection) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    pub fn new() -> Ref<Self> {
+        unsafe { Self::ref_from_raw(BNCreateReportCollection()) }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { BNGetReportCollectionCount(self.handle) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn report_type(&self, index: usize) -> ReportType {
+        unsafe { BNGetReportType(self.handle, index) }
+    }
+
+    pub fn view(&self, index: usize) -> Option<Ref<BinaryView>> {
+        let view = unsafe { BNGetReportView(self.handle, index) };
+        (!view.is_null()).then(|| unsafe { BinaryView::from_raw(view) })
+    }
+
+    pub fn title(&self, index: usize) -> BnString {
+        unsafe { BnString::from_raw(BNGetReportTitle(self.handle, index)) }
+    }
+
+    /// Contents of a plain text, markdown or HTML report
+    pub fn contents(&self, index: usize) -> BnString {
+        unsafe { BnString::from_raw(BNGetReportContents(self.handle, index)) }
+    }
+
+    /// Plain text alternative of a markdown or HTML report
+    pub fn plain_text(&self, index: usize) -> BnString {
+        unsafe { BnString::from_raw(BNGetReportPlainText(self.handle, index)) }
+    }
+
+    pub fn flow_graph(&self, index: usize) -> Option<Ref<FlowGraph>> {
+        let graph = unsafe { BNGetReportFlowGraph(self.handle, index) };
+        (!graph.is_null()).then(|| unsafe { Ref::new(FlowGraph::from_raw(graph)) })
+    }
+
+    pub fn add_plain_text_report(&self, view: Option<&BinaryView>, title: &str, contents: &str) {
+        unsafe {
+            BNAddPlainTextReportToCollection(
+                self.handle,
+                view.map(|view| view.handle).unwrap_or(ptr::null_mut()),
+                title.into_bytes_with_nul().as_ptr() as *const _,
+                contents.into_bytes_with_nul().as_ptr() as *const _,
+            )
+        }
+    }
+
+    pub fn add_markdown_report(
+        &self,
+        view: Option<&BinaryView>,
+        title: &str,
+        contents: &str,
+        plain_text: &str,
+    ) {
+        unsafe {
+            BNAddMarkdownReportToCollection(
+                self.handle,
+                view.map(|view| view.handle).unwrap_or(ptr::null_mut()),
+                title.into_bytes_with_nul().as_ptr() as *const _,
+                contents.into_bytes_with_nul().as_ptr() as *const _,
+                plain_text.into_bytes_with_nul().as_ptr() as *const _,
+            )
+        }
+    }
+
+    pub fn add_html_report(
+        &self,
+        view: Option<&BinaryView>,
+        title: &str,
+        contents: &str,
+        plain_text: &str,
+    ) {
+        unsafe {
+            BNAddHTMLReportToCollection(
+                self.handle,
+                view.map(|view| view.handle).unwrap_or(ptr::null_mut()),
+                title.into_bytes_with_nul().as_ptr() as *const _,
+                contents.into_bytes_with_nul().as_ptr() as *const _,
+                plain_text.into_bytes_with_nul().as_ptr() as *const _,
+            )
+        }
+    }
+
+    pub fn add_graph_report(&self, view: Option<&BinaryView>, title: &str, graph: &FlowGraph) {
+        unsafe {
+            BNAddGraphReportToCollection(
+                self.handle,
+                view.map(|view| view.handle).unwrap_or(ptr::null_mut()),
+                title.into_bytes_with_nul().as_ptr() as *const _,
+                graph.handle,
+            )
+        }
+    }
+
+    /// Show all the reports of the collection in a single window
+    pub fn show(&self, title: &str) {
+        unsafe {
+            BNShowReportCollection(
+                title.into_bytes_with_nul().as_ptr() as *const _,
+                self.handle,
+            )
+        }
+    }
+}
+
+impl ToOwned for ReportCollection {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for ReportCollection {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewReportCollectionReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeReportCollection(handle.handle);
+    }
+}
+
+/// A field of a form shown by [InteractionHandler::get_form_input]
+///
+/// The `value` of each field starts as its default, or an empty value, and holds the answer
+/// of the handler once the form is completed.
+pub enum FormInputField {
+    Label {
+        prompt: String,
+    },
+    Separator,
+    TextLine {
+        prompt: String,
+        default: Option<String>,
+        value: String,
+    },
+    MultilineText {
+        prompt: String,
+        default: Option<String>,
+        value: String,
+    },
+    Integer {
+        prompt: String,
+        default: Option<i64>,
+        value: i64,
+    },
+    Address {
+        prompt: String,
+        view: Option<Ref<BinaryView>>,
+        current_address: u64,
+        default: Option<u64>,
+        value: u64,
+    },
+    Choice {
+        prompt: String,
+        choices: Vec<String>,
+        default: Option<usize>,
+        value: usize,
+    },
+    OpenFileName {
+        prompt: String,
+        ext: String,
+        default: Option<String>,
+        value: String,
+    },
+    SaveFileName {
+        prompt: String,
+        ext: String,
+        default_name: String,
+        default: Option<String>,
+        value: String,
+    },
+    DirectoryName {
+        prompt: String,
+        default_name: String,
+        default: Option<String>,
+        value: String,
+    },
+}
+
+impl FormInputField {
+    unsafe fn from_raw(field: &BNFormInputField) -> Self {
+        let prompt = raw_to_string(field.prompt).unwrap_or_default();
+        let string_default = || {
+            field
+                .hasDefault
+                .then(|| raw_to_string(field.stringDefault).unwrap_or_default())
+        };
+        match field.type_ {
+            BNFormInputFieldType::LabelFormField => Self::Label { prompt },
+            BNFormInputFieldType::SeparatorFormField => Self::Separator,
+            BNFormInputFieldType::TextLineFormField => {
+                let default = string_default();
+                Self::TextLine {
+                    prompt,
+                    value: default.clone().unwrap_or_default(),
+                    default,
+                }
+            }
+            BNFormInputFieldType::MultilineTextFormField => {
+                let default = string_default();
+                Self::MultilineText {
+                    prompt,
+                    value: default.clone().unwrap_or_default(),
+                    default,
+                }
+            }
+            BNFormInputFieldType::IntegerFormField => {
+                let default = field.hasDefault.then_some(field.intDefault);
+                Self::Integer {
+                    prompt,
+                    default,
+                    value: default.unwrap_or_default(),
+                }
+            }
+            BNFormInputFieldType::AddressFormField => {
+                let default = field.hasDefault.then_some(field.addressDefault);
+                Self::Address {
+                    prompt,
+                    view: (!field.view.is_null())
+                        .then(|| BinaryView::from_raw(BNNewViewReference(field.view))),
+                    current_address: field.currentAddress,
+                    default,
+                    value: default.unwrap_or(field.currentAddress),
+                }
+            }
+            BNFormInputFieldType::ChoiceFormField => {
+                let choices = if field.choices.is_null() {
+                    vec![]
+                } else {
+                    slice::from_raw_parts(field.choices, field.count)
+                        .iter()
+                        .map(|choice| raw_to_string(*choice).unwrap_or_default())
+                        .collect()
+                };
+                let default = field.hasDefault.then_some(field.indexDefault);
+                Self::Choice {
+                    prompt,
+                    choices,
+                    default,
+                    value: default.unwrap_or_default(),
+                }
+            }
+            BNFormInputFieldType::OpenFileNameFormField => {
+                let default = string_default();
+                Self::OpenFileName {
+                    prompt,
+                    ext: raw_to_string(field.ext).unwrap_or_default(),
+                    value: default.clone().unwrap_or_default(),
+                    default,
+                }
+            }
+            BNFormInputFieldType::SaveFileNameFormField => {
+                let default = string_default();
+                Self::SaveFileName {
+                    prompt,
+                    ext: raw_to_string(field.ext).unwrap_or_default(),
+                    default_name: raw_to_string(field.defaultName).unwrap_or_default(),
+                    value: default.clone().unwrap_or_default(),
+                    default,
+                }
+            }
+            BNFormInputFieldType::DirectoryNameFormField => {
+                let default = string_default();
+                Self::DirectoryName {
+                    prompt,
+                    default_name: raw_to_string(field.defaultName).unwrap_or_default(),
+                    value: default.clone().unwrap_or_default(),
+                    default,
+                }
+            }
+        }
+    }
+
+    /// Store the answer to the field in the result of `field`, allocating the strings with the
+    /// core so they are released by `BNFreeFormInputResults`
+    unsafe fn write_result(&self, field: &mut BNFormInputField) {
+        match self {
+            Self::Label { .. } | Self::Separator => {}
+            Self::TextLine { value, .. }
+            | Self::MultilineText { value, .. }
+            | Self::OpenFileName { value, .. }
+            | Self::SaveFileName { value, .. }
+            | Self::DirectoryName { value, .. } => {
+                field.stringResult = BnString::new(value.as_str()).into_raw();
+            }
+            Self::Integer { value, .. } => field.intResult = *value,
+            Self::Address { value, .. } => field.addressResult = *value,
+            Self::Choice { value, .. } => field.indexResult = *value,
+        }
+    }
+}
+
+/// The task of a progress dialog, see [InteractionHandler::run_progress_dialog]
+pub struct ProgressDialogTask {
+    task: unsafe extern "C" fn(
+        *mut c_void,
+        Option<unsafe extern "C" fn(*mut c_void, usize, usize) -> bool>,
+        *mut c_void,
+    ),
+    ctxt: *mut c_void,
+}
+
+impl ProgressDialogTask {
+    /// Run the task, `progress` is called with the current and maximum progress and returns
+    /// `false` to cancel the task
+    pub fn run<F: FnMut(usize, usize) -> bool>(&self, mut progress: F) {
+        unsafe extern "C" fn cb_progress<F: FnMut(usize, usize) -> bool>(
+            ctxt: *mut c_void,
+            cur: usize,
+            max: usize,
+        ) -> bool {
+            ffi_wrap!("ProgressDialogTask::progress", {
+                let progress = &mut *(ctxt as *mut F);
+                progress(cur, max)
+            })
+        }
+
+        unsafe {
+            (self.task)(
+                self.ctxt,
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+            )
+        }
+    }
+}
+
+/// Answers the prompts and shows the reports requested through the functions of this module,
+/// by the core or by plugins, see [register_interaction_handler]
+///
+/// Only the text line, choice and form inputs, and the plain text reports must be handled,
+/// the other methods fall back to them.
+pub trait InteractionHandler: 'static + Sync {
+    fn show_message_box(
+        &self,
+        title: &str,
+        text: &str,
+        buttons: MessageBoxButtonSet,
+        icon: MessageBoxIcon,
+    ) -> MessageBoxButtonResult;
+
+    fn open_url(&self, _url: &str) -> bool {
+        false
+    }
+
+    fn show_plain_text_report(&self, view: Option<&BinaryView>, title: &str, contents: &str);
+
+    fn show_markdown_report(
+        &self,
+        view: Option<&BinaryView>,
+        title: &str,
+        _contents: &str,
+        plain_text: &str,
+    ) {
+        if !plain_text.is_empty() {
+            self.show_plain_text_report(view, title, plain_text)
+        }
+    }
+
+    fn show_html_report(
+        &self,
+        view: Option<&BinaryView>,
+        title: &str,
+        _contents: &str,
+        plain_text: &str,
+    ) {
+        if !plain_text.is_empty() {
+            self.show_plain_text_report(view, title, plain_text)
+        }
+    }
+
+    fn show_graph_report(&self, _view: Option<&BinaryView>, _title: &str, _graph: &FlowGraph) {}
+
+    fn show_report_collection(&self, _title: &str, reports: &ReportCollection) {
+        for i in 0..reports.len() {
+            let view = reports.view(i);
+            let view = view.as_deref();
+            let title = reports.title(i);
+            match reports.report_type(i) {
+                ReportType::PlainTextReportType => {
+                    self.show_plain_text_report(view, title.as_str(), reports.contents(i).as_str())
+                }
+                ReportType::MarkdownReportType => self.show_markdown_report(
+                    view,
+                    title.as_str(),
+                    reports.contents(i).as_str(),
+                    reports.plain_text(i).as_str(),
+                ),
+                ReportType::HTMLReportType => self.show_html_report(
+                    view,
+                    title.as_str(),
+                    reports.contents(i).as_str(),
+                    reports.plain_text(i).as_str(),
+                ),
+                ReportType::FlowGraphReportType => {
+                    if let Some(graph) = reports.flow_graph(i) {
+                        self.show_graph_report(view, title.as_str(), &graph)
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_text_line_input(&self, prompt: &str, title: &str) -> Option<String>;
+
+    /// Integer parsed from [InteractionHandler::get_text_line_input], in decimal or hexadecimal
+    /// with a `0x` prefix, `None` if the input is empty or not a number
+    fn get_integer_input(&self, prompt: &str, title: &str) -> Option<i64> {
+        let input = self.get_text_line_input(prompt, title)?;
+        let input = input.trim();
+        let (digits, negative) = match input.strip_prefix('-') {
+            Some(digits) => (digits, true),
+            None => (input, false),
+        };
+        let value = match digits.strip_prefix("0x").or(digits.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16).ok().map(|value| value as i64),
+            None => digits
+                .parse::<i64>()
+                .ok()
+                .or_else(|| digits.parse::<u64>().ok().map(|value| value as i64)),
+        }?;
+        Some(if negative {
+            value.wrapping_neg()
+        } else {
+            value
+        })
+    }
+
+    fn get_address_input(
+        &self,
+        prompt: &str,
+        title: &str,
+        _view: Option<&BinaryView>,
+        _current_address: u64,
+    ) -> Option<u64> {
+        self.get_integer_input(prompt, title)
+            .map(|value| value as u64)
+    }
+
+    fn get_choice_input(&self, prompt: &str, title: &str, choices: &[String]) -> Option<usize>;
+
+    /// Choice among a list too long for a drop-down, eg: all the functions of a view
+    fn get_large_choice_input(
+        &self,
+        prompt: &str,
+        title: &str,
+        choices: &[String],
+    ) -> Option<usize> {
+        self.get_choice_input(prompt, title, choices)
+    }
+
+    fn get_open_file_name_input(&self, prompt: &str, _ext: &str) -> Option<String> {
+        self.get_text_line_input(prompt, "Open File")
+    }
+
+    fn get_save_file_name_input(
+        &self,
+        prompt: &str,
+        _ext: &str,
+        _default_name: &str,
+    ) -> Option<String> {
+        self.get_text_line_input(prompt, "Save File")
+    }
+
+    fn get_directory_name_input(&self, prompt: &str, _default_name: &str) -> Option<String> {
+        self.get_text_line_input(prompt, "Select Directory")
+    }
+
+    /// Fill the `value` of each of `fields`, returns `false` if the form was cancelled
+    fn get_form_input(&self, fields: &mut [FormInputField], title: &str) -> bool;
+
+    /// Run `task` while showing its progress, returns `false` if it was cancelled
+    fn run_progress_dialog(
+        &self,
+        _title: &str,
+        _can_cancel: bool,
+        task: &ProgressDialogTask,
+    ) -> bool {
+        task.run(|_, _| true);
+        true
+    }
+}
+
+/// Register `handler` to answer all the prompts and show all the reports, replacing the
+/// handler of the UI or the default handler of headless sessions
+pub fn register_interaction_handler<H: InteractionHandler>(handler: H) {
+    let ctxt = Box::into_raw(Box::new(handler));
+    let mut callbacks = BNInteractionHandlerCallbacks {
+        context: ctxt as *mut c_void,
+        showPlainTextReport: Some(cb_show_plain_text_report::<H>),
+        showMarkdownReport: Some(cb_show_markdown_report::<H>),
+        showHTMLReport: Some(cb_show_html_report::<H>),
+        showGraphReport: Some(cb_show_graph_report::<H>),
+        showReportCollection: Some(cb_show_report_collection::<H>),
+        getTextLineInput: Some(cb_get_text_line_input::<H>),
+        getIntegerInput: Some(cb_get_integer_input::<H>),
+        getAddressInput: Some(cb_get_address_input::<H>),
+        getChoiceInput: Some(cb_get_choice_input::<H>),
+        getLargeChoiceInput: Some(cb_get_large_choice_input::<H>),
+        getOpenFileNameInput: Some(cb_get_open_file_name_input::<H>),
+        getSaveFileNameInput: Some(cb_get_save_file_name_input::<H>),
+        getDirectoryNameInput: Some(cb_get_directory_name_input::<H>),
+        getFormInput: Some(cb_get_form_input::<H>),
+        showMessageBox: Some(cb_show_message_box::<H>),
+        openUrl: Some(cb_open_url::<H>),
+        runProgressDialog: Some(cb_run_progress_dialog::<H>),
+    };
+    unsafe { BNRegisterInteractionHandler(&mut callbacks) }
+}
+
+unsafe fn choices_from_raw(choices: *mut *const c_char, count: usize) -> Vec<String> {
+    if choices.is_null() {
+        return vec![];
+    }
+    slice::from_raw_parts(choices, count)
+        .iter()
+        .map(|choice| str_from_raw(*choice).into_owned())
+        .collect()
+}
+
+unsafe fn write_string_result(value: Option<String>, result: *mut *mut c_char) -> bool {
+    match value {
+        Some(value) => {
+            *result = BnString::new(value).into_raw();
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe fn write_result<T>(value: Option<T>, result: *mut T) -> bool {
+    match value {
+        Some(value) => {
+            *result = value;
+            true
+        }
+        None => false,
+    }
+}
+
+extern "C" fn cb_show_plain_text_report<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    title: *const c_char,
+    contents: *const c_char,
+) {
+    ffi_wrap!("InteractionHandler::show_plain_text_report", unsafe {
+        let handler = &*(ctxt as *const H);
+        let view = (!view.is_null()).then_some(BinaryView { handle: view });
+        handler.show_plain_text_report(view.as_ref(), &str_from_raw(title), &str_from_raw(contents))
+    })
+}
+
+extern "C" fn cb_show_markdown_report<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    title: *const c_char,
+    contents: *const c_char,
+    plain_text: *const c_char,
+) {
+    ffi_wrap!("InteractionHandler::show_markdown_report", unsafe {
+        let handler = &*(ctxt as *const H);
+        let view = (!view.is_null()).then_some(BinaryView { handle: view });
+        handler.show_markdown_report(
+            view.as_ref(),
+            &str_from_raw(title),
+            &str_from_raw(contents),
+            &str_from_raw(plain_text),
+        )
+    })
+}
+
+extern "C" fn cb_show_html_report<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    title: *const c_char,
+    contents: *const c_char,
+    plain_text: *const c_char,
+) {
+    ffi_wrap!("InteractionHandler::show_html_report", unsafe {
+        let handler = &*(ctxt as *const H);
+        let view = (!view.is_null()).then_some(BinaryView { handle: view });
+        handler.show_html_report(
+            view.as_ref(),
+            &str_from_raw(title),
+            &str_from_raw(contents),
+            &str_from_raw(plain_text),
+        )
+    })
+}
+
+extern "C" fn cb_show_graph_report<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+    title: *const c_char,
+    graph: *mut BNFlowGraph,
+) {
+    ffi_wrap!("InteractionHandler::show_graph_report", unsafe {
+        let handler = &*(ctxt as *const H);
+        let view = (!view.is_null()).then_some(BinaryView { handle: view });
+        handler.show_graph_report(
+            view.as_ref(),
+            &str_from_raw(title),
+            &FlowGraph::from_raw(graph),
+        )
+    })
+}
+
+extern "C" fn cb_show_report_collection<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    title: *const c_char,
+    reports: *mut BNReportCollection,
+) {
+    ffi_wrap!("InteractionHandler::show_report_collection", unsafe {
+        let handler = &*(ctxt as *const H);
+        handler.show_report_collection(&str_from_raw(title), &ReportCollection { handle: reports })
+    })
+}
+
+extern "C" fn cb_get_text_line_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    result: *mut *mut c_char,
+    prompt: *const c_char,
+    title: *const c_char,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_text_line_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let value = handler.get_text_line_input(&str_from_raw(prompt), &str_from_raw(title));
+        write_string_result(value, result)
+    })
+}
+
+extern "C" fn cb_get_integer_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    result: *mut i64,
+    prompt: *const c_char,
+    title: *const c_char,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_integer_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let value = handler.get_integer_input(&str_from_raw(prompt), &str_from_raw(title));
+        write_result(value, result)
+    })
+}
+
+extern "C" fn cb_get_address_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    result: *mut u64,
+    prompt: *const c_char,
+    title: *const c_char,
+    view: *mut BNBinaryView,
+    current_address: u64,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_address_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let view = (!view.is_null()).then_some(BinaryView { handle: view });
+        let value = handler.get_address_input(
+            &str_from_raw(prompt),
+            &str_from_raw(title),
+            view.as_ref(),
+            current_address,
+        );
+        write_result(value, result)
+    })
+}
+
+extern "C" fn cb_get_choice_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    result: *mut usize,
+    prompt: *const c_char,
+    title: *const c_char,
+    choices: *mut *const c_char,
+    count: usize,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_choice_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let value = handler.get_choice_input(
+            &str_from_raw(prompt),
+            &str_from_raw(title),
+            &choices_from_raw(choices, count),
+        );
+        write_result(value, result)
+    })
+}
+
+extern "C" fn cb_get_large_choice_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    result: *mut usize,
+    prompt: *const c_char,
+    title: *const c_char,
+    choices: *mut *const c_char,
+    count: usize,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_large_choice_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let value = handler.get_large_choice_input(
+            &str_from_raw(prompt),
+            &str_from_raw(title),
+            &choices_from_raw(choices, count),
+        );
+        write_result(value, result)
+    })
+}
+
+extern "C" fn cb_get_open_file_name_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    result: *mut *mut c_char,
+    prompt: *const c_char,
+    ext: *const c_char,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_open_file_name_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let value = handler.get_open_file_name_input(&str_from_raw(prompt), &str_from_raw(ext));
+        write_string_result(value, result)
+    })
+}
+
+extern "C" fn cb_get_save_file_name_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    result: *mut *mut c_char,
+    prompt: *const c_char,
+    ext: *const c_char,
+    default_name: *const c_char,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_save_file_name_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let value = handler.get_save_file_name_input(
+            &str_from_raw(prompt),
+            &str_from_raw(ext),
+            &str_from_raw(default_name),
+        );
+        write_string_result(value, result)
+    })
+}
+
+extern "C" fn cb_get_directory_name_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    result: *mut *mut c_char,
+    prompt: *const c_char,
+    default_name: *const c_char,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_directory_name_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let value =
+            handler.get_directory_name_input(&str_from_raw(prompt), &str_from_raw(default_name));
+        write_string_result(value, result)
+    })
+}
+
+extern "C" fn cb_get_form_input<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    fields: *mut BNFormInputField,
+    count: usize,
+    title: *const c_char,
+) -> bool {
+    ffi_wrap!("InteractionHandler::get_form_input", unsafe {
+        let handler = &*(ctxt as *const H);
+        let raw_fields = slice::from_raw_parts_mut(fields, count);
+        let mut form: Vec<_> = raw_fields
+            .iter()
+            .map(|field| FormInputField::from_raw(field))
+            .collect();
+        if !handler.get_form_input(&mut form, &str_from_raw(title)) {
+            return false;
+        }
+        for (field, raw_field) in form.iter().zip(raw_fields.iter_mut()) {
+            field.write_result(raw_field);
+        }
+        true
+    })
+}
+
+extern "C" fn cb_show_message_box<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    title: *const c_char,
+    text: *const c_char,
+    buttons: BNMessageBoxButtonSet,
+    icon: BNMessageBoxIcon,
+) -> BNMessageBoxButtonResult {
+    ffi_wrap!("InteractionHandler::show_message_box", unsafe {
+        let handler = &*(ctxt as *const H);
+        handler.show_message_box(&str_from_raw(title), &str_from_raw(text), buttons, icon)
+    })
+}
+
+extern "C" fn cb_open_url<H: InteractionHandler>(ctxt: *mut c_void, url: *const c_char) -> bool {
+    ffi_wrap!("InteractionHandler::open_url", unsafe {
+        let handler = &*(ctxt as *const H);
+        handler.open_url(&str_from_raw(url))
+    })
+}
+
+extern "C" fn cb_run_progress_dialog<H: InteractionHandler>(
+    ctxt: *mut c_void,
+    title: *const c_char,
+    can_cancel: bool,
+    task: Option<
+        unsafe extern "C" fn(
+            *mut c_void,
+            Option<unsafe extern "C" fn(*mut c_void, usize, usize) -> bool>,
+            *mut c_void,
+        ),
+    >,
+    task_ctxt: *mut c_void,
+) -> bool {
+    ffi_wrap!("InteractionHandler::run_progress_dialog", unsafe {
+        let handler = &*(ctxt as *const H);
+        let Some(task) = task else {
+            return false;
+        };
+        let task = ProgressDialogTask {
+            task,
+            ctxt: task_ctxt,
+        };
+        handler.run_progress_dialog(&str_from_raw(title), can_cancel, &task)
+    })
+}
@@ -0,0 +1,84 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The high level representation of a function, as shown by the decompiler views.
+//!
+//! Only the representations created by the core are available, the core API has no way to
+//! register a custom language representation or token emitter.
+
+use binaryninjacore_sys::*;
+
+use crate::architecture::CoreArchitecture;
+use crate::function::Function;
+use crate::rc::{Ref, RefCountable};
+
+#[derive(PartialEq, Eq, Hash)]
+pub struct LanguageRepresentationFunction {
+    pub(crate) handle: *mut BNLanguageRepresentationFunction,
+}
+
+unsafe impl Send for LanguageRepresentationFunction {}
+unsafe impl Sync for LanguageRepresentationFunction {}
+
+impl LanguageRepresentationFunction {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNLanguageRepresentationFunction) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    /// Create a new, empty, language representation for `function`
+    pub fn new(arch: CoreArchitecture, function: &Function) -> Ref<Self> {
+        let handle = unsafe { BNCreateLanguageRepresentationFunction(arch.0, function.handle) };
+        assert!(!handle.is_null());
+        unsafe { Self::ref_from_raw(handle) }
+    }
+
+    /// Function this representation was generated from
+    pub fn owner_function(&self) -> Ref<Function> {
+        unsafe {
+            let func = BNGetLanguageRepresentationOwnerFunction(self.handle);
+            Function::from_raw(func)
+        }
+    }
+}
+
+impl ToOwned for LanguageRepresentationFunction {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for LanguageRepresentationFunction {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewLanguageRepresentationFunctionReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeLanguageRepresentationFunction(handle.handle);
+    }
+}
+
+impl core::fmt::Debug for LanguageRepresentationFunction {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "<language representation of {:?}>",
+            self.owner_function()
+        )
+    }
+}
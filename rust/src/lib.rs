@@ -126,15 +126,19 @@ mod operand_iter;
 
 pub mod architecture;
 pub mod backgroundtask;
+pub mod basedetection;
 pub mod basicblock;
 pub mod binaryreader;
 pub mod binaryview;
 pub mod binarywriter;
+pub mod callgraph;
 pub mod callingconvention;
+pub mod collaboration;
 pub mod command;
 pub mod custombinaryview;
 pub mod database;
 pub mod databuffer;
+pub mod datarenderer;
 pub mod debuginfo;
 pub mod demangle;
 pub mod disassembly;
@@ -150,16 +154,22 @@ pub mod functionrecognizer;
 pub mod headless;
 pub mod hlil;
 pub mod interaction;
+pub mod languagerepresentation;
 pub mod linearview;
 pub mod llil;
 pub mod logger;
+pub mod mainthread;
 pub mod metadata;
 pub mod mlil;
 pub mod platform;
+pub mod pluginmanager;
 pub mod project;
 pub mod rc;
 pub mod references;
 pub mod relocation;
+pub mod scriptingprovider;
+pub mod search;
+pub mod secrets;
 pub mod section;
 pub mod segment;
 pub mod settings;
@@ -167,10 +177,17 @@ pub mod string;
 pub mod symbol;
 pub mod tags;
 pub mod templatesimplifier;
-pub mod typelibrary;
+pub mod transform;
 pub mod typearchive;
+pub mod typecontainer;
+pub mod typelibrary;
+pub mod typeparser;
+pub mod typeprinter;
 pub mod types;
 pub mod update;
+pub mod websocketprovider;
+pub mod worker;
+pub mod workflow;
 
 use std::path::PathBuf;
 
@@ -192,7 +209,6 @@ use string::IntoJson;
 // const BN_INVALID_REGISTER: usize = 0xffffffff;
 // const BN_AUTOCOERCE_EXTERN_PTR: u64 = 0xfffffffd;
 // const BN_NOCOERCE_EXTERN_PTR: u64 = 0xfffffffe;
-// const BN_INVALID_OPERAND: u64 = 0xffffffff;
 // const BN_MAX_STRING_LENGTH: u64 = 128;
 // const BN_MAX_VARIABLE_OFFSET: u64 = 0x7fffffffff;
 // const BN_MAX_VARIABLE_INDEX: u64 = 0xfffff;
@@ -201,6 +217,7 @@ use string::IntoJson;
 
 const BN_FULL_CONFIDENCE: u8 = 255;
 const BN_INVALID_EXPR: usize = usize::MAX;
+const BN_INVALID_OPERAND: u32 = 0xffffffff;
 
 /// The main way to open and load files into Binary Ninja. Make sure you've properly initialized the core before calling this function. See [`crate::headless::init()`]
 pub fn load<S: BnStrCompatible>(filename: S) -> Option<rc::Ref<binaryview::BinaryView>> {
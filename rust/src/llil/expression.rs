@@ -24,6 +24,7 @@ use super::*;
 
 use crate::architecture::Architecture;
 use crate::architecture::RegisterInfo;
+use crate::mlil::MediumLevelILInstruction;
 
 // used as a marker for Expressions that can produce a value
 #[derive(Copy, Clone, Debug)]
@@ -358,8 +359,7 @@ where
         };
 
         match info {
-            // TODO ssa
-            Load(ref _op) => {} //visit!(Self::visit_tree, &op.source_mem_expr(), f),
+            Load(ref op) => visit!(Self::visit_tree, &op.source_mem_expr(), f),
             _ => {
                 let mut fb = |e: &Self| e.visit_tree(f);
                 visit!(common_visit, &info, &mut fb);
@@ -376,6 +376,34 @@ where
     F: FunctionForm,
 {
     // TODO possible values
+
+    /// The [Function::medium_level_il] expression for this expression
+    pub fn medium_level_il(&self) -> Option<MediumLevelILInstruction> {
+        let mlil = self.function.medium_level_il()?;
+        let expr_idx = self.function.medium_level_il_expr_index(self.expr_idx)?;
+        Some(mlil.instruction_from_idx(expr_idx))
+    }
+
+    /// All the [Function::medium_level_il] expressions for this expression
+    pub fn medium_level_il_exprs(&self) -> Vec<MediumLevelILInstruction> {
+        let Some(mlil) = self.function.medium_level_il() else {
+            return vec![];
+        };
+        self.function
+            .medium_level_il_expr_indexes(self.expr_idx)
+            .into_iter()
+            .map(|expr_idx| mlil.instruction_from_idx(expr_idx))
+            .collect()
+    }
+
+    /// The [Function::mapped_medium_level_il] expression for this expression
+    pub fn mapped_medium_level_il(&self) -> Option<MediumLevelILInstruction> {
+        let mlil = self.function.mapped_medium_level_il()?;
+        let expr_idx = self
+            .function
+            .mapped_medium_level_il_expr_index(self.expr_idx)?;
+        Some(mlil.instruction_from_idx(expr_idx))
+    }
 }
 
 pub enum ExprInfo<'func, A, M, F>
@@ -22,7 +22,9 @@ use std::marker::PhantomData;
 
 use crate::architecture::CoreArchitecture;
 use crate::basicblock::BasicBlock;
+use crate::mlil::MediumLevelILFunction;
 use crate::rc::*;
+use crate::types::{RegisterValue, Variable, VariableSourceType};
 
 use super::*;
 
@@ -100,6 +102,22 @@ where
         .to_owned()
     }
 
+    /// Take ownership of `handle`, unlike [Function::from_raw] which adds a reference
+    pub(crate) unsafe fn ref_from_raw(
+        borrower: A::Handle,
+        handle: *mut BNLowLevelILFunction,
+    ) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+
+        Ref::new(Self {
+            borrower,
+            handle,
+            _arch: PhantomData,
+            _mutability: PhantomData,
+            _form: PhantomData,
+        })
+    }
+
     pub(crate) fn arch(&self) -> &A {
         self.borrower.borrow()
     }
@@ -139,6 +157,17 @@ where
         }
     }
 
+    pub fn expression_from_idx(&self, expr_idx: usize) -> Expression<'_, A, M, F, ValueExpr> {
+        unsafe {
+            use binaryninjacore_sys::BNGetLowLevelILExprCount;
+            if expr_idx >= BNGetLowLevelILExprCount(self.handle) {
+                panic!("expression index {} out of bounds", expr_idx);
+            }
+
+            Expression::new(self, expr_idx)
+        }
+    }
+
     pub fn instruction_count(&self) -> usize {
         unsafe {
             use binaryninjacore_sys::BNGetLowLevelILInstructionCount;
@@ -146,6 +175,34 @@ where
         }
     }
 
+    /// The register backing the MLIL `var`, [None] if `var` isn't stored in a register
+    pub fn register_for_variable(&self, var: &Variable) -> Option<Register<A::Register>> {
+        if var.t != VariableSourceType::RegisterVariableSourceType {
+            return None;
+        }
+
+        let raw_id = var.storage as u32;
+        if raw_id >= 0x8000_0000 {
+            Some(Register::Temp(raw_id & 0x7fff_ffff))
+        } else {
+            self.arch().register_from_id(raw_id).map(Register::ArchReg)
+        }
+    }
+
+    /// The flag backing the MLIL `var`, [None] if `var` isn't stored in a flag
+    pub fn flag_for_variable(&self, var: &Variable) -> Option<A::Flag> {
+        if var.t != VariableSourceType::FlagVariableSourceType {
+            return None;
+        }
+
+        self.arch().flag_from_id(var.storage as u32)
+    }
+
+    /// The stack offset of the MLIL `var`, [None] if `var` isn't stored on the stack
+    pub fn stack_offset_for_variable(&self, var: &Variable) -> Option<i64> {
+        (var.t == VariableSourceType::StackVariableSourceType).then_some(var.storage)
+    }
+
     pub fn get_function(&self) -> Ref<crate::function::Function> {
         unsafe {
             let func = BNGetLowLevelILOwnerFunction(self.handle);
@@ -173,6 +230,251 @@ where
             Array::new(blocks, count, context)
         }
     }
+
+    /// The medium level IL lifted from this function
+    pub fn medium_level_il(&self) -> Option<Ref<MediumLevelILFunction>> {
+        use binaryninjacore_sys::BNGetMediumLevelILForLowLevelIL;
+
+        let mlil = unsafe { BNGetMediumLevelILForLowLevelIL(self.handle) };
+        (!mlil.is_null()).then(|| unsafe { Ref::new(MediumLevelILFunction { handle: mlil }) })
+    }
+
+    /// The medium level IL lifted from this function with mappings for every instruction, unused
+    /// stores are not removed
+    pub fn mapped_medium_level_il(&self) -> Option<Ref<MediumLevelILFunction>> {
+        use binaryninjacore_sys::BNGetMappedMediumLevelIL;
+
+        let mlil = unsafe { BNGetMappedMediumLevelIL(self.handle) };
+        (!mlil.is_null()).then(|| unsafe { Ref::new(MediumLevelILFunction { handle: mlil }) })
+    }
+
+    /// Index of the [Function::medium_level_il] instruction for the instruction at `instr_idx`
+    pub fn medium_level_il_instruction_index(&self, instr_idx: usize) -> Option<usize> {
+        use binaryninjacore_sys::BNGetMediumLevelILInstructionIndex;
+
+        let mlil = self.medium_level_il()?;
+        let result = unsafe { BNGetMediumLevelILInstructionIndex(self.handle, instr_idx) };
+        (result < mlil.instruction_count()).then_some(result)
+    }
+
+    /// Index of the [Function::medium_level_il] expression for the expression at `expr_idx`
+    pub fn medium_level_il_expr_index(&self, expr_idx: usize) -> Option<usize> {
+        use binaryninjacore_sys::{BNGetMediumLevelILExprCount, BNGetMediumLevelILExprIndex};
+
+        let mlil = self.medium_level_il()?;
+        let result = unsafe { BNGetMediumLevelILExprIndex(self.handle, expr_idx) };
+        (result < unsafe { BNGetMediumLevelILExprCount(mlil.handle) }).then_some(result)
+    }
+
+    /// Indices of all the [Function::medium_level_il] expressions for the expression at `expr_idx`
+    pub fn medium_level_il_expr_indexes(&self, expr_idx: usize) -> Vec<usize> {
+        use binaryninjacore_sys::{BNFreeILInstructionList, BNGetMediumLevelILExprIndexes};
+
+        let mut count = 0;
+        unsafe {
+            let exprs = BNGetMediumLevelILExprIndexes(self.handle, expr_idx, &mut count);
+            assert!(!exprs.is_null());
+            let result = std::slice::from_raw_parts(exprs, count).to_vec();
+            BNFreeILInstructionList(exprs);
+            result
+        }
+    }
+
+    /// Index of the [Function::mapped_medium_level_il] instruction for the instruction at
+    /// `instr_idx`
+    pub fn mapped_medium_level_il_instruction_index(&self, instr_idx: usize) -> Option<usize> {
+        use binaryninjacore_sys::BNGetMappedMediumLevelILInstructionIndex;
+
+        let mlil = self.mapped_medium_level_il()?;
+        let result = unsafe { BNGetMappedMediumLevelILInstructionIndex(self.handle, instr_idx) };
+        (result < mlil.instruction_count()).then_some(result)
+    }
+
+    /// Index of the [Function::mapped_medium_level_il] expression for the expression at
+    /// `expr_idx`
+    pub fn mapped_medium_level_il_expr_index(&self, expr_idx: usize) -> Option<usize> {
+        use binaryninjacore_sys::{BNGetMappedMediumLevelILExprIndex, BNGetMediumLevelILExprCount};
+
+        let mlil = self.mapped_medium_level_il()?;
+        let result = unsafe { BNGetMappedMediumLevelILExprIndex(self.handle, expr_idx) };
+        (result < unsafe { BNGetMediumLevelILExprCount(mlil.handle) }).then_some(result)
+    }
+}
+
+impl<A: Architecture> Function<A, Finalized, NonSSA<RegularNonSSA>> {
+    pub fn ssa_form(&self) -> Option<Ref<Function<A, Finalized, SSA>>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAForm;
+
+        let ssa = unsafe { BNGetLowLevelILSSAForm(self.handle) };
+        (!ssa.is_null()).then(|| unsafe { Function::ref_from_raw(self.borrower.clone(), ssa) })
+    }
+
+    /// Index in the SSA form of the instruction at `instr_idx`
+    pub fn ssa_instruction_index(&self, instr_idx: usize) -> usize {
+        use binaryninjacore_sys::BNGetLowLevelILSSAInstructionIndex;
+        unsafe { BNGetLowLevelILSSAInstructionIndex(self.handle, instr_idx) }
+    }
+
+    /// Index in the SSA form of the expression at `expr_idx`
+    pub fn ssa_expr_index(&self, expr_idx: usize) -> usize {
+        use binaryninjacore_sys::BNGetLowLevelILSSAExprIndex;
+        unsafe { BNGetLowLevelILSSAExprIndex(self.handle, expr_idx) }
+    }
+}
+
+impl<A: Architecture> Function<A, Finalized, SSA> {
+    pub fn non_ssa_form(&self) -> Option<Ref<Function<A, Finalized, NonSSA<RegularNonSSA>>>> {
+        use binaryninjacore_sys::BNGetLowLevelILNonSSAForm;
+
+        let func = unsafe { BNGetLowLevelILNonSSAForm(self.handle) };
+        (!func.is_null()).then(|| unsafe { Function::ref_from_raw(self.borrower.clone(), func) })
+    }
+
+    /// Index in the non-SSA form of the instruction at `instr_idx`
+    pub fn non_ssa_instruction_index(&self, instr_idx: usize) -> usize {
+        use binaryninjacore_sys::BNGetLowLevelILNonSSAInstructionIndex;
+        unsafe { BNGetLowLevelILNonSSAInstructionIndex(self.handle, instr_idx) }
+    }
+
+    /// Index in the non-SSA form of the expression at `expr_idx`
+    pub fn non_ssa_expr_index(&self, expr_idx: usize) -> usize {
+        use binaryninjacore_sys::BNGetLowLevelILNonSSAExprIndex;
+        unsafe { BNGetLowLevelILNonSSAExprIndex(self.handle, expr_idx) }
+    }
+
+    /// The instruction defining `reg`, [None] for registers defined on entry to the function
+    pub fn ssa_register_definition(
+        &self,
+        reg: SSARegister<A::Register>,
+    ) -> Option<Instruction<'_, A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSARegisterDefinition;
+
+        let instr_idx = unsafe {
+            BNGetLowLevelILSSARegisterDefinition(
+                self.handle,
+                reg.register().id(),
+                reg.version() as usize,
+            )
+        };
+        self.instruction_if_valid(instr_idx)
+    }
+
+    /// The instruction defining `flag`, [None] for flags defined on entry to the function
+    pub fn ssa_flag_definition(
+        &self,
+        flag: SSAFlag<A::Flag>,
+    ) -> Option<Instruction<'_, A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAFlagDefinition;
+
+        let instr_idx = unsafe {
+            BNGetLowLevelILSSAFlagDefinition(self.handle, flag.flag.id(), flag.version as usize)
+        };
+        self.instruction_if_valid(instr_idx)
+    }
+
+    /// The instruction defining the memory `version`, [None] for the memory on entry to the function
+    pub fn ssa_memory_definition(
+        &self,
+        version: usize,
+    ) -> Option<Instruction<'_, A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAMemoryDefinition;
+
+        let instr_idx = unsafe { BNGetLowLevelILSSAMemoryDefinition(self.handle, version) };
+        self.instruction_if_valid(instr_idx)
+    }
+
+    /// The instructions using `reg`
+    pub fn ssa_register_uses(
+        &self,
+        reg: SSARegister<A::Register>,
+    ) -> Vec<Instruction<'_, A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSARegisterUses;
+
+        let mut count = 0;
+        let uses = unsafe {
+            BNGetLowLevelILSSARegisterUses(
+                self.handle,
+                reg.register().id(),
+                reg.version() as usize,
+                &mut count,
+            )
+        };
+        unsafe { self.instructions_from_raw(uses, count) }
+    }
+
+    /// The instructions using `flag`
+    pub fn ssa_flag_uses(&self, flag: SSAFlag<A::Flag>) -> Vec<Instruction<'_, A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAFlagUses;
+
+        let mut count = 0;
+        let uses = unsafe {
+            BNGetLowLevelILSSAFlagUses(
+                self.handle,
+                flag.flag.id(),
+                flag.version as usize,
+                &mut count,
+            )
+        };
+        unsafe { self.instructions_from_raw(uses, count) }
+    }
+
+    /// The instructions using the memory `version`
+    pub fn ssa_memory_uses(&self, version: usize) -> Vec<Instruction<'_, A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNGetLowLevelILSSAMemoryUses;
+
+        let mut count = 0;
+        let uses = unsafe { BNGetLowLevelILSSAMemoryUses(self.handle, version, &mut count) };
+        unsafe { self.instructions_from_raw(uses, count) }
+    }
+
+    pub fn ssa_register_value(&self, reg: SSARegister<A::Register>) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILSSARegisterValue;
+
+        unsafe {
+            BNGetLowLevelILSSARegisterValue(
+                self.handle,
+                reg.register().id(),
+                reg.version() as usize,
+            )
+        }
+        .into()
+    }
+
+    pub fn ssa_flag_value(&self, flag: SSAFlag<A::Flag>) -> RegisterValue {
+        use binaryninjacore_sys::BNGetLowLevelILSSAFlagValue;
+
+        unsafe { BNGetLowLevelILSSAFlagValue(self.handle, flag.flag.id(), flag.version as usize) }
+            .into()
+    }
+
+    fn instruction_if_valid(&self, instr_idx: usize) -> Option<Instruction<'_, A, Finalized, SSA>> {
+        (instr_idx < self.instruction_count()).then_some(Instruction {
+            function: self,
+            instr_idx,
+        })
+    }
+
+    /// Copy and free the list of instruction indices returned by the core
+    unsafe fn instructions_from_raw(
+        &self,
+        indices: *mut usize,
+        count: usize,
+    ) -> Vec<Instruction<'_, A, Finalized, SSA>> {
+        use binaryninjacore_sys::BNFreeILInstructionList;
+
+        if indices.is_null() {
+            return vec![];
+        }
+        let result = std::slice::from_raw_parts(indices, count)
+            .iter()
+            .map(|&instr_idx| Instruction {
+                function: self,
+                instr_idx,
+            })
+            .collect();
+        BNFreeILInstructionList(indices);
+        result
+    }
 }
 
 // Allow instantiating Lifted IL functions for querying Lifted IL from Architectures
@@ -15,12 +15,16 @@
 use binaryninjacore_sys::BNGetLowLevelILByIndex;
 use binaryninjacore_sys::BNGetLowLevelILIndexForInstruction;
 use binaryninjacore_sys::BNLowLevelILInstruction;
+use binaryninjacore_sys::BNMediumLevelILFunction;
+use binaryninjacore_sys::BNVariable;
 
 use super::operation;
 use super::operation::Operation;
 use super::*;
 
 use crate::architecture::Architecture;
+use crate::mlil::MediumLevelILInstruction;
+use crate::types::Variable;
 
 pub struct Instruction<'func, A, M, F>
 where
@@ -171,6 +175,120 @@ where
     }
 }
 
+impl<'func, A, M> Instruction<'func, A, M, SSA>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn address(&self) -> u64 {
+        let expr_idx =
+            unsafe { BNGetLowLevelILIndexForInstruction(self.function.handle, self.instr_idx) };
+        let op = unsafe { BNGetLowLevelILByIndex(self.function.handle, expr_idx) };
+        op.address
+    }
+
+    pub fn info(&self) -> InstrInfo<'func, A, M, SSA> {
+        use binaryninjacore_sys::BNLowLevelILOperation::*;
+
+        let expr_idx =
+            unsafe { BNGetLowLevelILIndexForInstruction(self.function.handle, self.instr_idx) };
+        let op = unsafe { BNGetLowLevelILByIndex(self.function.handle, expr_idx) };
+
+        match op.operation {
+            LLIL_SET_REG_SSA | LLIL_SET_REG_SSA_PARTIAL => {
+                InstrInfo::SetReg(Operation::new(self.function, op))
+            }
+            LLIL_SET_REG_SPLIT_SSA => InstrInfo::SetRegSplit(Operation::new(self.function, op)),
+            LLIL_SET_FLAG_SSA => InstrInfo::SetFlag(Operation::new(self.function, op)),
+            LLIL_STORE_SSA => InstrInfo::Store(Operation::new(self.function, op)),
+            LLIL_CALL_SSA => InstrInfo::Call(Operation::new(self.function, op)),
+            LLIL_TAILCALL_SSA => InstrInfo::TailCall(Operation::new(self.function, op)),
+            LLIL_SYSCALL_SSA => InstrInfo::Syscall(Operation::new(self.function, op)),
+            LLIL_INTRINSIC_SSA => InstrInfo::Intrinsic(Operation::new(self.function, op)),
+            LLIL_REG_PHI => InstrInfo::RegPhi(Operation::new(self.function, op)),
+            LLIL_FLAG_PHI => InstrInfo::FlagPhi(Operation::new(self.function, op)),
+            LLIL_MEM_PHI => InstrInfo::MemPhi(Operation::new(self.function, op)),
+            _ => common_info(self.function, op).unwrap_or_else(|| {
+                let expr = Expression::new(self.function, expr_idx);
+
+                let info = unsafe { expr.info_from_op(op) };
+
+                InstrInfo::Value(expr, info)
+            }),
+        }
+    }
+}
+
+impl<'func, A, F> Instruction<'func, A, Finalized, F>
+where
+    A: 'func + Architecture,
+    F: FunctionForm,
+{
+    /// Index of the root expression of this instruction
+    pub fn expr_index(&self) -> usize {
+        unsafe { BNGetLowLevelILIndexForInstruction(self.function.handle, self.instr_idx) }
+    }
+
+    /// The [Function::medium_level_il] expression for this instruction
+    pub fn medium_level_il(&self) -> Option<MediumLevelILInstruction> {
+        self.function
+            .expression_from_idx(self.expr_index())
+            .medium_level_il()
+    }
+
+    /// All the [Function::medium_level_il] expressions for this instruction
+    pub fn medium_level_il_exprs(&self) -> Vec<MediumLevelILInstruction> {
+        self.function
+            .expression_from_idx(self.expr_index())
+            .medium_level_il_exprs()
+    }
+
+    /// The [Function::mapped_medium_level_il] expression for this instruction
+    pub fn mapped_medium_level_il(&self) -> Option<MediumLevelILInstruction> {
+        self.function
+            .expression_from_idx(self.expr_index())
+            .mapped_medium_level_il()
+    }
+
+    /// The medium level IL variable holding `reg` at this instruction
+    pub fn medium_level_il_variable_for_register(&self, reg: A::Register) -> Option<Variable> {
+        use binaryninjacore_sys::BNGetMediumLevelILVariableForRegisterAtInstruction;
+
+        self.medium_level_il_variable(|mlil, instr_idx| unsafe {
+            BNGetMediumLevelILVariableForRegisterAtInstruction(mlil, reg.id(), instr_idx)
+        })
+    }
+
+    /// The medium level IL variable holding `flag` at this instruction
+    pub fn medium_level_il_variable_for_flag(&self, flag: A::Flag) -> Option<Variable> {
+        use binaryninjacore_sys::BNGetMediumLevelILVariableForFlagAtInstruction;
+
+        self.medium_level_il_variable(|mlil, instr_idx| unsafe {
+            BNGetMediumLevelILVariableForFlagAtInstruction(mlil, flag.id(), instr_idx)
+        })
+    }
+
+    /// The medium level IL variable stored at stack `offset` at this instruction
+    pub fn medium_level_il_variable_for_stack_location(&self, offset: i64) -> Option<Variable> {
+        use binaryninjacore_sys::BNGetMediumLevelILVariableForStackLocationAtInstruction;
+
+        self.medium_level_il_variable(|mlil, instr_idx| unsafe {
+            BNGetMediumLevelILVariableForStackLocationAtInstruction(mlil, offset, instr_idx)
+        })
+    }
+
+    fn medium_level_il_variable<G>(&self, get: G) -> Option<Variable>
+    where
+        G: FnOnce(*mut BNMediumLevelILFunction, usize) -> BNVariable,
+    {
+        let mlil = self.function.medium_level_il()?;
+        let instr_idx = self
+            .function
+            .medium_level_il_instruction_index(self.instr_idx)?;
+        Some(unsafe { Variable::from_raw(get(mlil.handle, instr_idx)) })
+    }
+}
+
 pub enum InstrInfo<'func, A, M, F>
 where
     A: 'func + Architecture,
@@ -202,6 +320,10 @@ where
     Trap(Operation<'func, A, M, F, operation::Trap>),
     Undef(Operation<'func, A, M, F, operation::NoArgs>),
 
+    RegPhi(Operation<'func, A, M, F, operation::RegPhi>),
+    FlagPhi(Operation<'func, A, M, F, operation::FlagPhi>),
+    MemPhi(Operation<'func, A, M, F, operation::MemPhi>),
+
     Value(
         Expression<'func, A, M, F, ValueExpr>,
         ExprInfo<'func, A, M, F>,
@@ -21,6 +21,7 @@ use std::fmt;
 // can reg/set_reg be used with sizes that differ from what is in BNRegisterInfo?
 
 use crate::architecture::Architecture;
+use crate::architecture::Flag as ArchFlag;
 use crate::architecture::Register as ArchReg;
 use crate::function::Location;
 
@@ -85,6 +86,33 @@ impl<R: ArchReg> SSARegister<R> {
             SSARegister::Full(_, ver) | SSARegister::Partial(_, ver, _) => ver,
         }
     }
+
+    /// The register being versioned, which is the full register for partial accesses
+    pub fn register(&self) -> Register<R> {
+        match *self {
+            SSARegister::Full(reg, _) => reg,
+            SSARegister::Partial(full, _, _) => Register::ArchReg(full),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct SSAFlag<F: ArchFlag> {
+    pub flag: F,
+    pub version: u32,
+}
+
+impl<F: ArchFlag> SSAFlag<F> {
+    pub fn new(flag: F, version: u32) -> Self {
+        Self { flag, version }
+    }
+}
+
+/// Output of an intrinsic, which can write both registers and flags
+#[derive(Copy, Clone, Debug)]
+pub enum SSARegisterOrFlag<R: ArchReg, F: ArchFlag> {
+    Register(SSARegister<R>),
+    Flag(SSAFlag<F>),
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
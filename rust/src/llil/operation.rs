@@ -86,6 +86,35 @@ where
 // LLIL_SYSCALL, LLIL_SYSCALL_SSA
 pub struct Syscall;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Syscall>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    /// Registers defined by the syscall
+    pub fn output_regs(&self) -> Vec<SSARegister<A::Register>> {
+        self.call_output(0).1
+    }
+
+    /// Memory version defined by the syscall
+    pub fn dest_memory_version(&self) -> usize {
+        self.call_output(0).0
+    }
+
+    pub fn stack_reg(&self) -> SSARegister<A::Register> {
+        self.call_stack(1).0
+    }
+
+    /// Memory version the syscall reads
+    pub fn source_memory_version(&self) -> usize {
+        self.call_stack(1).1
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        self.call_params(2)
+    }
+}
+
 // LLIL_INTRINSIC, LLIL_INTRINSIC_SSA
 pub struct Intrinsic;
 
@@ -102,6 +131,147 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Intrinsic>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn intrinsic(&self) -> Option<A::Intrinsic> {
+        let raw_id = self.op.operands[2] as u32;
+        self.function.arch().intrinsic_from_id(raw_id)
+    }
+
+    /// Registers and flags defined by the intrinsic
+    pub fn outputs(&self) -> Vec<SSARegisterOrFlag<A::Register, A::Flag>> {
+        // flags are told apart from registers by bit 32 of their identifier
+        self.operand_list(0)
+            .chunks_exact(2)
+            .filter_map(|pair| match pair[0] & (1 << 32) {
+                0 => Some(SSARegisterOrFlag::Register(SSARegister::Full(
+                    self.register(pair[0] as u32),
+                    pair[1] as u32,
+                ))),
+                _ => self
+                    .ssa_flag(pair[0] & 0xffff_ffff, pair[1])
+                    .map(SSARegisterOrFlag::Flag),
+            })
+            .collect()
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        self.call_params(3)
+    }
+}
+
+impl<'func, A, M, O> Operation<'func, A, M, SSA, O>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+    O: OperationArguments,
+{
+    fn register(&self, raw_id: u32) -> Register<A::Register> {
+        if raw_id >= 0x8000_0000 {
+            Register::Temp(raw_id & 0x7fff_ffff)
+        } else {
+            self.function
+                .arch()
+                .register_from_id(raw_id)
+                .map(Register::ArchReg)
+                .unwrap_or_else(|| {
+                    error!(
+                        "got garbage register from {:?} @ 0x{:x}",
+                        self.op.operation, self.op.address
+                    );
+
+                    Register::Temp(0)
+                })
+        }
+    }
+
+    // SSA registers are stored as the register followed by its version, partial accesses
+    // additionally store the partial register after the version
+    fn ssa_register(&self, operand: usize) -> SSARegister<A::Register> {
+        use binaryninjacore_sys::BNLowLevelILOperation::*;
+
+        let reg = self.register(self.op.operands[operand] as u32);
+        let version = self.op.operands[operand + 1] as u32;
+
+        match self.op.operation {
+            LLIL_SET_REG_SSA_PARTIAL | LLIL_REG_SSA_PARTIAL => {
+                match (reg, self.register(self.op.operands[operand + 2] as u32)) {
+                    (Register::ArchReg(full), Register::ArchReg(partial)) => {
+                        SSARegister::Partial(full, version, partial)
+                    }
+                    _ => SSARegister::Full(reg, version),
+                }
+            }
+            _ => SSARegister::Full(reg, version),
+        }
+    }
+
+    fn ssa_flag(&self, raw_id: u64, version: u64) -> Option<SSAFlag<A::Flag>> {
+        self.function
+            .arch()
+            .flag_from_id(raw_id as u32)
+            .map(|flag| SSAFlag::new(flag, version as u32))
+    }
+
+    // operand lists are stored as a count followed by the index of the first list storage expression
+    fn operand_list(&self, operand: usize) -> Vec<u64> {
+        self.operand_list_of(&self.op, operand)
+    }
+
+    fn operand_list_of(&self, op: &BNLowLevelILInstruction, operand: usize) -> Vec<u64> {
+        let count = op.operands[operand] as usize;
+        let mut list = OperandListIter {
+            function: self.function,
+            cursor: unsafe {
+                BNGetLowLevelILByIndex(self.function.handle, op.operands[operand + 1] as usize)
+            },
+            cursor_operand: 0,
+        };
+
+        (0..count).map(|_| list.next()).collect()
+    }
+
+    // expressions only valid within their parent instruction, eg: LLIL_CALL_OUTPUT_SSA
+    fn sub_expr(&self, operand: usize) -> BNLowLevelILInstruction {
+        unsafe { BNGetLowLevelILByIndex(self.function.handle, self.op.operands[operand] as usize) }
+    }
+
+    fn ssa_register_list(&self, list: Vec<u64>) -> Vec<SSARegister<A::Register>> {
+        list.chunks_exact(2)
+            .map(|pair| SSARegister::Full(self.register(pair[0] as u32), pair[1] as u32))
+            .collect()
+    }
+
+    // LLIL_CALL_OUTPUT_SSA: memory version, then the list of output registers
+    fn call_output(&self, operand: usize) -> (usize, Vec<SSARegister<A::Register>>) {
+        let output = self.sub_expr(operand);
+        let regs = self.ssa_register_list(self.operand_list_of(&output, 1));
+        (output.operands[0] as usize, regs)
+    }
+
+    // LLIL_CALL_STACK_SSA: stack register and its version, then the memory version
+    fn call_stack(&self, operand: usize) -> (SSARegister<A::Register>, usize) {
+        let stack = self.sub_expr(operand);
+        let reg = SSARegister::Full(
+            self.register(stack.operands[0] as u32),
+            stack.operands[1] as u32,
+        );
+        (reg, stack.operands[2] as usize)
+    }
+
+    // LLIL_CALL_PARAM: list of parameter expressions
+    fn call_params(&self, operand: usize) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        let params = self.sub_expr(operand);
+        self.operand_list_of(&params, 0)
+            .into_iter()
+            .map(|expr_idx| Expression::new(self.function, expr_idx as usize))
+            .collect()
+    }
+}
+
 // LLIL_SET_REG, LLIL_SET_REG_SSA, LLIL_SET_REG_PARTIAL_SSA
 pub struct SetReg;
 
@@ -141,6 +311,32 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetReg>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_reg(&self) -> SSARegister<A::Register> {
+        self.ssa_register(0)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        use binaryninjacore_sys::BNLowLevelILOperation::LLIL_SET_REG_SSA_PARTIAL;
+
+        let operand = if self.op.operation == LLIL_SET_REG_SSA_PARTIAL {
+            3
+        } else {
+            2
+        };
+
+        Expression::new(self.function, self.op.operands[operand] as usize)
+    }
+}
+
 // LLIL_SET_REG_SPLIT, LLIL_SET_REG_SPLIT_SSA
 pub struct SetRegSplit;
 
@@ -201,6 +397,37 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetRegSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    // the destinations are LLIL_REG_SPLIT_DEST_SSA expressions holding the register and its version
+    fn split_dest(&self, operand: usize) -> SSARegister<A::Register> {
+        let dest = self.sub_expr(operand);
+        SSARegister::Full(
+            self.register(dest.operands[0] as u32),
+            dest.operands[1] as u32,
+        )
+    }
+
+    pub fn dest_reg_high(&self) -> SSARegister<A::Register> {
+        self.split_dest(0)
+    }
+
+    pub fn dest_reg_low(&self) -> SSARegister<A::Register> {
+        self.split_dest(1)
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression::new(self.function, self.op.operands[2] as usize)
+    }
+}
+
 // LLIL_SET_FLAG, LLIL_SET_FLAG_SSA
 pub struct SetFlag;
 
@@ -215,6 +442,20 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, SetFlag>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression::new(self.function, self.op.operands[2] as usize)
+    }
+}
+
 // LLIL_LOAD, LLIL_LOAD_SSA
 pub struct Load;
 
@@ -233,6 +474,24 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Load>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_mem_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression::new(self.function, self.op.operands[0] as usize)
+    }
+
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+}
+
 // LLIL_STORE, LLIL_STORE_SSA
 pub struct Store;
 
@@ -255,6 +514,34 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Store>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn dest_mem_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression::new(self.function, self.op.operands[0] as usize)
+    }
+
+    /// Memory version defined by this store
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[1] as usize
+    }
+
+    /// Memory version this store is applied to
+    pub fn source_memory_version(&self) -> usize {
+        self.op.operands[2] as usize
+    }
+
+    pub fn source_expr(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression::new(self.function, self.op.operands[3] as usize)
+    }
+}
+
 // LLIL_REG, LLIL_REG_SSA, LLIL_REG_SSA_PARTIAL
 pub struct Reg;
 
@@ -290,9 +577,47 @@ where
     }
 }
 
-// LLIL_REG_SPLIT
+impl<'func, A, M> Operation<'func, A, M, SSA, Reg>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn source_reg(&self) -> SSARegister<A::Register> {
+        self.ssa_register(0)
+    }
+}
+
+// LLIL_REG_SPLIT, LLIL_REG_SPLIT_SSA
 pub struct RegSplit;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, RegSplit>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn size(&self) -> usize {
+        self.op.size
+    }
+
+    pub fn high_reg(&self) -> SSARegister<A::Register> {
+        SSARegister::Full(
+            self.register(self.op.operands[0] as u32),
+            self.op.operands[1] as u32,
+        )
+    }
+
+    pub fn low_reg(&self) -> SSARegister<A::Register> {
+        SSARegister::Full(
+            self.register(self.op.operands[2] as u32),
+            self.op.operands[3] as u32,
+        )
+    }
+}
+
 impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, RegSplit>
     where
         A: 'func + Architecture,
@@ -349,6 +674,16 @@ impl<'func, A, M, V> Operation<'func, A, M, NonSSA<V>, RegSplit>
 // LLIL_FLAG, LLIL_FLAG_SSA
 pub struct Flag;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Flag>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn source_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag(self.op.operands[0], self.op.operands[1])
+    }
+}
+
 // LLIL_FLAG_BIT, LLIL_FLAG_BIT_SSA
 pub struct FlagBit;
 
@@ -369,7 +704,7 @@ where
 // LLIL_JUMP_TO
 pub struct JumpTo;
 
-struct TargetListIter<'func, A, M, F>
+struct OperandListIter<'func, A, M, F>
 where
     A: 'func + Architecture,
     M: FunctionMutability,
@@ -380,7 +715,7 @@ where
     cursor_operand: usize,
 }
 
-impl<'func, A, M, F> TargetListIter<'func, A, M, F>
+impl<'func, A, M, F> OperandListIter<'func, A, M, F>
 where
     A: 'func + Architecture,
     M: FunctionMutability,
@@ -412,7 +747,7 @@ where
     pub fn target_list(&self) -> BTreeMap<u64, usize> {
         let mut result = BTreeMap::new();
         let count = self.op.operands[1] as usize / 2;
-        let mut list = TargetListIter {
+        let mut list = OperandListIter {
             function: self.function,
             cursor: unsafe {
                 BNGetLowLevelILByIndex(self.function.handle, self.op.operands[2] as usize)
@@ -454,6 +789,39 @@ where
     }
 }
 
+impl<'func, A, M> Operation<'func, A, M, SSA, Call>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn target(&self) -> Expression<'func, A, M, SSA, ValueExpr> {
+        Expression::new(self.function, self.op.operands[1] as usize)
+    }
+
+    /// Registers defined by the call
+    pub fn output_regs(&self) -> Vec<SSARegister<A::Register>> {
+        self.call_output(0).1
+    }
+
+    /// Memory version defined by the call
+    pub fn dest_memory_version(&self) -> usize {
+        self.call_output(0).0
+    }
+
+    pub fn stack_reg(&self) -> SSARegister<A::Register> {
+        self.call_stack(2).0
+    }
+
+    /// Memory version the call reads
+    pub fn source_memory_version(&self) -> usize {
+        self.call_stack(2).1
+    }
+
+    pub fn params(&self) -> Vec<Expression<'func, A, M, SSA, ValueExpr>> {
+        self.call_params(3)
+    }
+}
+
 // LLIL_RET
 pub struct Ret;
 
@@ -559,12 +927,63 @@ where
 // LLIL_REG_PHI
 pub struct RegPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, RegPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_reg(&self) -> SSARegister<A::Register> {
+        self.ssa_register(0)
+    }
+
+    pub fn source_regs(&self) -> Vec<SSARegister<A::Register>> {
+        self.operand_list(2)
+            .chunks_exact(2)
+            .map(|pair| SSARegister::Full(self.register(pair[0] as u32), pair[1] as u32))
+            .collect()
+    }
+}
+
 // LLIL_FLAG_PHI
 pub struct FlagPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, FlagPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_flag(&self) -> Option<SSAFlag<A::Flag>> {
+        self.ssa_flag(self.op.operands[0], self.op.operands[1])
+    }
+
+    pub fn source_flags(&self) -> Vec<SSAFlag<A::Flag>> {
+        self.operand_list(2)
+            .chunks_exact(2)
+            .filter_map(|pair| self.ssa_flag(pair[0], pair[1]))
+            .collect()
+    }
+}
+
 // LLIL_MEM_PHI
 pub struct MemPhi;
 
+impl<'func, A, M> Operation<'func, A, M, SSA, MemPhi>
+where
+    A: 'func + Architecture,
+    M: FunctionMutability,
+{
+    pub fn dest_memory_version(&self) -> usize {
+        self.op.operands[0] as usize
+    }
+
+    pub fn source_memory_versions(&self) -> Vec<usize> {
+        self.operand_list(1)
+            .into_iter()
+            .map(|version| version as usize)
+            .collect()
+    }
+}
+
 // LLIL_CONST, LLIL_CONST_PTR
 pub struct Const;
 
@@ -0,0 +1,166 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Running code on the main thread of the application, usually the UI thread.
+//!
+//! ```no_run
+//! use binaryninja::mainthread;
+//!
+//! let action = mainthread::execute(|| println!("on the main thread")).unwrap();
+//! action.wait();
+//!
+//! let answer = mainthread::execute_and_wait(|| 6 * 7);
+//! assert_eq!(answer, 42);
+//! ```
+
+use binaryninjacore_sys::*;
+
+use std::ffi::c_void;
+
+use crate::rc::*;
+
+/// An action queued with [execute], to be run on the main thread
+#[derive(PartialEq, Eq, Hash)]
+pub struct MainThreadAction {
+    handle: *mut BNMainThreadAction,
+}
+
+unsafe impl Send for MainThreadAction {}
+unsafe impl Sync for MainThreadAction {}
+
+impl MainThreadAction {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNMainThreadAction) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    /// Run the action, this must be called from the main thread, see [MainThreadHandler]
+    pub fn execute(&self) {
+        unsafe { BNExecuteMainThreadAction(self.handle) }
+    }
+
+    pub fn is_done(&self) -> bool {
+        unsafe { BNIsMainThreadActionDone(self.handle) }
+    }
+
+    /// Block until the action has been run
+    pub fn wait(&self) {
+        unsafe { BNWaitForMainThreadAction(self.handle) }
+    }
+}
+
+impl ToOwned for MainThreadAction {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for MainThreadAction {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewMainThreadActionReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeMainThreadAction(handle.handle);
+    }
+}
+
+/// Runs the actions queued for the main thread, for applications embedding the core with
+/// their own event loop, see [register_main_thread]
+pub trait MainThreadHandler: 'static + Sync {
+    /// Queue `action` to be run on the main thread with [MainThreadAction::execute]
+    fn add_action(&self, action: Ref<MainThreadAction>);
+}
+
+/// Register the handler running the actions queued for the main thread
+pub fn register_main_thread<H: MainThreadHandler>(handler: H) {
+    let ctxt = Box::into_raw(Box::new(handler));
+    let mut callbacks = BNMainThreadCallbacks {
+        context: ctxt as *mut c_void,
+        addAction: Some(cb_add_action::<H>),
+    };
+    unsafe { BNRegisterMainThread(&mut callbacks) }
+}
+
+/// Queue `action` to be run on the main thread, returning without waiting for it
+///
+/// Returns [None] if the action was run immediately, eg: when called from the main thread.
+pub fn execute<F>(action: F) -> Option<Ref<MainThreadAction>>
+where
+    F: FnOnce() + Send + 'static,
+{
+    let ctxt = Box::into_raw(Box::new(action));
+    let handle = unsafe { BNExecuteOnMainThread(ctxt as *mut c_void, Some(cb_execute::<F>)) };
+    (!handle.is_null()).then(|| unsafe { MainThreadAction::ref_from_raw(handle) })
+}
+
+/// Run `action` on the main thread, blocking until it returns its result
+pub fn execute_and_wait<F, R>(action: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    let mut ctxt = WaitContext {
+        action: Some(action),
+        result: None,
+    };
+    unsafe {
+        BNExecuteOnMainThreadAndWait(
+            &mut ctxt as *mut WaitContext<F, R> as *mut c_void,
+            Some(cb_execute_and_wait::<F, R>),
+        )
+    };
+    ctxt.result.expect("main thread action was not executed")
+}
+
+/// If the caller is running on the main thread
+pub fn is_main_thread() -> bool {
+    unsafe { BNIsMainThread() }
+}
+
+struct WaitContext<F, R> {
+    action: Option<F>,
+    result: Option<R>,
+}
+
+extern "C" fn cb_execute<F: FnOnce() + Send + 'static>(ctxt: *mut c_void) {
+    ffi_wrap!("mainthread::execute", unsafe {
+        let action = Box::from_raw(ctxt as *mut F);
+        action()
+    })
+}
+
+extern "C" fn cb_execute_and_wait<F: FnOnce() -> R + Send, R: Send>(ctxt: *mut c_void) {
+    ffi_wrap!("mainthread::execute_and_wait", unsafe {
+        let ctxt = &mut *(ctxt as *mut WaitContext<F, R>);
+        if let Some(action) = ctxt.action.take() {
+            ctxt.result = Some(action());
+        }
+    })
+}
+
+extern "C" fn cb_add_action<H: MainThreadHandler>(
+    ctxt: *mut c_void,
+    action: *mut BNMainThreadAction,
+) {
+    ffi_wrap!("MainThreadHandler::add_action", unsafe {
+        let handler = &*(ctxt as *const H);
+        // The core hands over its reference to the action
+        handler.add_action(MainThreadAction::ref_from_raw(action))
+    })
+}
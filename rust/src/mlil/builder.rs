@@ -0,0 +1,727 @@
+use std::mem;
+
+use binaryninjacore_sys::*;
+
+use crate::architecture::CoreArchitecture;
+use crate::function::Function;
+use crate::rc::Ref;
+use crate::types::{SSAVariable, Variable};
+use crate::BN_INVALID_OPERAND;
+
+use super::lift::*;
+use super::operation::*;
+use super::MediumLevelILFunction;
+
+impl MediumLevelILFunction {
+    /// Create an empty function, to be filled with [MediumLevelILFunction::add_instruction] and
+    /// then finished with [MediumLevelILFunction::finalize]
+    pub fn new(arch: CoreArchitecture, function: &Function) -> Ref<Self> {
+        let handle = unsafe { BNCreateMediumLevelILFunction(arch.0, function.handle) };
+        assert!(!handle.is_null());
+        unsafe { Ref::new(Self { handle }) }
+    }
+
+    /// Add the expression `expr` and all its operands to the function, returning the index of
+    /// the new expression
+    ///
+    /// The `function` and `index` of `expr` and its operands are ignored, so instructions lifted
+    /// from this function can be modified and added back, and new instructions can be built from
+    /// scratch. The new expression is located at `expr.address`.
+    ///
+    /// Fails if `expr` contains a float constant that is neither 4 nor 8 bytes wide.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use binaryninja::mlil::{MediumLevelILFunction, MediumLevelILLiftedInstructionKind};
+    /// # use binaryninja::mlil::operation::Constant;
+    /// # let mlil: MediumLevelILFunction = todo!();
+    /// // replace the condition of an `if` with a constant
+    /// let instr = mlil.lifted_instruction_from_instruction_idx(0);
+    /// if let MediumLevelILLiftedInstructionKind::If(op) = &instr.kind {
+    ///     let mut condition = (*op.condition).clone();
+    ///     condition.kind = MediumLevelILLiftedInstructionKind::Const(Constant { constant: 1 });
+    ///     let new_condition = mlil.add_expr(&condition).unwrap();
+    ///     mlil.replace_expr(op.condition.index, new_condition);
+    /// }
+    /// ```
+    pub fn add_expr(&self, expr: &MediumLevelILLiftedInstruction) -> Result<usize, ()> {
+        use BNMediumLevelILOperation::*;
+        use MediumLevelILLiftedInstructionKind as Lifted;
+
+        let address = expr.address;
+        let (operation, operands) = match &expr.kind {
+            Lifted::Nop => (MLIL_NOP, [0; 5]),
+            Lifted::Noret => (MLIL_NORET, [0; 5]),
+            Lifted::Bp => (MLIL_BP, [0; 5]),
+            Lifted::Undef => (MLIL_UNDEF, [0; 5]),
+            Lifted::Unimpl => (MLIL_UNIMPL, [0; 5]),
+            Lifted::If(op) => (
+                MLIL_IF,
+                [
+                    self.expr_operand(&op.condition)?,
+                    op.dest_true,
+                    op.dest_false,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::FloatConst(op) => (
+                MLIL_FLOAT_CONST,
+                [get_raw_float(op.constant, expr.size)?, 0, 0, 0, 0],
+            ),
+            Lifted::Const(op) => (MLIL_CONST, [op.constant, 0, 0, 0, 0]),
+            Lifted::ConstPtr(op) => (MLIL_CONST_PTR, [op.constant, 0, 0, 0, 0]),
+            Lifted::Import(op) => (MLIL_IMPORT, [op.constant, 0, 0, 0, 0]),
+            Lifted::ExternPtr(op) => (MLIL_EXTERN_PTR, [op.constant, op.offset, 0, 0, 0]),
+            Lifted::ConstData(op) => {
+                let value = op.constant_data.value();
+                (
+                    MLIL_CONST_DATA,
+                    [
+                        value.state.into_raw_value() as u64,
+                        value.value as u64,
+                        0,
+                        0,
+                        0,
+                    ],
+                )
+            }
+            Lifted::Jump(op) => (MLIL_JUMP, [self.expr_operand(&op.dest)?, 0, 0, 0, 0]),
+            Lifted::RetHint(op) => (MLIL_RET_HINT, [self.expr_operand(&op.dest)?, 0, 0, 0, 0]),
+            Lifted::StoreSsa(op) => (
+                MLIL_STORE_SSA,
+                [
+                    self.expr_operand(&op.dest)?,
+                    op.dest_memory,
+                    op.src_memory,
+                    self.expr_operand(&op.src)?,
+                    0,
+                ],
+            ),
+            Lifted::StoreStructSsa(op) => (
+                MLIL_STORE_STRUCT_SSA,
+                [
+                    self.expr_operand(&op.dest)?,
+                    op.offset,
+                    op.dest_memory,
+                    op.src_memory,
+                    self.expr_operand(&op.src)?,
+                ],
+            ),
+            Lifted::StoreStruct(op) => (
+                MLIL_STORE_STRUCT,
+                [
+                    self.expr_operand(&op.dest)?,
+                    op.offset,
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::Store(op) => (
+                MLIL_STORE,
+                [
+                    self.expr_operand(&op.dest)?,
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::JumpTo(op) => {
+                let dest = self.expr_operand(&op.dest)?;
+                let targets: Vec<u64> = op.targets.iter().flat_map(|(k, v)| [*k, *v]).collect();
+                let [count, list] = self.operand_list(&targets);
+                (MLIL_JUMP_TO, [dest, count, list, 0, 0])
+            }
+            Lifted::Goto(op) => (MLIL_GOTO, [op.dest, 0, 0, 0, 0]),
+            Lifted::FreeVarSlot(op) => (MLIL_FREE_VAR_SLOT, [op.dest.to_identifier(), 0, 0, 0, 0]),
+            Lifted::SetVarField(op) => (
+                MLIL_SET_VAR_FIELD,
+                [
+                    op.dest.to_identifier(),
+                    op.offset,
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::SetVar(op) => (
+                MLIL_SET_VAR,
+                [
+                    op.dest.to_identifier(),
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::FreeVarSlotSsa(op) => (
+                MLIL_FREE_VAR_SLOT_SSA,
+                [
+                    op.dest.variable.to_identifier(),
+                    op.dest.version as u64,
+                    op.prev.version as u64,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::SetVarSsaField(op) => {
+                (MLIL_SET_VAR_SSA_FIELD, self.set_var_ssa_field_operands(op)?)
+            }
+            Lifted::SetVarAliasedField(op) => (
+                MLIL_SET_VAR_ALIASED_FIELD,
+                self.set_var_ssa_field_operands(op)?,
+            ),
+            Lifted::SetVarAliased(op) => (
+                MLIL_SET_VAR_ALIASED,
+                [
+                    op.dest.variable.to_identifier(),
+                    op.dest.version as u64,
+                    op.prev.version as u64,
+                    self.expr_operand(&op.src)?,
+                    0,
+                ],
+            ),
+            Lifted::SetVarSsa(op) => (
+                MLIL_SET_VAR_SSA,
+                [
+                    op.dest.variable.to_identifier(),
+                    op.dest.version as u64,
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::VarPhi(op) => {
+                let [count, list] = self.ssa_var_list(&op.src);
+                (
+                    MLIL_VAR_PHI,
+                    [
+                        op.dest.variable.to_identifier(),
+                        op.dest.version as u64,
+                        count,
+                        list,
+                        0,
+                    ],
+                )
+            }
+            Lifted::MemPhi(op) => {
+                let [count, list] = self.operand_list(&op.src_memory);
+                (MLIL_MEM_PHI, [op.dest_memory, count, list, 0, 0])
+            }
+            Lifted::VarSplit(op) => (
+                MLIL_VAR_SPLIT,
+                [op.high.to_identifier(), op.low.to_identifier(), 0, 0, 0],
+            ),
+            Lifted::SetVarSplit(op) => (
+                MLIL_SET_VAR_SPLIT,
+                [
+                    op.high.to_identifier(),
+                    op.low.to_identifier(),
+                    self.expr_operand(&op.src)?,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::VarSplitSsa(op) => (
+                MLIL_VAR_SPLIT_SSA,
+                [
+                    op.high.variable.to_identifier(),
+                    op.high.version as u64,
+                    op.low.variable.to_identifier(),
+                    op.low.version as u64,
+                    0,
+                ],
+            ),
+            Lifted::SetVarSplitSsa(op) => (
+                MLIL_SET_VAR_SPLIT_SSA,
+                [
+                    op.high.variable.to_identifier(),
+                    op.high.version as u64,
+                    op.low.variable.to_identifier(),
+                    op.low.version as u64,
+                    self.expr_operand(&op.src)?,
+                ],
+            ),
+            Lifted::Add(op) => (MLIL_ADD, self.binary_op_operands(op)?),
+            Lifted::Sub(op) => (MLIL_SUB, self.binary_op_operands(op)?),
+            Lifted::And(op) => (MLIL_AND, self.binary_op_operands(op)?),
+            Lifted::Or(op) => (MLIL_OR, self.binary_op_operands(op)?),
+            Lifted::Xor(op) => (MLIL_XOR, self.binary_op_operands(op)?),
+            Lifted::Lsl(op) => (MLIL_LSL, self.binary_op_operands(op)?),
+            Lifted::Lsr(op) => (MLIL_LSR, self.binary_op_operands(op)?),
+            Lifted::Asr(op) => (MLIL_ASR, self.binary_op_operands(op)?),
+            Lifted::Rol(op) => (MLIL_ROL, self.binary_op_operands(op)?),
+            Lifted::Ror(op) => (MLIL_ROR, self.binary_op_operands(op)?),
+            Lifted::Mul(op) => (MLIL_MUL, self.binary_op_operands(op)?),
+            Lifted::MuluDp(op) => (MLIL_MULU_DP, self.binary_op_operands(op)?),
+            Lifted::MulsDp(op) => (MLIL_MULS_DP, self.binary_op_operands(op)?),
+            Lifted::Divu(op) => (MLIL_DIVU, self.binary_op_operands(op)?),
+            Lifted::DivuDp(op) => (MLIL_DIVU_DP, self.binary_op_operands(op)?),
+            Lifted::Divs(op) => (MLIL_DIVS, self.binary_op_operands(op)?),
+            Lifted::DivsDp(op) => (MLIL_DIVS_DP, self.binary_op_operands(op)?),
+            Lifted::Modu(op) => (MLIL_MODU, self.binary_op_operands(op)?),
+            Lifted::ModuDp(op) => (MLIL_MODU_DP, self.binary_op_operands(op)?),
+            Lifted::Mods(op) => (MLIL_MODS, self.binary_op_operands(op)?),
+            Lifted::ModsDp(op) => (MLIL_MODS_DP, self.binary_op_operands(op)?),
+            Lifted::CmpE(op) => (MLIL_CMP_E, self.binary_op_operands(op)?),
+            Lifted::CmpNe(op) => (MLIL_CMP_NE, self.binary_op_operands(op)?),
+            Lifted::CmpSlt(op) => (MLIL_CMP_SLT, self.binary_op_operands(op)?),
+            Lifted::CmpUlt(op) => (MLIL_CMP_ULT, self.binary_op_operands(op)?),
+            Lifted::CmpSle(op) => (MLIL_CMP_SLE, self.binary_op_operands(op)?),
+            Lifted::CmpUle(op) => (MLIL_CMP_ULE, self.binary_op_operands(op)?),
+            Lifted::CmpSge(op) => (MLIL_CMP_SGE, self.binary_op_operands(op)?),
+            Lifted::CmpUge(op) => (MLIL_CMP_UGE, self.binary_op_operands(op)?),
+            Lifted::CmpSgt(op) => (MLIL_CMP_SGT, self.binary_op_operands(op)?),
+            Lifted::CmpUgt(op) => (MLIL_CMP_UGT, self.binary_op_operands(op)?),
+            Lifted::TestBit(op) => (MLIL_TEST_BIT, self.binary_op_operands(op)?),
+            Lifted::AddOverflow(op) => (MLIL_ADD_OVERFLOW, self.binary_op_operands(op)?),
+            Lifted::FcmpE(op) => (MLIL_FCMP_E, self.binary_op_operands(op)?),
+            Lifted::FcmpNe(op) => (MLIL_FCMP_NE, self.binary_op_operands(op)?),
+            Lifted::FcmpLt(op) => (MLIL_FCMP_LT, self.binary_op_operands(op)?),
+            Lifted::FcmpLe(op) => (MLIL_FCMP_LE, self.binary_op_operands(op)?),
+            Lifted::FcmpGe(op) => (MLIL_FCMP_GE, self.binary_op_operands(op)?),
+            Lifted::FcmpGt(op) => (MLIL_FCMP_GT, self.binary_op_operands(op)?),
+            Lifted::FcmpO(op) => (MLIL_FCMP_O, self.binary_op_operands(op)?),
+            Lifted::FcmpUo(op) => (MLIL_FCMP_UO, self.binary_op_operands(op)?),
+            Lifted::Fadd(op) => (MLIL_FADD, self.binary_op_operands(op)?),
+            Lifted::Fsub(op) => (MLIL_FSUB, self.binary_op_operands(op)?),
+            Lifted::Fmul(op) => (MLIL_FMUL, self.binary_op_operands(op)?),
+            Lifted::Fdiv(op) => (MLIL_FDIV, self.binary_op_operands(op)?),
+            Lifted::Adc(op) => (MLIL_ADC, self.binary_op_carry_operands(op)?),
+            Lifted::Sbb(op) => (MLIL_SBB, self.binary_op_carry_operands(op)?),
+            Lifted::Rlc(op) => (MLIL_RLC, self.binary_op_carry_operands(op)?),
+            Lifted::Rrc(op) => (MLIL_RRC, self.binary_op_carry_operands(op)?),
+            Lifted::Call(op) => (MLIL_CALL, self.call_operands(op)?),
+            Lifted::Tailcall(op) => (MLIL_TAILCALL, self.call_operands(op)?),
+            Lifted::Intrinsic(op) => {
+                let [num_outputs, outputs] = self.var_list(&op.output);
+                let [num_params, params] = self.expr_list(&op.params)?;
+                (
+                    MLIL_INTRINSIC,
+                    [
+                        num_outputs,
+                        outputs,
+                        op.intrinsic.1 as u64,
+                        num_params,
+                        params,
+                    ],
+                )
+            }
+            Lifted::Syscall(op) => {
+                let [num_outputs, outputs] = self.var_list(&op.output);
+                let [num_params, params] = self.expr_list(&op.params)?;
+                (MLIL_SYSCALL, [num_outputs, outputs, num_params, params, 0])
+            }
+            Lifted::IntrinsicSsa(op) => {
+                let [num_outputs, outputs] = self.ssa_var_list(&op.output);
+                let [num_params, params] = self.expr_list(&op.params)?;
+                (
+                    MLIL_INTRINSIC_SSA,
+                    [
+                        num_outputs,
+                        outputs,
+                        op.intrinsic.1 as u64,
+                        num_params,
+                        params,
+                    ],
+                )
+            }
+            Lifted::CallSsa(op) => (MLIL_CALL_SSA, self.call_ssa_operands(address, op)?),
+            Lifted::TailcallSsa(op) => (MLIL_TAILCALL_SSA, self.call_ssa_operands(address, op)?),
+            Lifted::CallUntypedSsa(op) => (
+                MLIL_CALL_UNTYPED_SSA,
+                self.call_untyped_ssa_operands(address, op)?,
+            ),
+            Lifted::TailcallUntypedSsa(op) => (
+                MLIL_TAILCALL_UNTYPED_SSA,
+                self.call_untyped_ssa_operands(address, op)?,
+            ),
+            Lifted::SyscallSsa(op) => {
+                let output = self.call_output_ssa(address, op.output_dest_memory, &op.output);
+                let [num_params, params] = self.expr_list(&op.params)?;
+                (
+                    MLIL_SYSCALL_SSA,
+                    [output, num_params, params, op.src_memory, 0],
+                )
+            }
+            Lifted::SyscallUntypedSsa(op) => {
+                let output = self.call_output_ssa(address, op.output_dest_memory, &op.output);
+                let params = self.call_param_ssa(address, op.params_src_memory, &op.params)?;
+                let stack = self.expr_operand(&op.stack)?;
+                (MLIL_SYSCALL_UNTYPED_SSA, [output, params, stack, 0, 0])
+            }
+            Lifted::CallUntyped(op) => {
+                (MLIL_CALL_UNTYPED, self.call_untyped_operands(address, op)?)
+            }
+            Lifted::TailcallUntyped(op) => (
+                MLIL_TAILCALL_UNTYPED,
+                self.call_untyped_operands(address, op)?,
+            ),
+            Lifted::SyscallUntyped(op) => {
+                let output = self.call_output(address, &op.output);
+                let params = self.call_param(address, &op.params)?;
+                let stack = self.expr_operand(&op.stack)?;
+                (MLIL_SYSCALL_UNTYPED, [output, params, stack, 0, 0])
+            }
+            Lifted::SeparateParamList(op) => {
+                let [count, list] = self.expr_list(&op.params)?;
+                (MLIL_SEPARATE_PARAM_LIST, [count, list, 0, 0, 0])
+            }
+            Lifted::SharedParamSlot(op) => {
+                let [count, list] = self.expr_list(&op.params)?;
+                (MLIL_SHARED_PARAM_SLOT, [count, list, 0, 0, 0])
+            }
+            Lifted::Neg(op) => (MLIL_NEG, self.unary_op_operands(op)?),
+            Lifted::Not(op) => (MLIL_NOT, self.unary_op_operands(op)?),
+            Lifted::Sx(op) => (MLIL_SX, self.unary_op_operands(op)?),
+            Lifted::Zx(op) => (MLIL_ZX, self.unary_op_operands(op)?),
+            Lifted::LowPart(op) => (MLIL_LOW_PART, self.unary_op_operands(op)?),
+            Lifted::BoolToInt(op) => (MLIL_BOOL_TO_INT, self.unary_op_operands(op)?),
+            Lifted::UnimplMem(op) => (MLIL_UNIMPL_MEM, self.unary_op_operands(op)?),
+            Lifted::Fsqrt(op) => (MLIL_FSQRT, self.unary_op_operands(op)?),
+            Lifted::Fneg(op) => (MLIL_FNEG, self.unary_op_operands(op)?),
+            Lifted::Fabs(op) => (MLIL_FABS, self.unary_op_operands(op)?),
+            Lifted::FloatToInt(op) => (MLIL_FLOAT_TO_INT, self.unary_op_operands(op)?),
+            Lifted::IntToFloat(op) => (MLIL_INT_TO_FLOAT, self.unary_op_operands(op)?),
+            Lifted::FloatConv(op) => (MLIL_FLOAT_CONV, self.unary_op_operands(op)?),
+            Lifted::RoundToInt(op) => (MLIL_ROUND_TO_INT, self.unary_op_operands(op)?),
+            Lifted::Floor(op) => (MLIL_FLOOR, self.unary_op_operands(op)?),
+            Lifted::Ceil(op) => (MLIL_CEIL, self.unary_op_operands(op)?),
+            Lifted::Ftrunc(op) => (MLIL_FTRUNC, self.unary_op_operands(op)?),
+            Lifted::Load(op) => (MLIL_LOAD, self.unary_op_operands(op)?),
+            Lifted::LoadStruct(op) => (
+                MLIL_LOAD_STRUCT,
+                [self.expr_operand(&op.src)?, op.offset, 0, 0, 0],
+            ),
+            Lifted::LoadStructSsa(op) => (
+                MLIL_LOAD_STRUCT_SSA,
+                [self.expr_operand(&op.src)?, op.offset, op.src_memory, 0, 0],
+            ),
+            Lifted::LoadSsa(op) => (
+                MLIL_LOAD_SSA,
+                [self.expr_operand(&op.src)?, op.src_memory, 0, 0, 0],
+            ),
+            Lifted::Ret(op) => {
+                let [count, list] = self.expr_list(&op.src)?;
+                (MLIL_RET, [count, list, 0, 0, 0])
+            }
+            Lifted::Var(op) => (MLIL_VAR, [op.src.to_identifier(), 0, 0, 0, 0]),
+            Lifted::AddressOf(op) => (MLIL_ADDRESS_OF, [op.src.to_identifier(), 0, 0, 0, 0]),
+            Lifted::VarField(op) => (MLIL_VAR_FIELD, [op.src.to_identifier(), op.offset, 0, 0, 0]),
+            Lifted::AddressOfField(op) => (
+                MLIL_ADDRESS_OF_FIELD,
+                [op.src.to_identifier(), op.offset, 0, 0, 0],
+            ),
+            Lifted::VarSsa(op) => (
+                MLIL_VAR_SSA,
+                [
+                    op.src.variable.to_identifier(),
+                    op.src.version as u64,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::VarAliased(op) => (
+                MLIL_VAR_ALIASED,
+                [
+                    op.src.variable.to_identifier(),
+                    op.src.version as u64,
+                    0,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::VarSsaField(op) => (
+                MLIL_VAR_SSA_FIELD,
+                [
+                    op.src.variable.to_identifier(),
+                    op.src.version as u64,
+                    op.offset,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::VarAliasedField(op) => (
+                MLIL_VAR_ALIASED_FIELD,
+                [
+                    op.src.variable.to_identifier(),
+                    op.src.version as u64,
+                    op.offset,
+                    0,
+                    0,
+                ],
+            ),
+            Lifted::Trap(op) => (MLIL_TRAP, [op.vector, 0, 0, 0, 0]),
+        };
+
+        Ok(self.add_raw_expr(operation, address, expr.size, operands))
+    }
+
+    /// Append the expression `expr` as a new instruction, returning the index of the instruction
+    pub fn add_instruction(&self, expr: usize) -> usize {
+        unsafe { BNMediumLevelILAddInstruction(self.handle, expr) }
+    }
+
+    /// Replace the expression `expr` with the expression `new_expr`, in place
+    pub fn replace_expr(&self, expr: usize, new_expr: usize) {
+        unsafe { BNReplaceMediumLevelILExpr(self.handle, expr, new_expr) }
+    }
+
+    /// Replace the expression of the instruction `instr_idx` with the expression `expr`
+    pub fn replace_instruction(&self, instr_idx: usize, expr: usize) {
+        unsafe { BNReplaceMediumLevelILInstruction(self.handle, instr_idx, expr) }
+    }
+
+    /// Add a `goto` to `label`, at the current address, returning the index of the expression
+    pub fn goto(&self, label: &mut MediumLevelILLabel) -> usize {
+        unsafe { BNMediumLevelILGoto(self.handle, &mut label.0) }
+    }
+
+    /// Add an `if` branching on `condition` to `t` or `f`, at the current address, returning the
+    /// index of the expression
+    pub fn if_expr(
+        &self,
+        condition: &MediumLevelILLiftedInstruction,
+        t: &mut MediumLevelILLabel,
+        f: &mut MediumLevelILLabel,
+    ) -> Result<usize, ()> {
+        let condition = self.add_expr(condition)?;
+        Ok(unsafe { BNMediumLevelILIf(self.handle, condition as u64, &mut t.0, &mut f.0) })
+    }
+
+    /// Add a `jump_to` on `dest` with the targets `(value, label)`, at the current address,
+    /// returning the index of the expression
+    pub fn jump_to(
+        &self,
+        dest: &MediumLevelILLiftedInstruction,
+        targets: &mut [(u64, &mut MediumLevelILLabel)],
+    ) -> Result<usize, ()> {
+        let dest = self.add_expr(dest)?;
+        let mut values: Vec<u64> = targets.iter().map(|(value, _)| *value).collect();
+        let mut labels: Vec<*mut BNMediumLevelILLabel> = targets
+            .iter_mut()
+            .map(|(_, label)| &mut label.0 as *mut _)
+            .collect();
+        let list = unsafe {
+            BNMediumLevelILAddLabelMap(
+                self.handle,
+                values.as_mut_ptr(),
+                labels.as_mut_ptr(),
+                labels.len(),
+            )
+        };
+        let address = self.current_address();
+        Ok(self.add_raw_expr(
+            BNMediumLevelILOperation::MLIL_JUMP_TO,
+            address,
+            0,
+            [dest as u64, (targets.len() * 2) as u64, list as u64, 0, 0],
+        ))
+    }
+
+    /// Set the target of the branches to `label` to the next instruction added
+    pub fn mark_label(&self, label: &mut MediumLevelILLabel) {
+        unsafe { BNMediumLevelILMarkLabel(self.handle, &mut label.0) }
+    }
+
+    /// The label of the instruction `instr_idx` of the source function, when copying a function
+    pub fn label_for_source_instruction(&self, instr_idx: usize) -> Option<&MediumLevelILLabel> {
+        let label = unsafe { BNGetLabelForMediumLevelILSourceInstruction(self.handle, instr_idx) };
+        (!label.is_null()).then(|| unsafe { &*(label as *mut MediumLevelILLabel) })
+    }
+
+    fn add_raw_expr(
+        &self,
+        operation: BNMediumLevelILOperation,
+        address: u64,
+        size: usize,
+        operands: [u64; 5],
+    ) -> usize {
+        let [a, b, c, d, e] = operands;
+        unsafe {
+            BNMediumLevelILAddExprWithLocation(
+                self.handle,
+                operation,
+                address,
+                BN_INVALID_OPERAND,
+                size,
+                a,
+                b,
+                c,
+                d,
+                e,
+            )
+        }
+    }
+
+    fn expr_operand(&self, expr: &MediumLevelILLiftedInstruction) -> Result<u64, ()> {
+        Ok(self.add_expr(expr)? as u64)
+    }
+
+    /// Returns the number of operands and the index of the list
+    fn operand_list(&self, operands: &[u64]) -> [u64; 2] {
+        let mut operands = operands.to_vec();
+        let list = unsafe {
+            BNMediumLevelILAddOperandList(self.handle, operands.as_mut_ptr(), operands.len())
+        };
+        [operands.len() as u64, list as u64]
+    }
+
+    fn expr_list(&self, exprs: &[MediumLevelILLiftedInstruction]) -> Result<[u64; 2], ()> {
+        let exprs: Vec<u64> = exprs
+            .iter()
+            .map(|expr| self.expr_operand(expr))
+            .collect::<Result<_, _>>()?;
+        Ok(self.operand_list(&exprs))
+    }
+
+    fn var_list(&self, vars: &[Variable]) -> [u64; 2] {
+        let vars: Vec<u64> = vars.iter().map(|var| var.to_identifier()).collect();
+        self.operand_list(&vars)
+    }
+
+    fn ssa_var_list(&self, vars: &[SSAVariable]) -> [u64; 2] {
+        let vars: Vec<u64> = vars
+            .iter()
+            .flat_map(|var| [var.variable.to_identifier(), var.version as u64])
+            .collect();
+        self.operand_list(&vars)
+    }
+
+    fn call_output(&self, address: u64, output: &[Variable]) -> u64 {
+        let [count, list] = self.var_list(output);
+        let operation = BNMediumLevelILOperation::MLIL_CALL_OUTPUT;
+        self.add_raw_expr(operation, address, 0, [count, list, 0, 0, 0]) as u64
+    }
+
+    fn call_param(
+        &self,
+        address: u64,
+        params: &[MediumLevelILLiftedInstruction],
+    ) -> Result<u64, ()> {
+        let [count, list] = self.expr_list(params)?;
+        let operation = BNMediumLevelILOperation::MLIL_CALL_PARAM;
+        Ok(self.add_raw_expr(operation, address, 0, [count, list, 0, 0, 0]) as u64)
+    }
+
+    fn call_output_ssa(&self, address: u64, dest_memory: u64, output: &[SSAVariable]) -> u64 {
+        let [count, list] = self.ssa_var_list(output);
+        let operation = BNMediumLevelILOperation::MLIL_CALL_OUTPUT_SSA;
+        self.add_raw_expr(operation, address, 0, [dest_memory, count, list, 0, 0]) as u64
+    }
+
+    fn call_param_ssa(
+        &self,
+        address: u64,
+        src_memory: u64,
+        params: &[MediumLevelILLiftedInstruction],
+    ) -> Result<u64, ()> {
+        let [count, list] = self.expr_list(params)?;
+        let operation = BNMediumLevelILOperation::MLIL_CALL_PARAM_SSA;
+        Ok(self.add_raw_expr(operation, address, 0, [src_memory, count, list, 0, 0]) as u64)
+    }
+
+    fn unary_op_operands(&self, op: &LiftedUnaryOp) -> Result<[u64; 5], ()> {
+        Ok([self.expr_operand(&op.src)?, 0, 0, 0, 0])
+    }
+
+    fn binary_op_operands(&self, op: &LiftedBinaryOp) -> Result<[u64; 5], ()> {
+        Ok([
+            self.expr_operand(&op.left)?,
+            self.expr_operand(&op.right)?,
+            0,
+            0,
+            0,
+        ])
+    }
+
+    fn binary_op_carry_operands(&self, op: &LiftedBinaryOpCarry) -> Result<[u64; 5], ()> {
+        Ok([
+            self.expr_operand(&op.left)?,
+            self.expr_operand(&op.right)?,
+            self.expr_operand(&op.carry)?,
+            0,
+            0,
+        ])
+    }
+
+    fn set_var_ssa_field_operands(&self, op: &LiftedSetVarSsaField) -> Result<[u64; 5], ()> {
+        Ok([
+            op.dest.variable.to_identifier(),
+            op.dest.version as u64,
+            op.prev.version as u64,
+            op.offset,
+            self.expr_operand(&op.src)?,
+        ])
+    }
+
+    fn call_operands(&self, op: &LiftedCall) -> Result<[u64; 5], ()> {
+        let [num_outputs, outputs] = self.var_list(&op.output);
+        let dest = self.expr_operand(&op.dest)?;
+        let [num_params, params] = self.expr_list(&op.params)?;
+        Ok([num_outputs, outputs, dest, num_params, params])
+    }
+
+    fn call_untyped_operands(&self, address: u64, op: &LiftedCallUntyped) -> Result<[u64; 5], ()> {
+        let output = self.call_output(address, &op.output);
+        let dest = self.expr_operand(&op.dest)?;
+        let params = self.call_param(address, &op.params)?;
+        let stack = self.expr_operand(&op.stack)?;
+        Ok([output, dest, params, stack, 0])
+    }
+
+    fn call_ssa_operands(&self, address: u64, op: &LiftedCallSsa) -> Result<[u64; 5], ()> {
+        let output = self.call_output_ssa(address, op.output_dest_memory, &op.output);
+        let dest = self.expr_operand(&op.dest)?;
+        let [num_params, params] = self.expr_list(&op.params)?;
+        Ok([output, dest, num_params, params, op.src_memory])
+    }
+
+    fn call_untyped_ssa_operands(
+        &self,
+        address: u64,
+        op: &LiftedCallUntypedSsa,
+    ) -> Result<[u64; 5], ()> {
+        let output = self.call_output_ssa(address, op.output_dest_memory, &op.output);
+        let dest = self.expr_operand(&op.dest)?;
+        let params = self.call_param_ssa(address, op.params_src_memory, &op.params)?;
+        let stack = self.expr_operand(&op.stack)?;
+        Ok([output, dest, params, stack, 0])
+    }
+}
+
+/// A branch target in a [MediumLevelILFunction] being built, see
+/// [MediumLevelILFunction::mark_label]
+#[repr(C)]
+pub struct MediumLevelILLabel(BNMediumLevelILLabel);
+
+impl MediumLevelILLabel {
+    pub fn new() -> Self {
+        unsafe {
+            let mut res = MediumLevelILLabel(mem::zeroed());
+            BNMediumLevelILInitLabel(&mut res.0);
+            res
+        }
+    }
+}
+
+impl Default for MediumLevelILLabel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Encode a float constant of `size` bytes as an IL operand, shared with the HLIL builder
+///
+/// Only single and double precision constants can be encoded, as the other sizes are not decoded
+/// either, see `get_float`
+pub(crate) fn get_raw_float(value: f64, size: usize) -> Result<u64, ()> {
+    match size {
+        4 => Ok((value as f32).to_bits() as u64),
+        8 => Ok(value.to_bits()),
+        _ => Err(()),
+    }
+}
@@ -8,6 +8,8 @@ use crate::basicblock::BasicBlock;
 use crate::disassembly::DisassemblySettings;
 use crate::flowgraph::FlowGraph;
 use crate::function::{Function, Location};
+use crate::hlil::HighLevelILFunction;
+use crate::llil;
 use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner, Ref, RefCountable};
 use crate::string::BnStrCompatible;
 use crate::types::{
@@ -95,6 +97,83 @@ impl MediumLevelILFunction {
         }
     }
 
+    /// The low level IL this function was lifted from
+    pub fn low_level_il(&self) -> Option<Ref<llil::RegularFunction<CoreArchitecture>>> {
+        let llil = unsafe { BNGetLowLevelILForMediumLevelIL(self.handle) };
+        (!llil.is_null()).then(|| unsafe {
+            llil::RegularFunction::ref_from_raw(self.get_function().arch(), llil)
+        })
+    }
+
+    /// The high level IL lifted from this function
+    pub fn high_level_il(&self, full_ast: bool) -> Option<Ref<HighLevelILFunction>> {
+        let hlil = unsafe { BNGetHighLevelILForMediumLevelIL(self.handle) };
+        (!hlil.is_null()).then(|| unsafe {
+            Ref::new(HighLevelILFunction {
+                handle: hlil,
+                full_ast,
+            })
+        })
+    }
+
+    /// Index of the [MediumLevelILFunction::low_level_il] instruction for the instruction at
+    /// `instr_idx`
+    pub fn low_level_il_instruction_index(&self, instr_idx: usize) -> Option<usize> {
+        let llil = self.low_level_il()?;
+        let result = unsafe { BNGetLowLevelILInstructionIndex(self.handle, instr_idx) };
+        (result < llil.instruction_count()).then_some(result)
+    }
+
+    /// Index of the [MediumLevelILFunction::low_level_il] expression for the expression at
+    /// `expr_idx`, see [llil::Function::expression_from_idx]
+    pub fn low_level_il_expr_index(&self, expr_idx: usize) -> Option<usize> {
+        let llil = self.low_level_il()?;
+        let result = unsafe { BNGetLowLevelILExprIndex(self.handle, expr_idx) };
+        (result < unsafe { BNGetLowLevelILExprCount(llil.handle) }).then_some(result)
+    }
+
+    /// Indices of all the [MediumLevelILFunction::low_level_il] expressions for the expression at
+    /// `expr_idx`
+    pub fn low_level_il_expr_indexes(&self, expr_idx: usize) -> Vec<usize> {
+        let mut count = 0;
+        unsafe {
+            let exprs = BNGetLowLevelILExprIndexes(self.handle, expr_idx, &mut count);
+            assert!(!exprs.is_null());
+            let result = core::slice::from_raw_parts(exprs, count).to_vec();
+            BNFreeILInstructionList(exprs);
+            result
+        }
+    }
+
+    /// Index of the [MediumLevelILFunction::high_level_il] instruction for the instruction at
+    /// `instr_idx`
+    pub fn high_level_il_instruction_index(&self, instr_idx: usize) -> Option<usize> {
+        let hlil = self.high_level_il(false)?;
+        let result = unsafe { BNGetHighLevelILInstructionIndex(self.handle, instr_idx) };
+        (result < hlil.instruction_count()).then_some(result)
+    }
+
+    /// Index of the [MediumLevelILFunction::high_level_il] expression for the expression at
+    /// `expr_idx`
+    pub fn high_level_il_expr_index(&self, expr_idx: usize) -> Option<usize> {
+        let hlil = self.high_level_il(false)?;
+        let result = unsafe { BNGetHighLevelILExprIndex(self.handle, expr_idx) };
+        (result < unsafe { BNGetHighLevelILExprCount(hlil.handle) }).then_some(result)
+    }
+
+    /// Indices of all the [MediumLevelILFunction::high_level_il] expressions for the expression
+    /// at `expr_idx`
+    pub fn high_level_il_expr_indexes(&self, expr_idx: usize) -> Vec<usize> {
+        let mut count = 0;
+        unsafe {
+            let exprs = BNGetHighLevelILExprIndexes(self.handle, expr_idx, &mut count);
+            assert!(!exprs.is_null());
+            let result = core::slice::from_raw_parts(exprs, count).to_vec();
+            BNFreeILInstructionList(exprs);
+            result
+        }
+    }
+
     pub fn basic_blocks(&self) -> Array<BasicBlock<MediumLevelILBlock>> {
         let mut count = 0;
         let blocks = unsafe { BNGetMediumLevelILBasicBlockList(self.handle, &mut count) };
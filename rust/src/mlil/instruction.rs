@@ -2,6 +2,7 @@ use binaryninjacore_sys::*;
 
 use crate::architecture::CoreIntrinsic;
 use crate::disassembly::InstructionTextToken;
+use crate::hlil::HighLevelILInstruction;
 use crate::operand_iter::OperandIter;
 use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner, Ref};
 use crate::types::{
@@ -940,6 +941,7 @@ impl MediumLevelILInstruction {
 
             SyscallSsa(op) => Lifted::SyscallSsa(LiftedSyscallSsa {
                 output: get_call_output_ssa(&self.function, op.output).collect(),
+                output_dest_memory: get_call_output_ssa_dest_memory(&self.function, op.output),
                 params: OperandIter::new(&*self.function, op.first_param, op.num_params)
                     .exprs()
                     .map(|expr| expr.lift())
@@ -948,9 +950,11 @@ impl MediumLevelILInstruction {
             }),
             SyscallUntypedSsa(op) => Lifted::SyscallUntypedSsa(LiftedSyscallUntypedSsa {
                 output: get_call_output_ssa(&self.function, op.output).collect(),
+                output_dest_memory: get_call_output_ssa_dest_memory(&self.function, op.output),
                 params: get_call_params_ssa(&self.function, op.params)
                     .map(|param| param.lift())
                     .collect(),
+                params_src_memory: get_call_params_ssa_src_memory(&self.function, op.params),
                 stack: self.lift_operand(op.stack),
             }),
 
@@ -1416,6 +1420,35 @@ impl MediumLevelILInstruction {
         self.split_var_for_definition(*var)
     }
 
+    /// Index of the [MediumLevelILFunction::low_level_il] expression for this expression
+    pub fn low_level_il_expr_index(&self) -> Option<usize> {
+        self.function.low_level_il_expr_index(self.index)
+    }
+
+    /// Indices of all the [MediumLevelILFunction::low_level_il] expressions for this expression
+    pub fn low_level_il_expr_indexes(&self) -> Vec<usize> {
+        self.function.low_level_il_expr_indexes(self.index)
+    }
+
+    /// The [MediumLevelILFunction::high_level_il] expression for this expression
+    pub fn high_level_il(&self) -> Option<HighLevelILInstruction> {
+        let hlil = self.function.high_level_il(true)?;
+        let expr_idx = self.function.high_level_il_expr_index(self.index)?;
+        Some(hlil.instruction_from_idx(expr_idx))
+    }
+
+    /// All the [MediumLevelILFunction::high_level_il] expressions for this expression
+    pub fn high_level_il_exprs(&self) -> Vec<HighLevelILInstruction> {
+        let Some(hlil) = self.function.high_level_il(true) else {
+            return vec![];
+        };
+        self.function
+            .high_level_il_expr_indexes(self.index)
+            .into_iter()
+            .map(|expr_idx| hlil.instruction_from_idx(expr_idx))
+            .collect()
+    }
+
     fn lift_operand(&self, expr_idx: usize) -> Box<MediumLevelILLiftedInstruction> {
         Box::new(self.function.lifted_instruction_from_idx(expr_idx))
     }
@@ -1468,6 +1501,7 @@ impl MediumLevelILInstruction {
     fn lift_call_ssa(&self, op: CallSsa) -> LiftedCallSsa {
         LiftedCallSsa {
             output: get_call_output_ssa(&self.function, op.output).collect(),
+            output_dest_memory: get_call_output_ssa_dest_memory(&self.function, op.output),
             dest: self.lift_operand(op.dest),
             params: OperandIter::new(&*self.function, op.first_param, op.num_params)
                 .exprs()
@@ -1480,10 +1514,12 @@ impl MediumLevelILInstruction {
     fn lift_call_untyped_ssa(&self, op: CallUntypedSsa) -> LiftedCallUntypedSsa {
         LiftedCallUntypedSsa {
             output: get_call_output_ssa(&self.function, op.output).collect(),
+            output_dest_memory: get_call_output_ssa_dest_memory(&self.function, op.output),
             dest: self.lift_operand(op.dest),
             params: get_call_params_ssa(&self.function, op.params)
                 .map(|param| param.lift())
                 .collect(),
+            params_src_memory: get_call_params_ssa_src_memory(&self.function, op.params),
             stack: self.lift_operand(op.stack),
         }
     }
@@ -1559,6 +1595,18 @@ fn get_call_params_ssa(
     OperandIter::new(function, op.operands[2] as usize, op.operands[1] as usize).exprs()
 }
 
+fn get_call_output_ssa_dest_memory(function: &MediumLevelILFunction, idx: usize) -> u64 {
+    let op = get_raw_operation(function, idx);
+    assert_eq!(op.operation, BNMediumLevelILOperation::MLIL_CALL_OUTPUT_SSA);
+    op.operands[0]
+}
+
+fn get_call_params_ssa_src_memory(function: &MediumLevelILFunction, idx: usize) -> u64 {
+    let op = get_raw_operation(function, idx);
+    assert_eq!(op.operation, BNMediumLevelILOperation::MLIL_CALL_PARAM_SSA);
+    op.operands[0]
+}
+
 /// Conditional branching instruction and an expected conditional result
 pub struct BranchDependence {
     pub instruction: MediumLevelILInstruction,
@@ -442,24 +442,30 @@ impl MediumLevelILLiftedInstruction {
             ],
             CallSsa(op) | TailcallSsa(op) => vec![
                 ("output", Operand::VarSsaList(op.output.clone())),
+                ("output_dest_memory", Operand::Int(op.output_dest_memory)),
                 ("dest", Operand::Expr(*op.dest.clone())),
                 ("params", Operand::ExprList(op.params.clone())),
                 ("src_memory", Operand::Int(op.src_memory)),
             ],
             CallUntypedSsa(op) | TailcallUntypedSsa(op) => vec![
                 ("output", Operand::VarSsaList(op.output.clone())),
+                ("output_dest_memory", Operand::Int(op.output_dest_memory)),
                 ("dest", Operand::Expr(*op.dest.clone())),
                 ("params", Operand::ExprList(op.params.clone())),
+                ("params_src_memory", Operand::Int(op.params_src_memory)),
                 ("stack", Operand::Expr(*op.stack.clone())),
             ],
             SyscallSsa(op) => vec![
                 ("output", Operand::VarSsaList(op.output.clone())),
+                ("output_dest_memory", Operand::Int(op.output_dest_memory)),
                 ("params", Operand::ExprList(op.params.clone())),
                 ("src_memory", Operand::Int(op.src_memory)),
             ],
             SyscallUntypedSsa(op) => vec![
                 ("output", Operand::VarSsaList(op.output.clone())),
+                ("output_dest_memory", Operand::Int(op.output_dest_memory)),
                 ("params", Operand::ExprList(op.params.clone())),
+                ("params_src_memory", Operand::Int(op.params_src_memory)),
                 ("stack", Operand::Expr(*op.stack.clone())),
             ],
             CallUntyped(op) | TailcallUntyped(op) => vec![
@@ -1,10 +1,12 @@
 mod block;
+mod builder;
 mod function;
 mod instruction;
 mod lift;
 pub mod operation;
 
 pub use self::block::*;
+pub use self::builder::*;
 pub use self::function::*;
 pub use self::instruction::*;
 pub use self::lift::*;
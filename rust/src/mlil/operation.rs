@@ -387,6 +387,7 @@ pub struct CallSsa {
 #[derive(Clone, Debug, PartialEq)]
 pub struct LiftedCallSsa {
     pub output: Vec<SSAVariable>,
+    pub output_dest_memory: u64,
     pub dest: Box<MediumLevelILLiftedInstruction>,
     pub params: Vec<MediumLevelILLiftedInstruction>,
     pub src_memory: u64,
@@ -403,8 +404,10 @@ pub struct CallUntypedSsa {
 #[derive(Clone, Debug, PartialEq)]
 pub struct LiftedCallUntypedSsa {
     pub output: Vec<SSAVariable>,
+    pub output_dest_memory: u64,
     pub dest: Box<MediumLevelILLiftedInstruction>,
     pub params: Vec<MediumLevelILLiftedInstruction>,
+    pub params_src_memory: u64,
     pub stack: Box<MediumLevelILLiftedInstruction>,
 }
 
@@ -419,6 +422,7 @@ pub struct SyscallSsa {
 #[derive(Clone, Debug, PartialEq)]
 pub struct LiftedSyscallSsa {
     pub output: Vec<SSAVariable>,
+    pub output_dest_memory: u64,
     pub params: Vec<MediumLevelILLiftedInstruction>,
     pub src_memory: u64,
 }
@@ -433,7 +437,9 @@ pub struct SyscallUntypedSsa {
 #[derive(Clone, Debug, PartialEq)]
 pub struct LiftedSyscallUntypedSsa {
     pub output: Vec<SSAVariable>,
+    pub output_dest_memory: u64,
     pub params: Vec<MediumLevelILLiftedInstruction>,
+    pub params_src_memory: u64,
     pub stack: Box<MediumLevelILLiftedInstruction>,
 }
 
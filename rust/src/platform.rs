@@ -14,7 +14,7 @@
 
 //! Contains all information related to the execution environment of the binary, mainly the calling conventions used
 
-use std::{borrow::Borrow, collections::HashMap, os::raw, path::Path, ptr, slice};
+use std::{borrow::Borrow, collections::HashMap, os::raw, path::Path, ptr};
 
 use binaryninjacore_sys::*;
 
@@ -23,7 +23,9 @@ use crate::{
     callingconvention::CallingConvention,
     rc::*,
     string::*,
+    typecontainer::TypeContainer,
     typelibrary::TypeLibrary,
+    typeparser,
     types::{QualifiedName, QualifiedNameAndType, Type},
 };
 
@@ -234,6 +236,12 @@ impl Platform {
         }
     }
 
+    /// Type container for the types of the platform and its type libraries
+    pub fn type_container(&self) -> TypeContainer {
+        let container = unsafe { BNGetPlatformTypeContainer(self.handle) };
+        unsafe { TypeContainer::from_raw(ptr::NonNull::new(container).unwrap()) }
+    }
+
     pub fn variables(&self) -> Array<QualifiedNameAndType> {
         unsafe {
             let mut count = 0;
@@ -251,45 +259,20 @@ impl Platform {
             Array::new(handles, count, ())
         }
     }
-}
 
-pub trait TypeParser {
-    fn parse_types_from_source<S: BnStrCompatible, P: AsRef<Path>>(
-        &self,
-        _source: S,
-        _filename: S,
-        _include_directories: &[P],
-        _auto_type_source: S,
-    ) -> Result<TypeParserResult, String> {
-        Err(String::new())
-    }
-}
-
-#[derive(Clone, Default)]
-pub struct TypeParserResult {
-    pub types: HashMap<String, Ref<Type>>,
-    pub variables: HashMap<String, Ref<Type>>,
-    pub functions: HashMap<String, Ref<Type>>,
-}
-
-impl TypeParser for Platform {
-    fn parse_types_from_source<S: BnStrCompatible, P: AsRef<Path>>(
+    /// Parse the types declared in `source`, using the types of this platform
+    ///
+    /// See [TypeParser](crate::typeparser::TypeParser) to select the parser or get structured
+    /// errors. This replaces the deprecated [TypeParser::parse_types_from_source], which returns
+    /// the types by name.
+    pub fn parse_types<S: BnStrCompatible, P: AsRef<Path>>(
         &self,
         source: S,
         filename: S,
         include_directories: &[P],
         auto_type_source: S,
-    ) -> Result<TypeParserResult, String> {
-        let mut result = BNTypeParserResult {
-            functionCount: 0,
-            typeCount: 0,
-            variableCount: 0,
-            functions: ptr::null_mut(),
-            types: ptr::null_mut(),
-            variables: ptr::null_mut(),
-        };
-
-        let mut type_parser_result = TypeParserResult::default();
+    ) -> Result<typeparser::TypeParserResult, String> {
+        let mut result = BNTypeParserResult::default();
 
         let mut error_string: *mut raw::c_char = ptr::null_mut();
 
@@ -297,16 +280,17 @@ impl TypeParser for Platform {
         let filename = filename.into_bytes_with_nul();
         let auto_type_source = auto_type_source.into_bytes_with_nul();
 
-        let mut include_dirs = vec![];
-
-        for dir in include_directories.iter() {
-            let d = dir
-                .as_ref()
-                .to_string_lossy()
-                .to_string()
-                .into_bytes_with_nul();
-            include_dirs.push(d.as_ptr() as _);
-        }
+        let include_dirs: Vec<_> = include_directories
+            .iter()
+            .map(|dir| {
+                dir.as_ref()
+                    .to_string_lossy()
+                    .to_string()
+                    .into_bytes_with_nul()
+            })
+            .collect();
+        let mut include_dirs: Vec<*const raw::c_char> =
+            include_dirs.iter().map(|dir| dir.as_ptr() as _).collect();
 
         unsafe {
             let success = BNParseTypesFromSource(
@@ -326,29 +310,57 @@ impl TypeParser for Platform {
                 return Err(error_msg.to_string());
             }
 
-            for i in slice::from_raw_parts(result.types, result.typeCount) {
-                let name = QualifiedName(i.name);
-                type_parser_result
-                    .types
-                    .insert(name.string(), Type::ref_from_raw(i.type_));
-            }
+            let type_parser_result = typeparser::TypeParserResult::from_raw(&result);
+            BNFreeTypeParserResult(&mut result);
+            Ok(type_parser_result)
+        }
+    }
+}
 
-            for i in slice::from_raw_parts(result.functions, result.functionCount) {
-                let name = QualifiedName(i.name);
-                type_parser_result
-                    .functions
-                    .insert(name.string(), Type::ref_from_raw(i.type_));
-            }
+#[deprecated(note = "use Platform::parse_types or crate::typeparser::TypeParser instead")]
+#[allow(deprecated)]
+pub trait TypeParser {
+    fn parse_types_from_source<S: BnStrCompatible, P: AsRef<Path>>(
+        &self,
+        _source: S,
+        _filename: S,
+        _include_directories: &[P],
+        _auto_type_source: S,
+    ) -> Result<TypeParserResult, String> {
+        Err(String::new())
+    }
+}
 
-            for i in slice::from_raw_parts(result.variables, result.variableCount) {
-                let name = QualifiedName(i.name);
-                type_parser_result
-                    .variables
-                    .insert(name.string(), Type::ref_from_raw(i.type_));
-            }
+#[deprecated(note = "use crate::typeparser::TypeParserResult instead")]
+#[derive(Clone, Default)]
+pub struct TypeParserResult {
+    pub types: HashMap<String, Ref<Type>>,
+    pub variables: HashMap<String, Ref<Type>>,
+    pub functions: HashMap<String, Ref<Type>>,
+}
+
+#[allow(deprecated)]
+impl TypeParser for Platform {
+    fn parse_types_from_source<S: BnStrCompatible, P: AsRef<Path>>(
+        &self,
+        source: S,
+        filename: S,
+        include_directories: &[P],
+        auto_type_source: S,
+    ) -> Result<TypeParserResult, String> {
+        fn by_name(parsed: Vec<typeparser::ParsedType>) -> HashMap<String, Ref<Type>> {
+            parsed
+                .into_iter()
+                .map(|parsed| (parsed.name.string(), parsed.ty))
+                .collect()
         }
 
-        Ok(type_parser_result)
+        let result = self.parse_types(source, filename, include_directories, auto_type_source)?;
+        Ok(TypeParserResult {
+            types: by_name(result.types),
+            variables: by_name(result.variables),
+            functions: by_name(result.functions),
+        })
     }
 }
 
@@ -0,0 +1,633 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Installing, enabling and updating the plugins published in plugin repositories.
+//!
+//! Repositories are git repositories with a `plugins.json` listing their plugins, cloned in the
+//! repositories directory of the user. The [RepositoryManager] keeps track of them and of the
+//! plugins enabled by the user.
+//!
+//! ```no_run
+//! use binaryninja::pluginmanager::RepositoryManager;
+//!
+//! binaryninja::headless::init();
+//!
+//! let manager = RepositoryManager::default_manager();
+//! manager.check_for_updates();
+//! let repo = manager.default_repository().unwrap();
+//! for plugin in &repo.plugins() {
+//!     if plugin.name().as_str() == "Example Plugin" && !plugin.is_installed() {
+//!         plugin.install();
+//!         plugin.enable(false);
+//!     }
+//! }
+//!
+//! binaryninja::headless::shutdown();
+//! ```
+
+use binaryninjacore_sys::*;
+
+use std::ffi::c_char;
+use std::fmt;
+
+use crate::rc::*;
+use crate::string::*;
+use crate::VersionInfo;
+
+pub type PluginStatus = BNPluginStatus;
+pub type PluginType = BNPluginType;
+
+/// Keeps track of the plugin repositories and of the plugins enabled by the user
+#[derive(PartialEq, Eq, Hash)]
+pub struct RepositoryManager {
+    handle: *mut BNRepositoryManager,
+}
+
+unsafe impl Send for RepositoryManager {}
+unsafe impl Sync for RepositoryManager {}
+
+impl RepositoryManager {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNRepositoryManager) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    /// Create a manager for the repositories of the user, with the list of enabled plugins
+    /// stored at `enabled_plugins_path`
+    ///
+    /// This is mostly useful to manage plugins independently of the user settings, eg: when
+    /// provisioning plugins from a local repository added with [RepositoryManager::add_repository].
+    pub fn new<S: BnStrCompatible>(enabled_plugins_path: S) -> Ref<Self> {
+        let path = enabled_plugins_path.into_bytes_with_nul();
+        unsafe {
+            Self::ref_from_raw(BNCreateRepositoryManager(
+                path.as_ref().as_ptr() as *const c_char
+            ))
+        }
+    }
+
+    /// The manager used by the core, plugins must have been initialized, see [crate::headless::init]
+    pub fn default_manager() -> Ref<Self> {
+        unsafe { Self::ref_from_raw(BNNewRepositoryManagerReference(BNGetRepositoryManager())) }
+    }
+
+    /// Fetch the latest version of all the repositories, returns false if any of them failed
+    pub fn check_for_updates(&self) -> bool {
+        unsafe { BNRepositoryManagerCheckForUpdates(self.handle) }
+    }
+
+    pub fn repositories(&self) -> Array<Repository> {
+        let mut count = 0;
+        unsafe {
+            let repos = BNRepositoryManagerGetRepositories(self.handle, &mut count);
+            Array::new(repos, count, ())
+        }
+    }
+
+    /// Add the repository at `url`, cloned in `repo_path` relative to the repositories directory
+    ///
+    /// The repository is only fetched by the next call to [RepositoryManager::check_for_updates].
+    pub fn add_repository<U: BnStrCompatible, P: BnStrCompatible>(
+        &self,
+        url: U,
+        repo_path: P,
+    ) -> bool {
+        let url = url.into_bytes_with_nul();
+        let repo_path = repo_path.into_bytes_with_nul();
+        unsafe {
+            BNRepositoryManagerAddRepository(
+                self.handle,
+                url.as_ref().as_ptr() as *const c_char,
+                repo_path.as_ref().as_ptr() as *const c_char,
+            )
+        }
+    }
+
+    /// Retrieve a repository by its path relative to the repositories directory
+    pub fn repository_by_path<S: BnStrCompatible>(&self, repo_path: S) -> Option<Ref<Repository>> {
+        let repo_path = repo_path.into_bytes_with_nul();
+        let repo = unsafe {
+            BNRepositoryGetRepositoryByPath(
+                self.handle,
+                repo_path.as_ref().as_ptr() as *const c_char,
+            )
+        };
+        (!repo.is_null())
+            .then(|| unsafe { Repository::ref_from_raw(BNNewRepositoryReference(repo)) })
+    }
+
+    /// The official repository of community plugins
+    pub fn default_repository(&self) -> Option<Ref<Repository>> {
+        let repo = unsafe { BNRepositoryManagerGetDefaultRepository(self.handle) };
+        (!repo.is_null())
+            .then(|| unsafe { Repository::ref_from_raw(BNNewRepositoryReference(repo)) })
+    }
+}
+
+impl ToOwned for RepositoryManager {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for RepositoryManager {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewRepositoryManagerReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeRepositoryManager(handle.handle);
+    }
+}
+
+/// A repository of plugins
+#[derive(PartialEq, Eq, Hash)]
+pub struct Repository {
+    handle: *mut BNRepository,
+}
+
+unsafe impl Send for Repository {}
+unsafe impl Sync for Repository {}
+
+impl Repository {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNRepository) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    pub fn url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRepositoryGetUrl(self.handle)) }
+    }
+
+    /// The path of the repository, relative to the repositories directory
+    pub fn path(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRepositoryGetRepoPath(self.handle)) }
+    }
+
+    /// The absolute path of the plugins of the repository
+    pub fn full_path(&self) -> BnString {
+        unsafe { BnString::from_raw(BNRepositoryGetPluginsPath(self.handle) as *mut c_char) }
+    }
+
+    pub fn plugins(&self) -> Array<RepoPlugin> {
+        let mut count = 0;
+        unsafe {
+            let plugins = BNRepositoryGetPlugins(self.handle, &mut count);
+            Array::new(plugins, count, ())
+        }
+    }
+
+    /// Retrieve a plugin by its path relative to the repository
+    pub fn plugin_by_path<S: BnStrCompatible>(&self, plugin_path: S) -> Option<Ref<RepoPlugin>> {
+        let plugin_path = plugin_path.into_bytes_with_nul();
+        let plugin = unsafe {
+            BNRepositoryGetPluginByPath(self.handle, plugin_path.as_ref().as_ptr() as *const c_char)
+        };
+        (!plugin.is_null())
+            .then(|| unsafe { RepoPlugin::ref_from_raw(BNNewPluginReference(plugin)) })
+    }
+}
+
+impl fmt::Debug for Repository {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<repository '{}'>", self.path())
+    }
+}
+
+impl ToOwned for Repository {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Repository {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewRepositoryReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeRepository(handle.handle);
+    }
+}
+
+impl CoreArrayProvider for Repository {
+    type Raw = *mut BNRepository;
+    type Context = ();
+    type Wrapped<'a> = Guard<'a, Repository>;
+}
+
+unsafe impl CoreArrayProviderInner for Repository {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeRepositoryManagerRepositoriesList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Guard::new(Repository { handle: *raw }, context)
+    }
+}
+
+/// A plugin published in a [Repository]
+#[derive(PartialEq, Eq, Hash)]
+pub struct RepoPlugin {
+    handle: *mut BNRepoPlugin,
+}
+
+unsafe impl Send for RepoPlugin {}
+unsafe impl Sync for RepoPlugin {}
+
+impl RepoPlugin {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNRepoPlugin) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetName(self.handle) as *mut c_char) }
+    }
+
+    pub fn version(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetVersion(self.handle) as *mut c_char) }
+    }
+
+    /// The commit of the repository of the plugin the published version was built from
+    pub fn commit(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetCommit(self.handle) as *mut c_char) }
+    }
+
+    pub fn author(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetAuthor(self.handle) as *mut c_char) }
+    }
+
+    pub fn description(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetDescription(self.handle) as *mut c_char) }
+    }
+
+    pub fn long_description(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetLongdescription(self.handle) as *mut c_char) }
+    }
+
+    pub fn license_text(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetLicenseText(self.handle) as *mut c_char) }
+    }
+
+    pub fn project_url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetProjectUrl(self.handle) as *mut c_char) }
+    }
+
+    pub fn package_url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetPackageUrl(self.handle) as *mut c_char) }
+    }
+
+    pub fn author_url(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetAuthorUrl(self.handle) as *mut c_char) }
+    }
+
+    /// The path of the repository containing the plugin
+    pub fn repository(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetRepository(self.handle) as *mut c_char) }
+    }
+
+    /// The path of the plugin, relative to its repository
+    pub fn path(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetPath(self.handle) as *mut c_char) }
+    }
+
+    /// The directory of the plugin inside of its package, if the package contains more than it
+    pub fn subdir(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetSubdir(self.handle) as *mut c_char) }
+    }
+
+    /// The python dependencies of the plugin, in the format of a `requirements.txt`
+    pub fn dependencies(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetDependencies(self.handle) as *mut c_char) }
+    }
+
+    /// The raw `plugin.json` of the plugin
+    pub fn project_data(&self) -> BnString {
+        unsafe { BnString::from_raw(BNPluginGetProjectData(self.handle)) }
+    }
+
+    /// The APIs the plugin is implemented with, eg: `python3`
+    pub fn apis(&self) -> Array<BnString> {
+        let mut count = 0;
+        unsafe {
+            let apis = BNPluginGetApis(self.handle, &mut count);
+            Array::new(apis, count, ())
+        }
+    }
+
+    /// The platforms the plugin can be installed on, eg: `Linux`
+    pub fn install_platforms(&self) -> Array<BnString> {
+        let mut count = 0;
+        unsafe {
+            let platforms = BNPluginGetPlatforms(self.handle, &mut count);
+            Array::new(platforms, count, ())
+        }
+    }
+
+    pub fn plugin_types(&self) -> Vec<PluginType> {
+        let mut count = 0;
+        unsafe {
+            let types = BNPluginGetPluginTypes(self.handle, &mut count);
+            if types.is_null() {
+                return Vec::new();
+            }
+            let result = std::slice::from_raw_parts(types, count).to_vec();
+            BNFreePluginTypes(types);
+            result
+        }
+    }
+
+    /// The minimum version of Binary Ninja the plugin was tested on
+    pub fn minimum_version_info(&self) -> VersionInfo {
+        unsafe { version_info_from_raw(BNPluginGetMinimumVersionInfo(self.handle)) }
+    }
+
+    /// The maximum version of Binary Ninja the plugin supports
+    pub fn maximum_version_info(&self) -> VersionInfo {
+        unsafe { version_info_from_raw(BNPluginGetMaximumVersionInfo(self.handle)) }
+    }
+
+    /// The time of the last update of the plugin, in seconds since the epoch
+    pub fn last_update(&self) -> u64 {
+        unsafe { BNPluginGetLastUpdate(self.handle) }
+    }
+
+    pub fn status(&self) -> PluginStatus {
+        unsafe { BNPluginGetPluginStatus(self.handle) }
+    }
+
+    pub fn is_installed(&self) -> bool {
+        unsafe { BNPluginIsInstalled(self.handle) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        unsafe { BNPluginIsEnabled(self.handle) }
+    }
+
+    pub fn is_running(&self) -> bool {
+        unsafe { BNPluginIsRunning(self.handle) }
+    }
+
+    pub fn is_update_available(&self) -> bool {
+        unsafe { BNPluginIsUpdateAvailable(self.handle) }
+    }
+
+    pub fn is_update_pending(&self) -> bool {
+        unsafe { BNPluginIsUpdatePending(self.handle) }
+    }
+
+    pub fn is_disable_pending(&self) -> bool {
+        unsafe { BNPluginIsDisablePending(self.handle) }
+    }
+
+    pub fn is_delete_pending(&self) -> bool {
+        unsafe { BNPluginIsDeletePending(self.handle) }
+    }
+
+    pub fn is_being_updated(&self) -> bool {
+        unsafe { BNPluginIsBeingUpdated(self.handle) }
+    }
+
+    pub fn is_being_deleted(&self) -> bool {
+        unsafe { BNPluginIsBeingDeleted(self.handle) }
+    }
+
+    pub fn are_dependencies_being_installed(&self) -> bool {
+        unsafe { BNPluginAreDependenciesBeingInstalled(self.handle) }
+    }
+
+    pub fn install(&self) -> bool {
+        unsafe { BNPluginInstall(self.handle) }
+    }
+
+    /// Install the python dependencies of the plugin, see [RepoPlugin::dependencies]
+    pub fn install_dependencies(&self) -> bool {
+        unsafe { BNPluginInstallDependencies(self.handle) }
+    }
+
+    pub fn uninstall(&self) -> bool {
+        unsafe { BNPluginUninstall(self.handle) }
+    }
+
+    pub fn update(&self) -> bool {
+        unsafe { BNPluginUpdate(self.handle) }
+    }
+
+    /// Enable the plugin, `force` enables it even if it does not support the current platform
+    /// or version of Binary Ninja
+    pub fn enable(&self, force: bool) -> bool {
+        unsafe { BNPluginEnable(self.handle, force) }
+    }
+
+    pub fn disable(&self) -> bool {
+        unsafe { BNPluginDisable(self.handle) }
+    }
+}
+
+impl fmt::Debug for RepoPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<plugin '{}' {}>", self.path(), self.version())
+    }
+}
+
+impl ToOwned for RepoPlugin {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for RepoPlugin {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewPluginReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreePlugin(handle.handle);
+    }
+}
+
+impl CoreArrayProvider for RepoPlugin {
+    type Raw = *mut BNRepoPlugin;
+    type Context = ();
+    type Wrapped<'a> = Guard<'a, RepoPlugin>;
+}
+
+unsafe impl CoreArrayProviderInner for RepoPlugin {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeRepositoryPluginList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Guard::new(RepoPlugin { handle: *raw }, context)
+    }
+}
+
+unsafe fn version_info_from_raw(info: BNVersionInfo) -> VersionInfo {
+    let channel = if info.channel.is_null() {
+        BnString::new("")
+    } else {
+        BnString::from_raw(info.channel)
+    };
+    VersionInfo {
+        major: info.major,
+        minor: info.minor,
+        build: info.build,
+        channel,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    const PLUGINS_JSON: &str = r#"[
+    {
+        "path": "test_plugin",
+        "name": "Test Plugin",
+        "version": "1.2.3",
+        "author": "Vector 35",
+        "description": "Plugin of the local test repository",
+        "longdescription": "Nothing to see here",
+        "license": {"name": "MIT", "text": "MIT License"},
+        "api": ["python3"],
+        "platforms": ["Darwin", "Linux", "Windows"],
+        "type": ["helper"],
+        "installinstructions": {"Darwin": "", "Linux": "", "Windows": ""},
+        "dependencies": "",
+        "minimumbinaryninjaversion": 0,
+        "pluginmetadataversion": 2,
+        "projectUrl": "",
+        "packageUrl": "",
+        "authorUrl": "",
+        "commit": "",
+        "lastUpdated": 1700000000
+    }
+]"#;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@localhost"])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    /// Files created by the test, removed when it ends
+    struct TempPaths(Vec<PathBuf>);
+
+    impl Drop for TempPaths {
+        fn drop(&mut self) {
+            for path in &self.0 {
+                if path.is_dir() {
+                    let _ = fs::remove_dir_all(path);
+                } else {
+                    let _ = fs::remove_file(path);
+                }
+            }
+        }
+    }
+
+    fn has_git() -> bool {
+        Command::new("git")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    /// Create a git repository with a single plugin in `repo_dir`
+    fn local_repository(repo_dir: &Path) -> String {
+        fs::create_dir_all(repo_dir.join("test_plugin")).unwrap();
+        fs::write(repo_dir.join("plugins.json"), PLUGINS_JSON).unwrap();
+        fs::write(repo_dir.join("test_plugin").join("__init__.py"), "").unwrap();
+        git(repo_dir, &["init", "-q"]);
+        git(repo_dir, &["add", "-A"]);
+        git(repo_dir, &["commit", "-q", "-m", "test plugin"]);
+        format!("file://{}", repo_dir.to_str().unwrap())
+    }
+
+    #[test]
+    fn local_repository_plugins() {
+        if !has_git() {
+            eprintln!("skipping local_repository_plugins: git is not available");
+            return;
+        }
+        crate::headless::init();
+
+        let unique_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        let repo_path = format!("test_repository_{unique_id}");
+        let repo_dir = std::env::temp_dir().join(&repo_path);
+        let enabled_plugins =
+            std::env::temp_dir().join(format!("enabled_plugins_{unique_id}.json"));
+        let mut temp_paths = TempPaths(vec![repo_dir.clone(), enabled_plugins.clone()]);
+        let url = local_repository(&repo_dir);
+
+        let manager = RepositoryManager::new(enabled_plugins.to_str().unwrap());
+        assert!(manager.add_repository(url.as_str(), repo_path.as_str()));
+        manager.check_for_updates();
+
+        let repo = manager.repository_by_path(repo_path.as_str()).unwrap();
+        // the clone of the repository made by the manager
+        let clone_dir = PathBuf::from(repo.full_path().as_str());
+        if clone_dir.ends_with(&repo_path) {
+            temp_paths.0.push(clone_dir);
+        }
+        assert_eq!(repo.url().as_str(), url);
+        assert_eq!(repo.path().as_str(), repo_path);
+        assert!(manager
+            .repositories()
+            .iter()
+            .any(|r| r.path().as_str() == repo_path));
+
+        let plugins = repo.plugins();
+        assert_eq!(plugins.len(), 1);
+        let plugin = repo.plugin_by_path("test_plugin").unwrap();
+        assert_eq!(plugin.name().as_str(), "Test Plugin");
+        assert_eq!(plugin.version().as_str(), "1.2.3");
+        assert_eq!(plugin.author().as_str(), "Vector 35");
+        assert_eq!(plugin.license_text().as_str(), "MIT License");
+        assert_eq!(plugin.last_update(), 1700000000);
+        assert_eq!(plugin.plugin_types(), vec![PluginType::HelperPluginType]);
+        let apis = plugin.apis();
+        assert_eq!(
+            apis.iter().map(|api| api.to_string()).collect::<Vec<_>>(),
+            ["python3"]
+        );
+        assert_eq!(plugin.install_platforms().len(), 3);
+        assert!(!plugin.is_installed());
+        assert!(!plugin.is_enabled());
+    }
+}
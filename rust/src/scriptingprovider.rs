@@ -0,0 +1,712 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scripting languages available in the scripting console, eg: Python.
+//!
+//! A new language is added by implementing [ScriptingProvider] and [ScriptingInstance], and
+//! registering it with [CoreScriptingProvider::register]. Each console owns an instance, created
+//! by the provider, which is told about the current view, function and address as they change.
+//!
+//! ```no_run
+//! use binaryninja::scriptingprovider::*;
+//!
+//! struct EchoProvider;
+//! struct EchoInstance;
+//!
+//! impl ScriptingProvider for EchoProvider {
+//!     type Instance = EchoInstance;
+//!
+//!     fn create_instance(&self) -> Option<EchoInstance> {
+//!         Some(EchoInstance)
+//!     }
+//! }
+//!
+//! impl ScriptingInstance for EchoInstance {
+//!     fn execute_script_input(
+//!         &self,
+//!         instance: &CoreScriptingInstance,
+//!         input: &str,
+//!     ) -> ScriptingProviderExecuteResult {
+//!         instance.notify_output(format!("{input}\n"));
+//!         ScriptingProviderExecuteResult::SuccessfulScriptExecution
+//!     }
+//! }
+//!
+//! #[no_mangle]
+//! pub extern "C" fn CorePluginInit() -> bool {
+//!     CoreScriptingProvider::register("Echo", "echo", EchoProvider);
+//!     true
+//! }
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNScriptingProviderExecuteResult as ScriptingProviderExecuteResult;
+pub use binaryninjacore_sys::BNScriptingProviderInputReadyState as ScriptingProviderInputReadyState;
+
+use std::ffi::{c_char, c_void};
+use std::fmt;
+use std::ptr;
+
+use crate::basicblock::BasicBlock;
+use crate::binaryview::BinaryView;
+use crate::function::{Function, NativeBlock};
+use crate::rc::*;
+use crate::string::*;
+
+/// A scripting language, creating an instance for each console
+pub trait ScriptingProvider: 'static + Sync {
+    type Instance: ScriptingInstance;
+
+    fn create_instance(&self) -> Option<Self::Instance>;
+
+    /// Load the module at `module` of the plugin repository at `repository`
+    fn load_module(&self, _repository: &str, _module: &str, _force: bool) -> bool {
+        false
+    }
+
+    /// Install the dependencies listed in `modules`, in the format of a `requirements.txt`
+    fn install_modules(&self, _modules: &str) -> bool {
+        false
+    }
+}
+
+/// The state of a console of a [ScriptingProvider]
+///
+/// Text is sent back to the console through the `instance` handle passed to the execute methods,
+/// see [CoreScriptingInstance::notify_output].
+pub trait ScriptingInstance: 'static + Sync {
+    fn execute_script_input(
+        &self,
+        instance: &CoreScriptingInstance,
+        input: &str,
+    ) -> ScriptingProviderExecuteResult;
+
+    fn execute_script_input_from_filename(
+        &self,
+        instance: &CoreScriptingInstance,
+        filename: &str,
+    ) -> ScriptingProviderExecuteResult {
+        match std::fs::read_to_string(filename) {
+            Ok(input) => self.execute_script_input(instance, &input),
+            Err(e) => {
+                instance.notify_error(format!("Failed to read {filename}: {e}\n"));
+                ScriptingProviderExecuteResult::InvalidScriptInput
+            }
+        }
+    }
+
+    fn cancel_script_input(&self) {}
+
+    /// `view` is being closed, references to it must be dropped
+    fn release_binary_view(&self, _view: &BinaryView) {}
+
+    fn set_current_binary_view(&self, _view: Option<&BinaryView>) {}
+
+    fn set_current_function(&self, _function: Option<&Function>) {}
+
+    fn set_current_basic_block(&self, _block: Option<&BasicBlock<NativeBlock>>) {}
+
+    fn set_current_address(&self, _addr: u64) {}
+
+    fn set_current_selection(&self, _begin: u64, _end: u64) {}
+
+    /// Completion of the input `text`, `state` is the number of times completion was requested
+    /// for this input
+    fn complete_input(&self, _text: &str, _state: u64) -> String {
+        String::new()
+    }
+
+    fn stop(&self) {}
+}
+
+/// Receives the text produced by a [CoreScriptingInstance], see
+/// [CoreScriptingInstance::register_output_listener]
+pub trait ScriptingOutputListener: 'static + Sync {
+    fn output(&self, _text: &str) {}
+
+    fn warning(&self, _text: &str) {}
+
+    fn error(&self, _text: &str) {}
+
+    fn input_ready_state_changed(&self, _state: ScriptingProviderInputReadyState) {}
+}
+
+/// A scripting provider registered with the core
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CoreScriptingProvider {
+    handle: *mut BNScriptingProvider,
+}
+
+unsafe impl Send for CoreScriptingProvider {}
+unsafe impl Sync for CoreScriptingProvider {}
+
+impl CoreScriptingProvider {
+    pub(crate) unsafe fn from_raw(handle: *mut BNScriptingProvider) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// List of all registered scripting providers
+    pub fn list() -> Array<CoreScriptingProvider> {
+        let mut count = 0;
+        let providers = unsafe { BNGetScriptingProviderList(&mut count) };
+        assert!(!providers.is_null());
+        unsafe { Array::new(providers, count, ()) }
+    }
+
+    /// Retrieve a scripting provider by its displayed name, eg: `Python`
+    pub fn by_name<S: BnStrCompatible>(name: S) -> Option<CoreScriptingProvider> {
+        let name = name.into_bytes_with_nul();
+        let provider =
+            unsafe { BNGetScriptingProviderByName(name.as_ref().as_ptr() as *const c_char) };
+        (!provider.is_null()).then(|| unsafe { Self::from_raw(provider) })
+    }
+
+    /// Retrieve a scripting provider by the name of its API, eg: `python3`
+    pub fn by_api_name<S: BnStrCompatible>(name: S) -> Option<CoreScriptingProvider> {
+        let name = name.into_bytes_with_nul();
+        let provider =
+            unsafe { BNGetScriptingProviderByAPIName(name.as_ref().as_ptr() as *const c_char) };
+        (!provider.is_null()).then(|| unsafe { Self::from_raw(provider) })
+    }
+
+    /// Register a new scripting provider, making it available in the scripting console.
+    pub fn register<S, A, T>(name: S, api_name: A, provider: T) -> CoreScriptingProvider
+    where
+        S: BnStrCompatible,
+        A: BnStrCompatible,
+        T: ScriptingProvider,
+    {
+        let name = name.into_bytes_with_nul();
+        let api_name = api_name.into_bytes_with_nul();
+        let ctxt = Box::into_raw(Box::new(ProviderContext {
+            handle: ptr::null_mut(),
+            provider,
+        }));
+
+        let mut callbacks = BNScriptingProviderCallbacks {
+            context: ctxt as *mut c_void,
+            createInstance: Some(cb_create_instance::<T>),
+            loadModule: Some(cb_load_module::<T>),
+            installModules: Some(cb_install_modules::<T>),
+        };
+
+        unsafe {
+            let handle = BNRegisterScriptingProvider(
+                name.as_ref().as_ptr() as *const c_char,
+                api_name.as_ref().as_ptr() as *const c_char,
+                &mut callbacks,
+            );
+            (*ctxt).handle = handle;
+            Self::from_raw(handle)
+        }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetScriptingProviderName(self.handle)) }
+    }
+
+    pub fn api_name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetScriptingProviderAPIName(self.handle)) }
+    }
+
+    /// Create a new instance of the language, as done when opening a console
+    pub fn create_instance(&self) -> Option<Ref<CoreScriptingInstance>> {
+        let instance = unsafe { BNCreateScriptingProviderInstance(self.handle) };
+        (!instance.is_null()).then(|| unsafe { CoreScriptingInstance::ref_from_raw(instance) })
+    }
+
+    pub fn load_module<R: BnStrCompatible, M: BnStrCompatible>(
+        &self,
+        repository: R,
+        module: M,
+        force: bool,
+    ) -> bool {
+        let repository = repository.into_bytes_with_nul();
+        let module = module.into_bytes_with_nul();
+        unsafe {
+            BNLoadScriptingProviderModule(
+                self.handle,
+                repository.as_ref().as_ptr() as *const c_char,
+                module.as_ref().as_ptr() as *const c_char,
+                force,
+            )
+        }
+    }
+
+    pub fn install_modules<S: BnStrCompatible>(&self, modules: S) -> bool {
+        let modules = modules.into_bytes_with_nul();
+        unsafe {
+            BNInstallScriptingProviderModules(
+                self.handle,
+                modules.as_ref().as_ptr() as *const c_char,
+            )
+        }
+    }
+}
+
+impl fmt::Debug for CoreScriptingProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<scripting provider '{}'>", self.name())
+    }
+}
+
+impl CoreArrayProvider for CoreScriptingProvider {
+    type Raw = *mut BNScriptingProvider;
+    type Context = ();
+    type Wrapped<'a> = CoreScriptingProvider;
+}
+
+unsafe impl CoreArrayProviderInner for CoreScriptingProvider {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeScriptingProviderList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::from_raw(*raw)
+    }
+}
+
+/// An instance of a scripting language, either created by the core or by a [ScriptingProvider]
+/// registered from Rust
+#[derive(PartialEq, Eq, Hash)]
+pub struct CoreScriptingInstance {
+    handle: *mut BNScriptingInstance,
+}
+
+unsafe impl Send for CoreScriptingInstance {}
+unsafe impl Sync for CoreScriptingInstance {}
+
+impl CoreScriptingInstance {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNScriptingInstance) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    pub fn execute_script_input<S: BnStrCompatible>(
+        &self,
+        input: S,
+    ) -> ScriptingProviderExecuteResult {
+        let input = input.into_bytes_with_nul();
+        unsafe { BNExecuteScriptInput(self.handle, input.as_ref().as_ptr() as *const c_char) }
+    }
+
+    pub fn execute_script_input_from_filename<S: BnStrCompatible>(
+        &self,
+        filename: S,
+    ) -> ScriptingProviderExecuteResult {
+        let filename = filename.into_bytes_with_nul();
+        unsafe {
+            BNExecuteScriptInputFromFilename(
+                self.handle,
+                filename.as_ref().as_ptr() as *const c_char,
+            )
+        }
+    }
+
+    pub fn cancel_script_input(&self) {
+        unsafe { BNCancelScriptInput(self.handle) }
+    }
+
+    pub fn release_binary_view(&self, view: &BinaryView) {
+        unsafe { BNScriptingInstanceReleaseBinaryView(self.handle, view.handle) }
+    }
+
+    pub fn set_current_binary_view(&self, view: Option<&BinaryView>) {
+        let view = view.map(|view| view.handle).unwrap_or(ptr::null_mut());
+        unsafe { BNSetScriptingInstanceCurrentBinaryView(self.handle, view) }
+    }
+
+    pub fn set_current_function(&self, function: Option<&Function>) {
+        let function = function.map(|func| func.handle).unwrap_or(ptr::null_mut());
+        unsafe { BNSetScriptingInstanceCurrentFunction(self.handle, function) }
+    }
+
+    pub fn set_current_basic_block(&self, block: Option<&BasicBlock<NativeBlock>>) {
+        let block = block.map(|block| block.handle).unwrap_or(ptr::null_mut());
+        unsafe { BNSetScriptingInstanceCurrentBasicBlock(self.handle, block) }
+    }
+
+    pub fn set_current_address(&self, addr: u64) {
+        unsafe { BNSetScriptingInstanceCurrentAddress(self.handle, addr) }
+    }
+
+    pub fn set_current_selection(&self, begin: u64, end: u64) {
+        unsafe { BNSetScriptingInstanceCurrentSelection(self.handle, begin, end) }
+    }
+
+    pub fn complete_input<S: BnStrCompatible>(&self, text: S, state: u64) -> BnString {
+        let text = text.into_bytes_with_nul();
+        unsafe {
+            BnString::from_raw(BNScriptingInstanceCompleteInput(
+                self.handle,
+                text.as_ref().as_ptr() as *const c_char,
+                state,
+            ))
+        }
+    }
+
+    pub fn stop(&self) {
+        unsafe { BNStopScriptingInstance(self.handle) }
+    }
+
+    /// Send `text` to the console and output listeners of the instance
+    pub fn notify_output<S: BnStrCompatible>(&self, text: S) {
+        let text = text.into_bytes_with_nul();
+        unsafe {
+            BNNotifyOutputForScriptingInstance(self.handle, text.as_ref().as_ptr() as *const c_char)
+        }
+    }
+
+    pub fn notify_warning<S: BnStrCompatible>(&self, text: S) {
+        let text = text.into_bytes_with_nul();
+        unsafe {
+            BNNotifyWarningForScriptingInstance(
+                self.handle,
+                text.as_ref().as_ptr() as *const c_char,
+            )
+        }
+    }
+
+    pub fn notify_error<S: BnStrCompatible>(&self, text: S) {
+        let text = text.into_bytes_with_nul();
+        unsafe {
+            BNNotifyErrorForScriptingInstance(self.handle, text.as_ref().as_ptr() as *const c_char)
+        }
+    }
+
+    pub fn input_ready_state(&self) -> ScriptingProviderInputReadyState {
+        unsafe { BNGetScriptingInstanceInputReadyState(self.handle) }
+    }
+
+    pub fn set_input_ready_state(&self, state: ScriptingProviderInputReadyState) {
+        unsafe { BNNotifyInputReadyStateForScriptingInstance(self.handle, state) }
+    }
+
+    /// Characters delimiting the words completed by [CoreScriptingInstance::complete_input]
+    pub fn delimiters(&self) -> String {
+        unsafe { str_from_raw(BNGetScriptingInstanceDelimiters(self.handle)).into_owned() }
+    }
+
+    pub fn set_delimiters<S: BnStrCompatible>(&self, delimiters: S) {
+        let delimiters = delimiters.into_bytes_with_nul();
+        unsafe {
+            BNSetScriptingInstanceDelimiters(
+                self.handle,
+                delimiters.as_ref().as_ptr() as *const c_char,
+            )
+        }
+    }
+
+    /// Register `listener` to receive the output of the instance, until the returned guard is
+    /// dropped
+    pub fn register_output_listener<L: ScriptingOutputListener>(
+        &self,
+        listener: L,
+    ) -> ScriptingOutputListenerGuard<L> {
+        let ctxt = Box::into_raw(Box::new(listener));
+        let mut callbacks = output_listener_callbacks(ctxt);
+        unsafe { BNRegisterScriptingInstanceOutputListener(self.handle, &mut callbacks) };
+
+        ScriptingOutputListenerGuard {
+            instance: self.to_owned(),
+            ctxt,
+        }
+    }
+}
+
+impl ToOwned for CoreScriptingInstance {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for CoreScriptingInstance {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewScriptingInstanceReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeScriptingInstance(handle.handle);
+    }
+}
+
+/// Unregisters the [ScriptingOutputListener] when dropped
+pub struct ScriptingOutputListenerGuard<L: ScriptingOutputListener> {
+    instance: Ref<CoreScriptingInstance>,
+    ctxt: *mut L,
+}
+
+impl<L: ScriptingOutputListener> Drop for ScriptingOutputListenerGuard<L> {
+    fn drop(&mut self) {
+        let mut callbacks = output_listener_callbacks(self.ctxt);
+        unsafe {
+            BNUnregisterScriptingInstanceOutputListener(self.instance.handle, &mut callbacks);
+            let _listener = Box::from_raw(self.ctxt);
+        }
+    }
+}
+
+fn output_listener_callbacks<L: ScriptingOutputListener>(
+    ctxt: *mut L,
+) -> BNScriptingOutputListener {
+    BNScriptingOutputListener {
+        context: ctxt as *mut c_void,
+        output: Some(cb_output::<L>),
+        warning: Some(cb_warning::<L>),
+        error: Some(cb_error::<L>),
+        inputReadyStateChanged: Some(cb_input_ready_state_changed::<L>),
+    }
+}
+
+struct ProviderContext<T: ScriptingProvider> {
+    handle: *mut BNScriptingProvider,
+    provider: T,
+}
+
+struct InstanceContext<I: ScriptingInstance> {
+    handle: *mut BNScriptingInstance,
+    instance: I,
+}
+
+extern "C" fn cb_create_instance<T: ScriptingProvider>(
+    ctxt: *mut c_void,
+) -> *mut BNScriptingInstance {
+    ffi_wrap!("ScriptingProvider::create_instance", unsafe {
+        let ctxt = &*(ctxt as *const ProviderContext<T>);
+        let Some(instance) = ctxt.provider.create_instance() else {
+            return ptr::null_mut();
+        };
+
+        let instance_ctxt = Box::into_raw(Box::new(InstanceContext {
+            handle: ptr::null_mut(),
+            instance,
+        }));
+        let mut callbacks = BNScriptingInstanceCallbacks {
+            context: instance_ctxt as *mut c_void,
+            destroyInstance: Some(cb_destroy_instance::<T::Instance>),
+            externalRefTaken: None,
+            externalRefReleased: None,
+            executeScriptInput: Some(cb_execute_script_input::<T::Instance>),
+            executeScriptInputFromFilename: Some(
+                cb_execute_script_input_from_filename::<T::Instance>,
+            ),
+            cancelScriptInput: Some(cb_cancel_script_input::<T::Instance>),
+            releaseBinaryView: Some(cb_release_binary_view::<T::Instance>),
+            setCurrentBinaryView: Some(cb_set_current_binary_view::<T::Instance>),
+            setCurrentFunction: Some(cb_set_current_function::<T::Instance>),
+            setCurrentBasicBlock: Some(cb_set_current_basic_block::<T::Instance>),
+            setCurrentAddress: Some(cb_set_current_address::<T::Instance>),
+            setCurrentSelection: Some(cb_set_current_selection::<T::Instance>),
+            completeInput: Some(cb_complete_input::<T::Instance>),
+            stop: Some(cb_stop::<T::Instance>),
+        };
+
+        // The reference returned by the core is handed over to the caller, the context is
+        // freed by `destroyInstance` once the last reference is released
+        let handle = BNInitScriptingInstance(ctxt.handle, &mut callbacks);
+        (*instance_ctxt).handle = handle;
+        handle
+    })
+}
+
+extern "C" fn cb_load_module<T: ScriptingProvider>(
+    ctxt: *mut c_void,
+    repository: *const c_char,
+    module: *const c_char,
+    force: bool,
+) -> bool {
+    ffi_wrap!("ScriptingProvider::load_module", unsafe {
+        let ctxt = &*(ctxt as *const ProviderContext<T>);
+        ctxt.provider
+            .load_module(&str_from_raw(repository), &str_from_raw(module), force)
+    })
+}
+
+extern "C" fn cb_install_modules<T: ScriptingProvider>(
+    ctxt: *mut c_void,
+    modules: *const c_char,
+) -> bool {
+    ffi_wrap!("ScriptingProvider::install_modules", unsafe {
+        let ctxt = &*(ctxt as *const ProviderContext<T>);
+        ctxt.provider.install_modules(&str_from_raw(modules))
+    })
+}
+
+extern "C" fn cb_destroy_instance<I: ScriptingInstance>(ctxt: *mut c_void) {
+    ffi_wrap!("ScriptingInstance::destroy", unsafe {
+        drop(Box::from_raw(ctxt as *mut InstanceContext<I>));
+    })
+}
+
+extern "C" fn cb_execute_script_input<I: ScriptingInstance>(
+    ctxt: *mut c_void,
+    input: *const c_char,
+) -> ScriptingProviderExecuteResult {
+    ffi_wrap!("ScriptingInstance::execute_script_input", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        let instance = CoreScriptingInstance {
+            handle: ctxt.handle,
+        };
+        ctxt.instance
+            .execute_script_input(&instance, &str_from_raw(input))
+    })
+}
+
+extern "C" fn cb_execute_script_input_from_filename<I: ScriptingInstance>(
+    ctxt: *mut c_void,
+    filename: *const c_char,
+) -> ScriptingProviderExecuteResult {
+    ffi_wrap!(
+        "ScriptingInstance::execute_script_input_from_filename",
+        unsafe {
+            let ctxt = &*(ctxt as *const InstanceContext<I>);
+            let instance = CoreScriptingInstance {
+                handle: ctxt.handle,
+            };
+            ctxt.instance
+                .execute_script_input_from_filename(&instance, &str_from_raw(filename))
+        }
+    )
+}
+
+extern "C" fn cb_cancel_script_input<I: ScriptingInstance>(ctxt: *mut c_void) {
+    ffi_wrap!("ScriptingInstance::cancel_script_input", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        ctxt.instance.cancel_script_input()
+    })
+}
+
+extern "C" fn cb_release_binary_view<I: ScriptingInstance>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+) {
+    ffi_wrap!("ScriptingInstance::release_binary_view", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        if !view.is_null() {
+            ctxt.instance
+                .release_binary_view(&BinaryView { handle: view })
+        }
+    })
+}
+
+extern "C" fn cb_set_current_binary_view<I: ScriptingInstance>(
+    ctxt: *mut c_void,
+    view: *mut BNBinaryView,
+) {
+    ffi_wrap!("ScriptingInstance::set_current_binary_view", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        let view = (!view.is_null()).then_some(BinaryView { handle: view });
+        ctxt.instance.set_current_binary_view(view.as_ref())
+    })
+}
+
+extern "C" fn cb_set_current_function<I: ScriptingInstance>(
+    ctxt: *mut c_void,
+    func: *mut BNFunction,
+) {
+    ffi_wrap!("ScriptingInstance::set_current_function", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        let func = (!func.is_null()).then_some(Function { handle: func });
+        ctxt.instance.set_current_function(func.as_ref())
+    })
+}
+
+extern "C" fn cb_set_current_basic_block<I: ScriptingInstance>(
+    ctxt: *mut c_void,
+    block: *mut BNBasicBlock,
+) {
+    ffi_wrap!("ScriptingInstance::set_current_basic_block", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        let block = (!block.is_null()).then(|| BasicBlock::from_raw(block, NativeBlock::new()));
+        ctxt.instance.set_current_basic_block(block.as_ref())
+    })
+}
+
+extern "C" fn cb_set_current_address<I: ScriptingInstance>(ctxt: *mut c_void, addr: u64) {
+    ffi_wrap!("ScriptingInstance::set_current_address", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        ctxt.instance.set_current_address(addr)
+    })
+}
+
+extern "C" fn cb_set_current_selection<I: ScriptingInstance>(
+    ctxt: *mut c_void,
+    begin: u64,
+    end: u64,
+) {
+    ffi_wrap!("ScriptingInstance::set_current_selection", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        ctxt.instance.set_current_selection(begin, end)
+    })
+}
+
+extern "C" fn cb_complete_input<I: ScriptingInstance>(
+    ctxt: *mut c_void,
+    text: *const c_char,
+    state: u64,
+) -> *mut c_char {
+    ffi_wrap!("ScriptingInstance::complete_input", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        let completed = ctxt.instance.complete_input(&str_from_raw(text), state);
+        BnString::new(completed).into_raw()
+    })
+}
+
+extern "C" fn cb_stop<I: ScriptingInstance>(ctxt: *mut c_void) {
+    ffi_wrap!("ScriptingInstance::stop", unsafe {
+        let ctxt = &*(ctxt as *const InstanceContext<I>);
+        ctxt.instance.stop()
+    })
+}
+
+extern "C" fn cb_output<L: ScriptingOutputListener>(ctxt: *mut c_void, text: *const c_char) {
+    ffi_wrap!("ScriptingOutputListener::output", unsafe {
+        let listener = &*(ctxt as *const L);
+        listener.output(&str_from_raw(text))
+    })
+}
+
+extern "C" fn cb_warning<L: ScriptingOutputListener>(ctxt: *mut c_void, text: *const c_char) {
+    ffi_wrap!("ScriptingOutputListener::warning", unsafe {
+        let listener = &*(ctxt as *const L);
+        listener.warning(&str_from_raw(text))
+    })
+}
+
+extern "C" fn cb_error<L: ScriptingOutputListener>(ctxt: *mut c_void, text: *const c_char) {
+    ffi_wrap!("ScriptingOutputListener::error", unsafe {
+        let listener = &*(ctxt as *const L);
+        listener.error(&str_from_raw(text))
+    })
+}
+
+extern "C" fn cb_input_ready_state_changed<L: ScriptingOutputListener>(
+    ctxt: *mut c_void,
+    state: ScriptingProviderInputReadyState,
+) {
+    ffi_wrap!(
+        "ScriptingOutputListener::input_ready_state_changed",
+        unsafe {
+            let listener = &*(ctxt as *const L);
+            listener.input_ready_state_changed(state)
+        }
+    )
+}
@@ -0,0 +1,409 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Searching a [BinaryView] for data, text, constants and byte patterns.
+//!
+//! The searches are started from the `find_*` methods of [BinaryViewExt], and are lazy: every
+//! call to [Iterator::next] searches for the next match only, so breaking out of the loop is
+//! enough to stop the search. Pattern searches are the exception, all their matches are
+//! collected by the first call to [Iterator::next], and can only be stopped from their progress
+//! callback, see [BinaryViewExt::find_pattern_with_progress].
+//!
+//! ```no_run
+//! # use binaryninja::binaryview::BinaryViewExt;
+//! # let bv = binaryninja::load("example").unwrap();
+//! for addr in bv.find_pattern(.., "55 48 89 e5 ?? 8? ec").unwrap() {
+//!     println!("prologue at {:#x}", addr);
+//! }
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNFindFlag as FindFlag;
+
+use std::ffi::{c_char, c_void};
+use std::ops::{Bound, RangeBounds};
+
+use crate::binaryview::{BinaryView, BinaryViewBase, BinaryViewExt};
+use crate::databuffer::DataBuffer;
+use crate::disassembly::DisassemblySettings;
+use crate::mlil::FunctionGraphType;
+use crate::rc::*;
+use crate::string::BnStrCompatible;
+
+pub(crate) enum Query {
+    Data(DataBuffer, FindFlag),
+    Text(
+        Vec<u8>,
+        Ref<DisassemblySettings>,
+        FindFlag,
+        FunctionGraphType,
+    ),
+    Constant(u64, Ref<DisassemblySettings>, FunctionGraphType),
+    Pattern(String, Option<PatternMask>),
+}
+
+/// Masked bytes a pattern match needs to be checked against, for masks that are not nibble aligned
+pub(crate) struct PatternMask {
+    data: Vec<u8>,
+    mask: Vec<u8>,
+}
+
+impl Query {
+    /// Build a query for `data`, fails if `data` is empty
+    pub(crate) fn data(data: &[u8], flags: FindFlag) -> Result<Self, ()> {
+        if data.is_empty() {
+            return Err(());
+        }
+        Ok(Self::Data(DataBuffer::new(data)?, flags))
+    }
+
+    pub(crate) fn text(
+        text: Vec<u8>,
+        settings: Option<&DisassemblySettings>,
+        flags: FindFlag,
+        graph_type: FunctionGraphType,
+    ) -> Self {
+        Self::Text(text, settings_or_default(settings), flags, graph_type)
+    }
+
+    pub(crate) fn constant(
+        constant: u64,
+        settings: Option<&DisassemblySettings>,
+        graph_type: FunctionGraphType,
+    ) -> Self {
+        Self::Constant(constant, settings_or_default(settings), graph_type)
+    }
+
+    /// Build a query from a hex string where `?` matches any nibble, whitespace is ignored
+    pub(crate) fn pattern(pattern: &str) -> Result<Self, ()> {
+        let pattern: String = pattern.chars().filter(|c| !c.is_whitespace()).collect();
+        if pattern.is_empty()
+            || pattern.len() & 1 == 1
+            || !pattern.chars().all(|c| c == '?' || c.is_ascii_hexdigit())
+        {
+            return Err(());
+        }
+        Ok(Self::Pattern(pattern, None))
+    }
+
+    /// Build a query matching the bytes for which `byte & mask == data & mask`
+    pub(crate) fn masked(data: &[u8], mask: &[u8]) -> Result<Self, ()> {
+        if data.is_empty() || data.len() != mask.len() {
+            return Err(());
+        }
+        let mut pattern = String::with_capacity(data.len() * 2);
+        let mut nibble_aligned = true;
+        for (byte, mask) in data.iter().zip(mask) {
+            for shift in [4, 0] {
+                let nibble_mask = (mask >> shift) & 0xf;
+                if nibble_mask == 0xf {
+                    pattern.push_str(&format!("{:x}", (byte >> shift) & 0xf));
+                } else {
+                    nibble_aligned &= nibble_mask == 0;
+                    pattern.push('?');
+                }
+            }
+        }
+        let check = (!nibble_aligned).then(|| PatternMask {
+            data: data.to_vec(),
+            mask: mask.to_vec(),
+        });
+        Ok(Self::Pattern(pattern, check))
+    }
+}
+
+fn settings_or_default(settings: Option<&DisassemblySettings>) -> Ref<DisassemblySettings> {
+    settings
+        .map(|s| s.to_owned())
+        .unwrap_or_else(DisassemblySettings::new)
+}
+
+/// Lazy iterator over the addresses matching a search, see [BinaryViewExt::find_data],
+/// [BinaryViewExt::find_text], [BinaryViewExt::find_constant] and [BinaryViewExt::find_pattern].
+///
+/// The search stops if the progress callback returns `false`.
+pub struct FindMatches<F = fn(usize, usize) -> bool>
+where
+    F: FnMut(usize, usize) -> bool,
+{
+    view: Ref<BinaryView>,
+    query: Query,
+    next: u64,
+    end: u64,
+    progress: F,
+    finished: bool,
+    pattern_matches: Option<std::vec::IntoIter<u64>>,
+}
+
+impl FindMatches {
+    pub(crate) fn new<R: RangeBounds<u64>>(view: &BinaryView, range: R, query: Query) -> Self {
+        fn no_progress(_progress: usize, _total: usize) -> bool {
+            true
+        }
+        Self::with_progress(view, range, query, no_progress)
+    }
+}
+
+impl<F: FnMut(usize, usize) -> bool> FindMatches<F> {
+    pub(crate) fn with_progress<R: RangeBounds<u64>>(
+        view: &BinaryView,
+        range: R,
+        query: Query,
+        progress: F,
+    ) -> Self {
+        let next = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.saturating_add(1),
+            Bound::Unbounded => view.start(),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.saturating_add(1),
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => view.end(),
+        };
+        Self {
+            view: view.to_owned(),
+            query,
+            next,
+            end,
+            progress,
+            finished: false,
+            pattern_matches: None,
+        }
+    }
+
+    fn find_next(&mut self) -> Option<u64> {
+        let view = self.view.handle;
+        let progress_ctxt = &mut self.progress as *mut F as *mut c_void;
+        let mut result = 0;
+        let found = match &self.query {
+            Query::Data(data, flags) => unsafe {
+                BNFindNextDataWithProgress(
+                    view,
+                    self.next,
+                    self.end,
+                    data.as_raw(),
+                    &mut result,
+                    *flags,
+                    progress_ctxt,
+                    Some(cb_progress::<F>),
+                )
+            },
+            Query::Text(text, settings, flags, graph_type) => unsafe {
+                BNFindNextTextWithProgress(
+                    view,
+                    self.next,
+                    self.end,
+                    text.as_ptr() as *const c_char,
+                    &mut result,
+                    settings.handle,
+                    *flags,
+                    *graph_type,
+                    progress_ctxt,
+                    Some(cb_progress::<F>),
+                )
+            },
+            Query::Constant(constant, settings, graph_type) => unsafe {
+                BNFindNextConstantWithProgress(
+                    view,
+                    self.next,
+                    self.end,
+                    *constant,
+                    &mut result,
+                    settings.handle,
+                    *graph_type,
+                    progress_ctxt,
+                    Some(cb_progress::<F>),
+                )
+            },
+            Query::Pattern(pattern, check) => {
+                if self.pattern_matches.is_none() {
+                    let matches: Vec<u64> = self
+                        .search_pattern(pattern, progress_ctxt)
+                        .into_iter()
+                        .filter(|&addr| match check {
+                            Some(check) => self.check_mask(check, addr),
+                            None => true,
+                        })
+                        .collect();
+                    self.pattern_matches = Some(matches.into_iter());
+                }
+                match self.pattern_matches.as_mut().and_then(Iterator::next) {
+                    Some(addr) => {
+                        result = addr;
+                        true
+                    }
+                    None => false,
+                }
+            }
+        };
+        (found && result < self.end).then_some(result)
+    }
+
+    /// Collect all the addresses matching `pattern` in the remaining range, in one search
+    ///
+    /// The search has no progress of its own, the progress callback is called with the offset of
+    /// every match in the range, and stops the search when it returns `false`.
+    fn search_pattern(&self, pattern: &str, progress_ctxt: *mut c_void) -> Vec<u64> {
+        struct SearchContext<F> {
+            matches: Vec<u64>,
+            progress: *mut F,
+            start: u64,
+            end: u64,
+        }
+
+        unsafe extern "C" fn cb_match<F: FnMut(usize, usize) -> bool>(
+            ctxt: *mut c_void,
+            addr: u64,
+            data: *mut BNDataBuffer,
+        ) -> bool {
+            ffi_wrap!("FindMatches::search_pattern", {
+                // the matched data is owned by the callback
+                if !data.is_null() {
+                    drop(DataBuffer::from_raw(data));
+                }
+                let ctxt = &mut *(ctxt as *mut SearchContext<F>);
+                ctxt.matches.push(addr);
+                let progress = addr.saturating_sub(ctxt.start) as usize;
+                (*ctxt.progress)(progress, (ctxt.end - ctxt.start) as usize)
+            })
+        }
+
+        if self.next >= self.end {
+            return vec![];
+        }
+        // the end of the search query is inclusive
+        let query = format!(
+            r#"{{"pattern":"{}","start":{},"end":{},"raw":false,"ignoreCase":false,"overlap":true,"align":1}}"#,
+            pattern,
+            self.next,
+            self.end - 1
+        );
+        let query = query.into_bytes_with_nul();
+        let mut ctxt = SearchContext {
+            matches: Vec::new(),
+            progress: progress_ctxt as *mut F,
+            start: self.next,
+            end: self.end,
+        };
+        unsafe {
+            BNSearch(
+                self.view.handle,
+                query.as_ptr() as *const c_char,
+                &mut ctxt as *mut SearchContext<F> as *mut c_void,
+                Some(cb_match::<F>),
+            )
+        };
+        ctxt.matches
+    }
+
+    fn check_mask(&self, check: &PatternMask, addr: u64) -> bool {
+        let bytes = self.view.read_vec(addr, check.data.len());
+        bytes.len() == check.data.len()
+            && bytes
+                .iter()
+                .zip(check.data.iter().zip(&check.mask))
+                .all(|(byte, (data, mask))| byte & mask == data & mask)
+    }
+}
+
+impl<F: FnMut(usize, usize) -> bool> Iterator for FindMatches<F> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished || self.next >= self.end {
+            return None;
+        }
+        match self.find_next() {
+            Some(addr) => {
+                self.next = addr + 1;
+                Some(addr)
+            }
+            None => {
+                self.finished = true;
+                None
+            }
+        }
+    }
+}
+
+impl<F: FnMut(usize, usize) -> bool> std::iter::FusedIterator for FindMatches<F> {}
+
+unsafe extern "C" fn cb_progress<F: FnMut(usize, usize) -> bool>(
+    ctxt: *mut c_void,
+    progress: usize,
+    total: usize,
+) -> bool {
+    ffi_wrap!("FindMatches::progress", {
+        let closure = &mut *(ctxt as *mut F);
+        closure(progress, total)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FindFlag, Query};
+
+    fn pattern_of(query: Query) -> (String, Option<(Vec<u8>, Vec<u8>)>) {
+        match query {
+            Query::Pattern(pattern, check) => {
+                (pattern, check.map(|check| (check.data, check.mask)))
+            }
+            _ => panic!("not a pattern query"),
+        }
+    }
+
+    #[test]
+    fn pattern() {
+        let (pattern, check) = pattern_of(Query::pattern("55 48 89 e5 ?? 8?\tec").unwrap());
+        assert_eq!(pattern, "554889e5??8?ec");
+        assert!(check.is_none());
+
+        assert!(Query::pattern("").is_err());
+        assert!(Query::pattern("   ").is_err());
+        // odd number of nibbles
+        assert!(Query::pattern("55 4").is_err());
+        // not a hex digit
+        assert!(Query::pattern("55 4g").is_err());
+        assert!(Query::pattern("55 *8").is_err());
+    }
+
+    #[test]
+    fn masked() {
+        // nibble aligned masks are turned into wildcards
+        let query = Query::masked(&[0x55, 0x48, 0x89], &[0xff, 0x00, 0xf0]).unwrap();
+        let (pattern, check) = pattern_of(query);
+        assert_eq!(pattern, "55??8?");
+        assert!(check.is_none());
+
+        // other masks need the match to be checked against the masked bytes
+        let query = Query::masked(&[0x55, 0x48], &[0xff, 0x0c]).unwrap();
+        let (pattern, check) = pattern_of(query);
+        assert_eq!(pattern, "55??");
+        assert_eq!(check, Some((vec![0x55, 0x48], vec![0xff, 0x0c])));
+
+        assert!(Query::masked(&[], &[]).is_err());
+        assert!(Query::masked(&[0x55, 0x48], &[0xff]).is_err());
+    }
+
+    #[test]
+    fn data() {
+        assert!(Query::data(&[], FindFlag::FindCaseSensitive).is_err());
+        match Query::data(b"\x55\x48", FindFlag::FindCaseSensitive).unwrap() {
+            Query::Data(data, _flags) => assert_eq!(data.get_data(), b"\x55\x48"),
+            _ => panic!("not a data query"),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage for credentials, such as API tokens, kept out of the [Settings](crate::settings::Settings).
+//!
+//! Secrets are strings identified by a key, stored by one of the providers listed by
+//! [CoreSecretsProvider::list]. New providers, eg: backed by the keyring of the system, can be
+//! added by implementing [SecretsProvider] and calling [CoreSecretsProvider::register].
+//!
+//! ```no_run
+//! use binaryninja::secrets::CoreSecretsProvider;
+//!
+//! let provider = CoreSecretsProvider::list().get(0);
+//! if !provider.has_data("symbols.example.com") {
+//!     provider.store_data("symbols.example.com", "token");
+//! }
+//! let token = provider.get_data("symbols.example.com");
+//! ```
+
+use binaryninjacore_sys::*;
+
+use std::ffi::{c_char, c_void};
+use std::fmt;
+use std::ptr;
+
+use crate::rc::*;
+use crate::string::*;
+
+/// A store of secrets, identified by a key
+pub trait SecretsProvider: 'static + Sync {
+    fn has_data(&self, key: &str) -> bool;
+
+    /// The secret stored for `key`, [None] if there is none
+    fn get_data(&self, key: &str) -> Option<String>;
+
+    /// Store `data` for `key`, replacing the previous secret if any
+    fn store_data(&self, key: &str, data: &str) -> bool;
+
+    fn delete_data(&self, key: &str) -> bool;
+}
+
+/// A secrets provider registered with the core
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CoreSecretsProvider {
+    handle: *mut BNSecretsProvider,
+}
+
+unsafe impl Send for CoreSecretsProvider {}
+unsafe impl Sync for CoreSecretsProvider {}
+
+impl CoreSecretsProvider {
+    pub(crate) unsafe fn from_raw(handle: *mut BNSecretsProvider) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// List of all registered secrets providers
+    pub fn list() -> Array<CoreSecretsProvider> {
+        let mut count = 0;
+        let providers = unsafe { BNGetSecretsProviderList(&mut count) };
+        assert!(!providers.is_null());
+        unsafe { Array::new(providers, count, ()) }
+    }
+
+    /// Retrieve a secrets provider by its name
+    pub fn by_name<S: BnStrCompatible>(name: S) -> Option<CoreSecretsProvider> {
+        let name = name.into_bytes_with_nul();
+        let provider =
+            unsafe { BNGetSecretsProviderByName(name.as_ref().as_ptr() as *const c_char) };
+        (!provider.is_null()).then(|| unsafe { Self::from_raw(provider) })
+    }
+
+    /// Register a new secrets provider, making it available for every user of the core.
+    pub fn register<S, T>(name: S, provider: T) -> CoreSecretsProvider
+    where
+        S: BnStrCompatible,
+        T: SecretsProvider,
+    {
+        let name = name.into_bytes_with_nul();
+        let ctxt = Box::into_raw(Box::new(provider));
+
+        let mut callbacks = BNSecretsProviderCallbacks {
+            context: ctxt as *mut c_void,
+            hasData: Some(cb_has_data::<T>),
+            getData: Some(cb_get_data::<T>),
+            storeData: Some(cb_store_data::<T>),
+            deleteData: Some(cb_delete_data::<T>),
+        };
+
+        unsafe {
+            Self::from_raw(BNRegisterSecretsProvider(
+                name.as_ref().as_ptr() as *const c_char,
+                &mut callbacks,
+            ))
+        }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetSecretsProviderName(self.handle)) }
+    }
+
+    pub fn has_data<S: BnStrCompatible>(&self, key: S) -> bool {
+        let key = key.into_bytes_with_nul();
+        unsafe { BNSecretsProviderHasData(self.handle, key.as_ref().as_ptr() as *const c_char) }
+    }
+
+    /// The secret stored for `key`, [None] if there is none
+    pub fn get_data<S: BnStrCompatible>(&self, key: S) -> Option<BnString> {
+        let key = key.into_bytes_with_nul();
+        let data = unsafe {
+            BNGetSecretsProviderData(self.handle, key.as_ref().as_ptr() as *const c_char)
+        };
+        (!data.is_null()).then(|| unsafe { BnString::from_raw(data) })
+    }
+
+    /// Store `data` for `key`, replacing the previous secret if any
+    pub fn store_data<K: BnStrCompatible, D: BnStrCompatible>(&self, key: K, data: D) -> bool {
+        let key = key.into_bytes_with_nul();
+        let data = data.into_bytes_with_nul();
+        unsafe {
+            BNStoreSecretsProviderData(
+                self.handle,
+                key.as_ref().as_ptr() as *const c_char,
+                data.as_ref().as_ptr() as *const c_char,
+            )
+        }
+    }
+
+    pub fn delete_data<S: BnStrCompatible>(&self, key: S) -> bool {
+        let key = key.into_bytes_with_nul();
+        unsafe { BNDeleteSecretsProviderData(self.handle, key.as_ref().as_ptr() as *const c_char) }
+    }
+}
+
+impl fmt::Debug for CoreSecretsProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<secrets provider '{}'>", self.name())
+    }
+}
+
+impl CoreArrayProvider for CoreSecretsProvider {
+    type Raw = *mut BNSecretsProvider;
+    type Context = ();
+    type Wrapped<'a> = CoreSecretsProvider;
+}
+
+unsafe impl CoreArrayProviderInner for CoreSecretsProvider {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeSecretsProviderList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::from_raw(*raw)
+    }
+}
+
+extern "C" fn cb_has_data<T: SecretsProvider>(ctxt: *mut c_void, key: *const c_char) -> bool {
+    ffi_wrap!("SecretsProvider::has_data", unsafe {
+        let provider = &*(ctxt as *const T);
+        provider.has_data(&str_from_raw(key))
+    })
+}
+
+extern "C" fn cb_get_data<T: SecretsProvider>(
+    ctxt: *mut c_void,
+    key: *const c_char,
+) -> *mut c_char {
+    ffi_wrap!("SecretsProvider::get_data", unsafe {
+        let provider = &*(ctxt as *const T);
+        match provider.get_data(&str_from_raw(key)) {
+            Some(data) => BnString::new(data).into_raw(),
+            None => ptr::null_mut(),
+        }
+    })
+}
+
+extern "C" fn cb_store_data<T: SecretsProvider>(
+    ctxt: *mut c_void,
+    key: *const c_char,
+    data: *const c_char,
+) -> bool {
+    ffi_wrap!("SecretsProvider::store_data", unsafe {
+        let provider = &*(ctxt as *const T);
+        provider.store_data(&str_from_raw(key), &str_from_raw(data))
+    })
+}
+
+extern "C" fn cb_delete_data<T: SecretsProvider>(ctxt: *mut c_void, key: *const c_char) -> bool {
+    ffi_wrap!("SecretsProvider::delete_data", unsafe {
+        let provider = &*(ctxt as *const T);
+        provider.delete_data(&str_from_raw(key))
+    })
+}
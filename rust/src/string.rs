@@ -33,6 +33,14 @@ pub(crate) fn raw_to_string(ptr: *const raw::c_char) -> Option<String> {
     }
 }
 
+/// Borrow the string at `ptr`, empty if `ptr` is null
+pub(crate) unsafe fn str_from_raw<'a>(ptr: *const raw::c_char) -> Cow<'a, str> {
+    if ptr.is_null() {
+        return "".into();
+    }
+    CStr::from_ptr(ptr).to_string_lossy()
+}
+
 /// Is the quivalent of `core::ffi::CString` but using the allocation and free
 /// functions provided by binaryninja_sys.
 #[repr(transparent)]
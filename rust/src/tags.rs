@@ -200,19 +200,19 @@ pub struct TagReference {
     ref_type: TagReferenceType,
     auto_defined: bool,
     tag: Ref<Tag>,
-    arch: CoreArchitecture,
-    func: Ref<Function>,
+    arch: Option<CoreArchitecture>,
+    func: Option<Ref<Function>>,
     addr: u64,
 }
 
 impl TagReference {
-    unsafe fn from_borrowed_raw(value: &BNTagReference) -> Self {
+    pub(crate) unsafe fn from_borrowed_raw(value: &BNTagReference) -> Self {
         Self {
             ref_type: value.refType,
             auto_defined: value.autoDefined,
             tag: Tag { handle: value.tag }.to_owned(),
-            arch: CoreArchitecture::from_raw(value.arch),
-            func: Function { handle: value.func }.to_owned(),
+            arch: (!value.arch.is_null()).then(|| CoreArchitecture::from_raw(value.arch)),
+            func: (!value.func.is_null()).then(|| Function { handle: value.func }.to_owned()),
             addr: value.addr,
         }
     }
@@ -225,11 +225,13 @@ impl TagReference {
     pub fn tag(&self) -> &Tag {
         &self.tag
     }
-    pub fn arch(&self) -> CoreArchitecture {
+    /// The architecture of the tagged function, `None` for data tags
+    pub fn arch(&self) -> Option<CoreArchitecture> {
         self.arch
     }
-    pub fn functions(&self) -> &Function {
-        &self.func
+    /// The tagged function, `None` for data tags
+    pub fn functions(&self) -> Option<&Function> {
+        self.func.as_deref()
     }
     pub fn address(&self) -> u64 {
         self.addr
@@ -0,0 +1,501 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Encoders, decoders, compressors and hashes that operate on a [DataBuffer].
+//!
+//! Transforms registered with the core (eg: `Base64`, `XOR`, `Zlib`, `LZMA`) are listed by
+//! [Transform::list], new ones can be added by implementing [CustomTransform] and calling
+//! [Transform::register].
+//!
+//! ```no_run
+//! use binaryninja::databuffer::DataBuffer;
+//! use binaryninja::transform::Transform;
+//!
+//! let xor = Transform::by_name("XOR").unwrap();
+//! let key = DataBuffer::new(&[0x41]).unwrap();
+//! let input = DataBuffer::new(b"secret").unwrap();
+//! let output = xor.encode(&input, [("key", &key)]).unwrap();
+//! let decoded = xor.decode(&output, [("key", &key)]).unwrap();
+//! assert_eq!(decoded.get_data(), input.get_data());
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNTransformType as TransformType;
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CStr};
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+use crate::databuffer::DataBuffer;
+use crate::rc::*;
+use crate::string::*;
+
+/// Description of a parameter accepted by a [Transform]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TransformParameter {
+    /// Name used to pass the parameter to [Transform::encode] and [Transform::decode]
+    pub name: String,
+    /// Name displayed to the user
+    pub long_name: String,
+    /// Required length of the value, `0` for a variable length value
+    pub fixed_length: usize,
+}
+
+impl TransformParameter {
+    pub fn new<S: Into<String>, L: Into<String>>(
+        name: S,
+        long_name: L,
+        fixed_length: usize,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            long_name: long_name.into(),
+            fixed_length,
+        }
+    }
+
+    pub(crate) unsafe fn from_raw(value: &BNTransformParameterInfo) -> Self {
+        Self {
+            name: CStr::from_ptr(value.name).to_string_lossy().into_owned(),
+            long_name: CStr::from_ptr(value.longName)
+                .to_string_lossy()
+                .into_owned(),
+            fixed_length: value.fixedLength,
+        }
+    }
+
+    fn into_raw(self) -> BNTransformParameterInfo {
+        BNTransformParameterInfo {
+            name: BnString::new(self.name).into_raw(),
+            longName: BnString::new(self.long_name).into_raw(),
+            fixedLength: self.fixed_length,
+        }
+    }
+}
+
+impl CoreArrayProvider for TransformParameter {
+    type Raw = BNTransformParameterInfo;
+    type Context = ();
+    type Wrapped<'a> = TransformParameter;
+}
+
+unsafe impl CoreArrayProviderInner for TransformParameter {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeTransformParameterList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::from_raw(raw)
+    }
+}
+
+/// A transform registered with the core
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Transform {
+    handle: *mut BNTransform,
+}
+
+unsafe impl Send for Transform {}
+unsafe impl Sync for Transform {}
+
+impl Transform {
+    pub(crate) unsafe fn from_raw(handle: *mut BNTransform) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// List of all registered transforms
+    pub fn list() -> Array<Transform> {
+        let mut count = 0;
+        let transforms = unsafe { BNGetTransformTypeList(&mut count) };
+        assert!(!transforms.is_null());
+        unsafe { Array::new(transforms, count, ()) }
+    }
+
+    /// Retrieve a transform by its name
+    pub fn by_name<S: BnStrCompatible>(name: S) -> Option<Transform> {
+        let name = name.into_bytes_with_nul();
+        let transform = unsafe { BNGetTransformByName(name.as_ref().as_ptr() as *const c_char) };
+        (!transform.is_null()).then(|| unsafe { Self::from_raw(transform) })
+    }
+
+    /// Register a new transform, making it available for every user of the core.
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    /// use binaryninja::databuffer::DataBuffer;
+    /// use binaryninja::transform::{CustomTransform, Transform, TransformType};
+    ///
+    /// struct Reverse;
+    ///
+    /// impl CustomTransform for Reverse {
+    ///     fn decode(&self, input: &DataBuffer, _params: &HashMap<String, DataBuffer>) -> Result<DataBuffer, ()> {
+    ///         let mut data = input.get_data().to_vec();
+    ///         data.reverse();
+    ///         DataBuffer::new(&data)
+    ///     }
+    ///
+    ///     fn encode(&self, input: &DataBuffer, params: &HashMap<String, DataBuffer>) -> Result<DataBuffer, ()> {
+    ///         self.decode(input, params)
+    ///     }
+    /// }
+    ///
+    /// Transform::register(TransformType::InvertingTransform, "Reverse", "Reverse bytes", "Example", Reverse);
+    /// ```
+    pub fn register<N, L, G, T>(
+        transform_type: TransformType,
+        name: N,
+        long_name: L,
+        group: G,
+        transform: T,
+    ) -> Transform
+    where
+        N: BnStrCompatible,
+        L: BnStrCompatible,
+        G: BnStrCompatible,
+        T: CustomTransform,
+    {
+        extern "C" fn cb_get_parameters<T>(
+            ctxt: *mut c_void,
+            count: *mut usize,
+        ) -> *mut BNTransformParameterInfo
+        where
+            T: CustomTransform,
+        {
+            ffi_wrap!("CustomTransform::parameters", unsafe {
+                let transform = &*(ctxt as *const T);
+                let params: Box<[BNTransformParameterInfo]> = transform
+                    .parameters()
+                    .into_iter()
+                    .map(TransformParameter::into_raw)
+                    .collect();
+                *count = params.len();
+                Box::leak(params).as_mut_ptr()
+            })
+        }
+
+        extern "C" fn cb_free_parameters(params: *mut BNTransformParameterInfo, count: usize) {
+            ffi_wrap!("CustomTransform::free_parameters", unsafe {
+                let params = Box::from_raw(ptr::slice_from_raw_parts_mut(params, count));
+                for param in params.iter() {
+                    drop(BnString::from_raw(param.name));
+                    drop(BnString::from_raw(param.longName));
+                }
+            })
+        }
+
+        extern "C" fn cb_decode<T>(
+            ctxt: *mut c_void,
+            input: *mut BNDataBuffer,
+            output: *mut BNDataBuffer,
+            params: *mut BNTransformParameter,
+            param_count: usize,
+        ) -> bool
+        where
+            T: CustomTransform,
+        {
+            ffi_wrap!("CustomTransform::decode", unsafe {
+                let transform = &*(ctxt as *const T);
+                let input = ManuallyDrop::new(DataBuffer::from_raw(input));
+                let params = params_from_raw(params, param_count);
+                match transform.decode(&input, &params) {
+                    Ok(result) => {
+                        let mut output = ManuallyDrop::new(DataBuffer::from_raw(output));
+                        DataBuffer::assign(&mut output, &result);
+                        true
+                    }
+                    Err(()) => false,
+                }
+            })
+        }
+
+        extern "C" fn cb_encode<T>(
+            ctxt: *mut c_void,
+            input: *mut BNDataBuffer,
+            output: *mut BNDataBuffer,
+            params: *mut BNTransformParameter,
+            param_count: usize,
+        ) -> bool
+        where
+            T: CustomTransform,
+        {
+            ffi_wrap!("CustomTransform::encode", unsafe {
+                let transform = &*(ctxt as *const T);
+                let input = ManuallyDrop::new(DataBuffer::from_raw(input));
+                let params = params_from_raw(params, param_count);
+                match transform.encode(&input, &params) {
+                    Ok(result) => {
+                        let mut output = ManuallyDrop::new(DataBuffer::from_raw(output));
+                        DataBuffer::assign(&mut output, &result);
+                        true
+                    }
+                    Err(()) => false,
+                }
+            })
+        }
+
+        let name = name.into_bytes_with_nul();
+        let long_name = long_name.into_bytes_with_nul();
+        let group = group.into_bytes_with_nul();
+        let ctxt = Box::into_raw(Box::new(transform));
+
+        let mut callbacks = BNCustomTransform {
+            context: ctxt as *mut c_void,
+            getParameters: Some(cb_get_parameters::<T>),
+            freeParameters: Some(cb_free_parameters),
+            decode: Some(cb_decode::<T>),
+            encode: Some(cb_encode::<T>),
+        };
+
+        unsafe {
+            Self::from_raw(BNRegisterTransformType(
+                transform_type,
+                name.as_ref().as_ptr() as *const c_char,
+                long_name.as_ref().as_ptr() as *const c_char,
+                group.as_ref().as_ptr() as *const c_char,
+                &mut callbacks,
+            ))
+        }
+    }
+
+    pub fn transform_type(&self) -> TransformType {
+        unsafe { BNGetTransformType(self.handle) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTransformName(self.handle)) }
+    }
+
+    pub fn long_name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTransformLongName(self.handle)) }
+    }
+
+    pub fn group(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTransformGroup(self.handle)) }
+    }
+
+    /// The parameters accepted by [Transform::encode] and [Transform::decode]
+    pub fn parameters(&self) -> Array<TransformParameter> {
+        let mut count = 0;
+        let params = unsafe { BNGetTransformParameterList(self.handle, &mut count) };
+        unsafe { Array::new(params, count, ()) }
+    }
+
+    /// Decode `input` using `params`, a list of parameter names and values
+    pub fn decode<I, K, V>(&self, input: &DataBuffer, params: I) -> Result<DataBuffer, ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: Borrow<DataBuffer>,
+    {
+        self.apply(BNDecode, input, params)
+    }
+
+    /// Encode `input` using `params`, a list of parameter names and values
+    pub fn encode<I, K, V>(&self, input: &DataBuffer, params: I) -> Result<DataBuffer, ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: Borrow<DataBuffer>,
+    {
+        self.apply(BNEncode, input, params)
+    }
+
+    fn apply<I, K, V>(
+        &self,
+        func: unsafe extern "C" fn(
+            *mut BNTransform,
+            *mut BNDataBuffer,
+            *mut BNDataBuffer,
+            *mut BNTransformParameter,
+            usize,
+        ) -> bool,
+        input: &DataBuffer,
+        params: I,
+    ) -> Result<DataBuffer, ()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: BnStrCompatible,
+        V: Borrow<DataBuffer>,
+    {
+        let params: Vec<_> = params
+            .into_iter()
+            .map(|(name, value)| (name.into_bytes_with_nul(), value))
+            .collect();
+        let mut params_raw: Vec<BNTransformParameter> = params
+            .iter()
+            .map(|(name, value)| BNTransformParameter {
+                name: name.as_ref().as_ptr() as *const c_char,
+                value: value.borrow().as_raw(),
+            })
+            .collect();
+        let output = DataBuffer::default();
+        let success = unsafe {
+            func(
+                self.handle,
+                input.as_raw(),
+                output.as_raw(),
+                params_raw.as_mut_ptr(),
+                params_raw.len(),
+            )
+        };
+        if success {
+            Ok(output)
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl std::fmt::Debug for Transform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<transform '{}'>", self.name())
+    }
+}
+
+impl CoreArrayProvider for Transform {
+    type Raw = *mut BNTransform;
+    type Context = ();
+    type Wrapped<'a> = Transform;
+}
+
+unsafe impl CoreArrayProviderInner for Transform {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeTransformTypeList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::from_raw(*raw)
+    }
+}
+
+/// The trait required for custom transforms, see [Transform::register].
+pub trait CustomTransform: 'static + Sync {
+    /// The parameters accepted by this transform
+    fn parameters(&self) -> Vec<TransformParameter> {
+        vec![]
+    }
+
+    fn decode(
+        &self,
+        input: &DataBuffer,
+        params: &HashMap<String, DataBuffer>,
+    ) -> Result<DataBuffer, ()>;
+
+    fn encode(
+        &self,
+        input: &DataBuffer,
+        params: &HashMap<String, DataBuffer>,
+    ) -> Result<DataBuffer, ()>;
+}
+
+unsafe fn params_from_raw(
+    params: *mut BNTransformParameter,
+    count: usize,
+) -> HashMap<String, DataBuffer> {
+    if params.is_null() {
+        return HashMap::new();
+    }
+    std::slice::from_raw_parts(params, count)
+        .iter()
+        .map(|param| {
+            let name = CStr::from_ptr(param.name).to_string_lossy().into_owned();
+            // the value is owned by the caller, keep our own copy of it
+            let value = ManuallyDrop::new(DataBuffer::from_raw(param.value));
+            (name, DataBuffer::clone(&value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::databuffer::DataBuffer;
+
+    use super::{CustomTransform, Transform, TransformParameter, TransformType};
+
+    const DUMMY_DATA_0: &[u8] = b"0123456789\x00\x01\x02\x03\x04\x05\x06\x07\x08\x09\x09\xFF";
+    const NO_PARAMS: [(&str, &DataBuffer); 0] = [];
+
+    struct XorWithKey;
+
+    impl CustomTransform for XorWithKey {
+        fn parameters(&self) -> Vec<TransformParameter> {
+            vec![TransformParameter::new("key", "Key", 1)]
+        }
+
+        fn decode(
+            &self,
+            input: &DataBuffer,
+            params: &HashMap<String, DataBuffer>,
+        ) -> Result<DataBuffer, ()> {
+            let key = params.get("key").ok_or(())?.get_data();
+            let key = *key.first().ok_or(())?;
+            let data: Vec<u8> = input.get_data().iter().map(|b| b ^ key).collect();
+            DataBuffer::new(&data)
+        }
+
+        fn encode(
+            &self,
+            input: &DataBuffer,
+            params: &HashMap<String, DataBuffer>,
+        ) -> Result<DataBuffer, ()> {
+            self.decode(input, params)
+        }
+    }
+
+    #[test]
+    fn core_round_trip() {
+        crate::headless::init();
+
+        let base64 = Transform::by_name("Base64").unwrap();
+        let input = DataBuffer::new(DUMMY_DATA_0).unwrap();
+        let encoded = base64.encode(&input, NO_PARAMS).unwrap();
+        assert_ne!(encoded.get_data(), DUMMY_DATA_0);
+        let decoded = base64.decode(&encoded, NO_PARAMS).unwrap();
+        assert_eq!(decoded.get_data(), DUMMY_DATA_0);
+    }
+
+    #[test]
+    fn custom_round_trip() {
+        crate::headless::init();
+
+        let transform = Transform::register(
+            TransformType::InvertingTransform,
+            "TestXorWithKey",
+            "Test XOR with key",
+            "Test",
+            XorWithKey,
+        );
+        assert_eq!(transform.name().as_str(), "TestXorWithKey");
+        let params: Vec<_> = transform.parameters().iter().collect();
+        assert_eq!(params, [TransformParameter::new("key", "Key", 1)]);
+
+        let key = DataBuffer::new(&[0x55]).unwrap();
+        let input = DataBuffer::new(DUMMY_DATA_0).unwrap();
+        let encoded = transform.encode(&input, [("key", &key)]).unwrap();
+        let expected: Vec<u8> = DUMMY_DATA_0.iter().map(|b| b ^ 0x55).collect();
+        assert_eq!(encoded.get_data(), &expected[..]);
+        let decoded = transform.decode(&encoded, [("key", &key)]).unwrap();
+        assert_eq!(decoded.get_data(), DUMMY_DATA_0);
+
+        // the key parameter is required
+        assert!(transform.decode(&encoded, NO_PARAMS).is_err());
+    }
+}
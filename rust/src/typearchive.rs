@@ -7,6 +7,7 @@ use crate::metadata::Metadata;
 use crate::platform::Platform;
 use crate::rc::{Array, CoreArrayProvider, CoreArrayProviderInner, Ref};
 use crate::string::{BnStrCompatible, BnString};
+use crate::typecontainer::TypeContainer;
 use crate::types::{QualifiedName, QualifiedNameAndType, QualifiedNameTypeAndId, Type};
 
 /// Type Archives are a collection of types which can be shared between different analysis
@@ -627,13 +628,12 @@ impl TypeArchive {
         unsafe { BNIsTypeArchive(file.as_ref().as_ptr() as *const ffi::c_char) }
     }
 
-    // TODO implement TypeContainer
-    ///// Get the TypeContainer interface for this Type Archive, presenting types
-    ///// at the current snapshot in the archive.
-    //pub fn type_container(&self) -> TypeContainer {
-    //    let result = unsafe { BNGetTypeArchiveTypeContainer(self.as_raw()) };
-    //    unsafe { TypeContainer::from_raw(ptr::NonNull::new(result).unwrap()) }
-    //}
+    /// Get the TypeContainer interface for this Type Archive, presenting types
+    /// at the current snapshot in the archive.
+    pub fn type_container(&self) -> TypeContainer {
+        let result = unsafe { BNGetTypeArchiveTypeContainer(self.as_raw()) };
+        unsafe { TypeContainer::from_raw(ptr::NonNull::new(result).unwrap()) }
+    }
 
     /// Do some function in a transaction making a new snapshot whose id is passed to func. If func throws,
     /// the transaction will be rolled back and the snapshot will not be created.
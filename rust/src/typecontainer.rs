@@ -0,0 +1,383 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A generic interface to a source of types, such as the analysis of a [BinaryView](crate::binaryview::BinaryView),
+//! a [TypeArchive](crate::typearchive::TypeArchive) or a [TypeLibrary](crate::typelibrary::TypeLibrary)
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::{c_char, c_void};
+use std::mem;
+use std::ptr::{self, NonNull};
+use std::slice;
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNTypeContainerType as TypeContainerType;
+
+use crate::platform::Platform;
+use crate::rc::{Array, Ref};
+use crate::string::{BnStrCompatible, BnString};
+use crate::typeparser::{errors_from_raw, string_list, TypeParserError, TypeParserResult};
+use crate::types::{QualifiedName, QualifiedNameAndType, Type};
+
+/// A source of types, used to resolve named type references
+#[repr(transparent)]
+pub struct TypeContainer {
+    handle: NonNull<BNTypeContainer>,
+}
+
+impl TypeContainer {
+    pub(crate) unsafe fn from_raw(handle: NonNull<BNTypeContainer>) -> Self {
+        Self { handle }
+    }
+
+    pub(crate) unsafe fn ref_from_raw(handle: &*mut BNTypeContainer) -> &Self {
+        debug_assert!(!handle.is_null());
+        mem::transmute(handle)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn as_raw(&self) -> &mut BNTypeContainer {
+        &mut *self.handle.as_ptr()
+    }
+
+    /// Unique id of the container, this is the same for all containers of a given source
+    pub fn id(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTypeContainerGetId(self.as_raw())) }
+    }
+
+    /// Name displayed for the container
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNTypeContainerGetName(self.as_raw())) }
+    }
+
+    /// Kind of source of the types
+    pub fn container_type(&self) -> TypeContainerType {
+        unsafe { BNTypeContainerGetType(self.as_raw()) }
+    }
+
+    /// If types can be added to, renamed or deleted from the container
+    pub fn is_mutable(&self) -> bool {
+        unsafe { BNTypeContainerIsMutable(self.as_raw()) }
+    }
+
+    /// Platform of the types in the container
+    pub fn platform(&self) -> Ref<Platform> {
+        unsafe { Platform::ref_from_raw(BNTypeContainerGetPlatform(self.as_raw())) }
+    }
+
+    /// Add or update a type, returning its id, [None] if the type could not be added
+    pub fn add_type<N: Into<QualifiedName>>(&self, name: N, ty: &Type) -> Option<String> {
+        let name = name.into();
+        let mut ids = self.add_types(&[(name.clone(), ty.to_owned())])?;
+        ids.remove(&name)
+    }
+
+    /// Add or update types, returning the id of each type by name, [None] if the types could
+    /// not be added
+    ///
+    /// Types are added all at once, which is faster than adding them one by one with
+    /// [TypeContainer::add_type] and resolves references between them.
+    pub fn add_types(
+        &self,
+        types: &[(QualifiedName, Ref<Type>)],
+    ) -> Option<HashMap<QualifiedName, String>> {
+        self.add_types_with_progress(types, |_, _| true)
+    }
+
+    /// Same as [TypeContainer::add_types], reporting progress with `progress`, which can
+    /// return false to cancel
+    pub fn add_types_with_progress<F: FnMut(usize, usize) -> bool>(
+        &self,
+        types: &[(QualifiedName, Ref<Type>)],
+        mut progress: F,
+    ) -> Option<HashMap<QualifiedName, String>> {
+        let names: Vec<BNQualifiedName> = types.iter().map(|(name, _)| name.0).collect();
+        let mut handles: Vec<*mut BNType> = types.iter().map(|(_, ty)| ty.handle).collect();
+        let mut result_names = ptr::null_mut();
+        let mut result_ids = ptr::null_mut();
+        let mut result_count = 0;
+        let success = unsafe {
+            BNTypeContainerAddTypes(
+                self.as_raw(),
+                names.as_ptr(),
+                handles.as_mut_ptr(),
+                types.len(),
+                Some(cb_progress::<F>),
+                &mut progress as *mut F as *mut c_void,
+                &mut result_names,
+                &mut result_ids,
+                &mut result_count,
+            )
+        };
+        if !success {
+            return None;
+        }
+
+        let names: Array<QualifiedName> = unsafe { Array::new(result_names, result_count, ()) };
+        let ids: Array<BnString> = unsafe { Array::new(result_ids, result_count, ()) };
+        Some(
+            names
+                .iter()
+                .zip(ids.iter())
+                .map(|(name, id)| (name.clone(), id.to_string()))
+                .collect(),
+        )
+    }
+
+    /// Rename the type with id `type_id`
+    pub fn rename_type<S: BnStrCompatible, N: Into<QualifiedName>>(
+        &self,
+        type_id: S,
+        new_name: N,
+    ) -> bool {
+        let type_id = type_id.into_bytes_with_nul();
+        let new_name = new_name.into();
+        unsafe {
+            BNTypeContainerRenameType(
+                self.as_raw(),
+                type_id.as_ref().as_ptr() as *const c_char,
+                &new_name.0,
+            )
+        }
+    }
+
+    /// Delete the type with id `type_id`
+    pub fn delete_type<S: BnStrCompatible>(&self, type_id: S) -> bool {
+        let type_id = type_id.into_bytes_with_nul();
+        unsafe {
+            BNTypeContainerDeleteType(self.as_raw(), type_id.as_ref().as_ptr() as *const c_char)
+        }
+    }
+
+    /// Id of the type named `name`, [None] if there is no such type
+    pub fn type_id<N: Into<QualifiedName>>(&self, name: N) -> Option<BnString> {
+        let name = name.into();
+        let mut result = ptr::null_mut();
+        let success = unsafe { BNTypeContainerGetTypeId(self.as_raw(), &name.0, &mut result) };
+        (success && !result.is_null()).then(|| unsafe { BnString::from_raw(result) })
+    }
+
+    /// Name of the type with id `type_id`, [None] if there is no such type
+    pub fn type_name<S: BnStrCompatible>(&self, type_id: S) -> Option<QualifiedName> {
+        let type_id = type_id.into_bytes_with_nul();
+        let mut result = BNQualifiedName::default();
+        let success = unsafe {
+            BNTypeContainerGetTypeName(
+                self.as_raw(),
+                type_id.as_ref().as_ptr() as *const c_char,
+                &mut result,
+            )
+        };
+        success.then(|| QualifiedName(result))
+    }
+
+    /// Type with id `type_id`, [None] if there is no such type
+    pub fn type_by_id<S: BnStrCompatible>(&self, type_id: S) -> Option<Ref<Type>> {
+        let type_id = type_id.into_bytes_with_nul();
+        let mut result = ptr::null_mut();
+        let success = unsafe {
+            BNTypeContainerGetTypeById(
+                self.as_raw(),
+                type_id.as_ref().as_ptr() as *const c_char,
+                &mut result,
+            )
+        };
+        (success && !result.is_null()).then(|| unsafe { Type::ref_from_raw(result) })
+    }
+
+    /// Type named `name`, [None] if there is no such type
+    pub fn type_by_name<N: Into<QualifiedName>>(&self, name: N) -> Option<Ref<Type>> {
+        let name = name.into();
+        let mut result = ptr::null_mut();
+        let success = unsafe { BNTypeContainerGetTypeByName(self.as_raw(), &name.0, &mut result) };
+        (success && !result.is_null()).then(|| unsafe { Type::ref_from_raw(result) })
+    }
+
+    /// All the types in the container, with their names by id
+    pub fn types(&self) -> Option<HashMap<String, (QualifiedName, Ref<Type>)>> {
+        let mut ids = ptr::null_mut();
+        let mut names = ptr::null_mut();
+        let mut types = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            BNTypeContainerGetTypes(self.as_raw(), &mut ids, &mut names, &mut types, &mut count)
+        };
+        if !success {
+            return None;
+        }
+
+        let ids: Array<BnString> = unsafe { Array::new(ids, count, ()) };
+        let names: Array<QualifiedName> = unsafe { Array::new(names, count, ()) };
+        let result = unsafe { slice::from_raw_parts(types, count) }
+            .iter()
+            .zip(ids.iter().zip(names.iter()))
+            .map(|(ty, (id, name))| {
+                let ty = unsafe { Type::ref_from_raw(BNNewTypeReference(*ty)) };
+                (id.to_string(), (name.clone(), ty))
+            })
+            .collect();
+        unsafe { BNFreeTypeList(types, count) };
+        Some(result)
+    }
+
+    /// Ids of all the types in the container
+    pub fn type_ids(&self) -> Option<HashSet<String>> {
+        let mut ids = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe { BNTypeContainerGetTypeIds(self.as_raw(), &mut ids, &mut count) };
+        if !success {
+            return None;
+        }
+
+        let ids: Array<BnString> = unsafe { Array::new(ids, count, ()) };
+        Some(ids.iter().map(str::to_string).collect())
+    }
+
+    /// Names of all the types in the container
+    pub fn type_names(&self) -> Option<Array<QualifiedName>> {
+        let mut names = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe { BNTypeContainerGetTypeNames(self.as_raw(), &mut names, &mut count) };
+        success.then(|| unsafe { Array::new(names, count, ()) })
+    }
+
+    /// Names of all the types in the container, by id
+    pub fn type_names_and_ids(&self) -> Option<HashMap<String, QualifiedName>> {
+        let mut ids = ptr::null_mut();
+        let mut names = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            BNTypeContainerGetTypeNamesAndIds(self.as_raw(), &mut ids, &mut names, &mut count)
+        };
+        if !success {
+            return None;
+        }
+
+        let ids: Array<BnString> = unsafe { Array::new(ids, count, ()) };
+        let names: Array<QualifiedName> = unsafe { Array::new(names, count, ()) };
+        Some(
+            ids.iter()
+                .zip(names.iter())
+                .map(|(id, name)| (id.to_string(), name.clone()))
+                .collect(),
+        )
+    }
+
+    /// Parse a single type and name, eg: `int foo` or `struct bar { int x; }`, resolving
+    /// named types against the types of the container
+    ///
+    /// * `import_dependencies` - Import the types referenced from type libraries and archives
+    pub fn parse_type_string<S: BnStrCompatible>(
+        &self,
+        source: S,
+        import_dependencies: bool,
+    ) -> Result<QualifiedNameAndType, Vec<TypeParserError>> {
+        let source = source.into_bytes_with_nul();
+        let mut result = BNQualifiedNameAndType::default();
+        let mut errors = ptr::null_mut();
+        let mut error_count = 0;
+        let success = unsafe {
+            BNTypeContainerParseTypeString(
+                self.as_raw(),
+                source.as_ref().as_ptr() as *const c_char,
+                import_dependencies,
+                &mut result,
+                &mut errors,
+                &mut error_count,
+            )
+        };
+        let errors = unsafe { errors_from_raw(errors, error_count) };
+        if success {
+            Ok(QualifiedNameAndType(result))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse an entire block of source into types, variables and functions, resolving named
+    /// types against the types of the container
+    ///
+    /// * `file_name` - Name of the file containing the source, used in errors
+    /// * `options` - Options for the parser, see [crate::typeparser::parse_options_text]
+    /// * `include_dirs` - Directories to search for included headers
+    /// * `auto_type_source` - Source of the types, used to mark them as auto types
+    /// * `import_dependencies` - Import the types referenced from type libraries and archives
+    #[allow(clippy::too_many_arguments)]
+    pub fn parse_types_from_source<S: BnStrCompatible, F: BnStrCompatible, A: BnStrCompatible>(
+        &self,
+        source: S,
+        file_name: F,
+        options: &[String],
+        include_dirs: &[String],
+        auto_type_source: A,
+        import_dependencies: bool,
+    ) -> Result<TypeParserResult, Vec<TypeParserError>> {
+        let source = source.into_bytes_with_nul();
+        let file_name = file_name.into_bytes_with_nul();
+        let auto_type_source = auto_type_source.into_bytes_with_nul();
+        let (_options, options_raw) = string_list(options);
+        let (_include_dirs, include_dirs_raw) = string_list(include_dirs);
+        let mut result = BNTypeParserResult::default();
+        let mut errors = ptr::null_mut();
+        let mut error_count = 0;
+        let success = unsafe {
+            BNTypeContainerParseTypesFromSource(
+                self.as_raw(),
+                source.as_ref().as_ptr() as *const c_char,
+                file_name.as_ref().as_ptr() as *const c_char,
+                options_raw.as_ptr(),
+                options_raw.len(),
+                include_dirs_raw.as_ptr(),
+                include_dirs_raw.len(),
+                auto_type_source.as_ref().as_ptr() as *const c_char,
+                import_dependencies,
+                &mut result,
+                &mut errors,
+                &mut error_count,
+            )
+        };
+        let errors = unsafe { errors_from_raw(errors, error_count) };
+        if success {
+            let parsed = unsafe { TypeParserResult::from_raw(&result) };
+            unsafe { BNFreeTypeParserResult(&mut result) };
+            Ok(parsed)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Clone for TypeContainer {
+    fn clone(&self) -> Self {
+        unsafe { Self::from_raw(NonNull::new(BNDuplicateTypeContainer(self.as_raw())).unwrap()) }
+    }
+}
+
+impl Drop for TypeContainer {
+    fn drop(&mut self) {
+        unsafe { BNFreeTypeContainer(self.as_raw()) }
+    }
+}
+
+unsafe extern "C" fn cb_progress<F: FnMut(usize, usize) -> bool>(
+    ctxt: *mut c_void,
+    progress: usize,
+    total: usize,
+) -> bool {
+    ffi_wrap!("TypeContainer::add_types_with_progress", {
+        let ctxt: &mut F = &mut *(ctxt as *mut F);
+        ctxt(progress, total)
+    })
+}
@@ -8,6 +8,7 @@ use crate::{
     platform::Platform,
     rc::{Array, CoreArrayProvider, CoreArrayProviderInner, Ref},
     string::{BnStrCompatible, BnString},
+    typecontainer::TypeContainer,
     types::{QualifiedName, QualifiedNameAndType, Type},
 };
 
@@ -257,13 +258,12 @@ impl TypeLibrary {
         unsafe { Metadata::from_raw(md_handle) }
     }
 
-    // TODO: implement TypeContainer
-    // /// Type Container for all TYPES within the Type Library. Objects are not included.
-    // /// The Type Container's Platform will be the first platform associated with the Type Library.
-    // pub fn type_container(&self) -> TypeContainer {
-    //     let result = unsafe{ BNGetTypeLibraryTypeContainer(self.as_raw())};
-    //     unsafe{TypeContainer::from_raw(ptr::NonNull::new(result).unwrap())}
-    // }
+    /// Type Container for all TYPES within the Type Library. Objects are not included.
+    /// The Type Container's Platform will be the first platform associated with the Type Library.
+    pub fn type_container(&self) -> TypeContainer {
+        let result = unsafe { BNGetTypeLibraryTypeContainer(self.as_raw()) };
+        unsafe { TypeContainer::from_raw(ptr::NonNull::new(result).unwrap()) }
+    }
 
     /// Directly inserts a named object into the type library's object store.
     /// This is not done recursively, so care should be taken that types referring to other types
@@ -0,0 +1,749 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsers turning source code, usually C, into [Type]s.
+//!
+//! Parsers registered with the core (eg: the clang based `ClangTypeParser`) are listed by
+//! [CoreTypeParser::list], new ones can be added by implementing [TypeParser] and calling
+//! [CoreTypeParser::register].
+//!
+//! ```no_run
+//! use binaryninja::platform::Platform;
+//! use binaryninja::typeparser::{CoreTypeParser, TypeParser};
+//!
+//! let platform = Platform::by_name("linux-x86_64").unwrap();
+//! let parser = CoreTypeParser::default_parser().unwrap();
+//! match parser.parse_type_string("struct foo { int bar; }", &platform, None) {
+//!     Ok(result) => println!("{} = {}", result.name(), result.type_object().to_string()),
+//!     Err(errors) => errors.iter().for_each(|error| eprintln!("{}", error)),
+//! }
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNTypeParserErrorSeverity as TypeParserErrorSeverity;
+pub use binaryninjacore_sys::BNTypeParserOption as TypeParserOption;
+
+use std::ffi::{c_char, c_void, CStr};
+use std::fmt;
+use std::mem::{self, ManuallyDrop};
+use std::ptr;
+use std::slice;
+
+use crate::platform::Platform;
+use crate::rc::*;
+use crate::string::*;
+use crate::typecontainer::TypeContainer;
+use crate::types::{QualifiedName, QualifiedNameAndType, Type};
+
+/// A diagnostic emitted while parsing
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeParserError {
+    pub severity: TypeParserErrorSeverity,
+    pub message: String,
+    pub file_name: String,
+    pub line: u64,
+    pub column: u64,
+}
+
+impl TypeParserError {
+    pub fn new<M: Into<String>, F: Into<String>>(
+        severity: TypeParserErrorSeverity,
+        message: M,
+        file_name: F,
+        line: u64,
+        column: u64,
+    ) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            file_name: file_name.into(),
+            line,
+            column,
+        }
+    }
+
+    pub(crate) unsafe fn from_raw(value: &BNTypeParserError) -> Self {
+        Self {
+            severity: value.severity,
+            message: raw_to_string(value.message).unwrap_or_default(),
+            file_name: raw_to_string(value.fileName).unwrap_or_default(),
+            line: value.line,
+            column: value.column,
+        }
+    }
+
+    fn into_raw(self) -> BNTypeParserError {
+        BNTypeParserError {
+            severity: self.severity,
+            message: BnString::new(self.message).into_raw(),
+            fileName: BnString::new(self.file_name).into_raw(),
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+impl fmt::Display for TypeParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use TypeParserErrorSeverity::*;
+        let severity = match self.severity {
+            IgnoredSeverity => "ignored",
+            NoteSeverity => "note",
+            RemarkSeverity => "remark",
+            WarningSeverity => "warning",
+            ErrorSeverity => "error",
+            FatalSeverity => "fatal error",
+        };
+        if !self.file_name.is_empty() {
+            write!(f, "{}:{}:{}: ", self.file_name, self.line, self.column)?;
+        }
+        write!(f, "{}: {}", severity, self.message)
+    }
+}
+
+/// A type, variable or function declared in the parsed source
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParsedType {
+    pub name: QualifiedName,
+    pub ty: Ref<Type>,
+    /// If the type was declared by the user rather than by an included header
+    pub is_user: bool,
+}
+
+impl ParsedType {
+    pub fn new(name: QualifiedName, ty: Ref<Type>, is_user: bool) -> Self {
+        Self { name, ty, is_user }
+    }
+
+    pub(crate) unsafe fn from_raw(value: &BNParsedType) -> Self {
+        Self {
+            name: mem::transmute::<&BNQualifiedName, &QualifiedName>(&value.name).clone(),
+            ty: Type::ref_from_raw(BNNewTypeReference(value.type_)),
+            is_user: value.isUser,
+        }
+    }
+
+    fn into_raw(self) -> BNParsedType {
+        let name = ManuallyDrop::new(self.name);
+        BNParsedType {
+            name: name.0,
+            type_: unsafe { BNNewTypeReference(self.ty.handle) },
+            isUser: self.is_user,
+        }
+    }
+}
+
+/// Everything declared in the parsed source
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeParserResult {
+    pub types: Vec<ParsedType>,
+    pub variables: Vec<ParsedType>,
+    pub functions: Vec<ParsedType>,
+}
+
+impl TypeParserResult {
+    /// Copy the contents of `value`, which is still owned by the caller
+    pub(crate) unsafe fn from_raw(value: &BNTypeParserResult) -> Self {
+        unsafe fn parsed_types(raw: *mut BNParsedType, count: usize) -> Vec<ParsedType> {
+            if raw.is_null() {
+                return vec![];
+            }
+            slice::from_raw_parts(raw, count)
+                .iter()
+                .map(|value| ParsedType::from_raw(value))
+                .collect()
+        }
+
+        Self {
+            types: parsed_types(value.types, value.typeCount),
+            variables: parsed_types(value.variables, value.variableCount),
+            functions: parsed_types(value.functions, value.functionCount),
+        }
+    }
+
+    fn into_raw(self) -> BNTypeParserResult {
+        fn parsed_types(values: Vec<ParsedType>) -> (*mut BNParsedType, usize) {
+            let values: Box<[BNParsedType]> =
+                values.into_iter().map(ParsedType::into_raw).collect();
+            let count = values.len();
+            (Box::leak(values).as_mut_ptr(), count)
+        }
+
+        let (types, type_count) = parsed_types(self.types);
+        let (variables, variable_count) = parsed_types(self.variables);
+        let (functions, function_count) = parsed_types(self.functions);
+        BNTypeParserResult {
+            types,
+            variables,
+            functions,
+            typeCount: type_count,
+            variableCount: variable_count,
+            functionCount: function_count,
+        }
+    }
+}
+
+/// A parser turning source code into types, either one registered with the core, see
+/// [CoreTypeParser], or a custom one registered with [CoreTypeParser::register]
+pub trait TypeParser {
+    /// Get the string representation of `option` with `value`, to be passed in the `options` of
+    /// [TypeParser::preprocess_source] and [TypeParser::parse_types_from_source]
+    fn option_text(&self, _option: TypeParserOption, _value: &str) -> Option<String> {
+        None
+    }
+
+    /// Preprocess `source`, expanding includes and macros
+    ///
+    /// * `source` - source code to preprocess
+    /// * `file_name` - name of the file containing `source`
+    /// * `platform` - platform whose types are available to the source
+    /// * `existing_types` - types already defined, which can be referenced by the source
+    /// * `options` - parser specific options
+    /// * `include_dirs` - directories searched for included files
+    #[allow(clippy::too_many_arguments)]
+    fn preprocess_source(
+        &self,
+        source: &str,
+        _file_name: &str,
+        _platform: &Platform,
+        _existing_types: Option<&TypeContainer>,
+        _options: &[String],
+        _include_dirs: &[String],
+    ) -> Result<String, Vec<TypeParserError>> {
+        Ok(source.to_string())
+    }
+
+    /// Parse all the types, variables and functions declared in `source`
+    ///
+    /// * `auto_type_source` - if not empty, the types are parsed as automatically defined,
+    ///   using this as their source
+    ///
+    /// See [TypeParser::preprocess_source] for the other arguments.
+    #[allow(clippy::too_many_arguments)]
+    fn parse_types_from_source(
+        &self,
+        source: &str,
+        file_name: &str,
+        platform: &Platform,
+        existing_types: Option<&TypeContainer>,
+        options: &[String],
+        include_dirs: &[String],
+        auto_type_source: &str,
+    ) -> Result<TypeParserResult, Vec<TypeParserError>>;
+
+    /// Parse the single type declared by `source`, eg: `struct foo { int bar; }` or `int (*)()`
+    fn parse_type_string(
+        &self,
+        source: &str,
+        platform: &Platform,
+        existing_types: Option<&TypeContainer>,
+    ) -> Result<QualifiedNameAndType, Vec<TypeParserError>>;
+}
+
+/// A type parser registered with the core
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CoreTypeParser {
+    handle: *mut BNTypeParser,
+}
+
+unsafe impl Send for CoreTypeParser {}
+unsafe impl Sync for CoreTypeParser {}
+
+impl CoreTypeParser {
+    pub(crate) unsafe fn from_raw(handle: *mut BNTypeParser) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// List of all registered type parsers
+    pub fn list() -> Array<CoreTypeParser> {
+        let mut count = 0;
+        let parsers = unsafe { BNGetTypeParserList(&mut count) };
+        assert!(!parsers.is_null());
+        unsafe { Array::new(parsers, count, ()) }
+    }
+
+    /// Retrieve a type parser by its name
+    pub fn by_name<S: BnStrCompatible>(name: S) -> Option<CoreTypeParser> {
+        let name = name.into_bytes_with_nul();
+        let parser = unsafe { BNGetTypeParserByName(name.as_ref().as_ptr() as *const c_char) };
+        (!parser.is_null()).then(|| unsafe { Self::from_raw(parser) })
+    }
+
+    /// The type parser selected by the `analysis.types.parserName` setting
+    pub fn default_parser() -> Option<CoreTypeParser> {
+        let parser = unsafe { BNGetDefaultTypeParser() };
+        (!parser.is_null()).then(|| unsafe { Self::from_raw(parser) })
+    }
+
+    /// Register a new type parser, making it available for every user of the core.
+    ///
+    /// ```no_run
+    /// use binaryninja::platform::Platform;
+    /// use binaryninja::typecontainer::TypeContainer;
+    /// use binaryninja::typeparser::*;
+    /// use binaryninja::types::{QualifiedName, QualifiedNameAndType, Type};
+    ///
+    /// /// Parses `name: width` lines into integer types
+    /// struct IdlParser;
+    ///
+    /// impl TypeParser for IdlParser {
+    ///     fn parse_types_from_source(
+    ///         &self,
+    ///         source: &str,
+    ///         file_name: &str,
+    ///         _platform: &Platform,
+    ///         _existing_types: Option<&TypeContainer>,
+    ///         _options: &[String],
+    ///         _include_dirs: &[String],
+    ///         _auto_type_source: &str,
+    ///     ) -> Result<TypeParserResult, Vec<TypeParserError>> {
+    ///         let mut result = TypeParserResult::default();
+    ///         for (line, text) in source.lines().enumerate() {
+    ///             let Some((name, width)) = text.split_once(':') else {
+    ///                 let message = format!("expected `name: width`, got `{}`", text);
+    ///                 let severity = TypeParserErrorSeverity::ErrorSeverity;
+    ///                 let error = TypeParserError::new(severity, message, file_name, line as u64 + 1, 1);
+    ///                 return Err(vec![error]);
+    ///             };
+    ///             let width = width.trim().parse().unwrap_or(4);
+    ///             let ty = Type::int(width, false);
+    ///             result.types.push(ParsedType::new(QualifiedName::from(name.trim()), ty, true));
+    ///         }
+    ///         Ok(result)
+    ///     }
+    ///
+    ///     fn parse_type_string(
+    ///         &self,
+    ///         source: &str,
+    ///         _platform: &Platform,
+    ///         _existing_types: Option<&TypeContainer>,
+    ///     ) -> Result<QualifiedNameAndType, Vec<TypeParserError>> {
+    ///         let width = source.trim().parse().unwrap_or(4);
+    ///         Ok(QualifiedNameAndType::new(QualifiedName::from(""), &Type::int(width, false)))
+    ///     }
+    /// }
+    ///
+    /// CoreTypeParser::register("IdlParser", IdlParser);
+    /// ```
+    pub fn register<S, T>(name: S, parser: T) -> CoreTypeParser
+    where
+        S: BnStrCompatible,
+        T: TypeParser + 'static + Sync,
+    {
+        let name = name.into_bytes_with_nul();
+        let ctxt = Box::into_raw(Box::new(parser));
+
+        let mut callbacks = BNTypeParserCallbacks {
+            context: ctxt as *mut c_void,
+            getOptionText: Some(cb_get_option_text::<T>),
+            preprocessSource: Some(cb_preprocess_source::<T>),
+            parseTypesFromSource: Some(cb_parse_types_from_source::<T>),
+            parseTypeString: Some(cb_parse_type_string::<T>),
+            freeString: Some(cb_free_string),
+            freeResult: Some(cb_free_result),
+            freeErrorList: Some(cb_free_error_list),
+        };
+
+        unsafe {
+            Self::from_raw(BNRegisterTypeParser(
+                name.as_ref().as_ptr() as *const c_char,
+                &mut callbacks,
+            ))
+        }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypeParserName(self.handle)) }
+    }
+}
+
+impl TypeParser for CoreTypeParser {
+    fn option_text(&self, option: TypeParserOption, value: &str) -> Option<String> {
+        let value = BnString::new(value);
+        let mut result = ptr::null_mut();
+        let success =
+            unsafe { BNGetTypeParserOptionText(self.handle, option, value.as_ptr(), &mut result) };
+        (success && !result.is_null()).then(|| unsafe { BnString::from_raw(result) }.to_string())
+    }
+
+    fn preprocess_source(
+        &self,
+        source: &str,
+        file_name: &str,
+        platform: &Platform,
+        existing_types: Option<&TypeContainer>,
+        options: &[String],
+        include_dirs: &[String],
+    ) -> Result<String, Vec<TypeParserError>> {
+        let source = BnString::new(source);
+        let file_name = BnString::new(file_name);
+        let (_options, options_raw) = string_list(options);
+        let (_include_dirs, include_dirs_raw) = string_list(include_dirs);
+        let mut output = ptr::null_mut();
+        let mut errors = ptr::null_mut();
+        let mut error_count = 0;
+        let success = unsafe {
+            BNTypeParserPreprocessSource(
+                self.handle,
+                source.as_ptr(),
+                file_name.as_ptr(),
+                platform.handle,
+                container_raw(existing_types),
+                options_raw.as_ptr(),
+                options_raw.len(),
+                include_dirs_raw.as_ptr(),
+                include_dirs_raw.len(),
+                &mut output,
+                &mut errors,
+                &mut error_count,
+            )
+        };
+        let errors = unsafe { errors_from_raw(errors, error_count) };
+        if success && !output.is_null() {
+            Ok(unsafe { BnString::from_raw(output) }.to_string())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn parse_types_from_source(
+        &self,
+        source: &str,
+        file_name: &str,
+        platform: &Platform,
+        existing_types: Option<&TypeContainer>,
+        options: &[String],
+        include_dirs: &[String],
+        auto_type_source: &str,
+    ) -> Result<TypeParserResult, Vec<TypeParserError>> {
+        let source = BnString::new(source);
+        let file_name = BnString::new(file_name);
+        let auto_type_source = BnString::new(auto_type_source);
+        let (_options, options_raw) = string_list(options);
+        let (_include_dirs, include_dirs_raw) = string_list(include_dirs);
+        let mut result = BNTypeParserResult::default();
+        let mut errors = ptr::null_mut();
+        let mut error_count = 0;
+        let success = unsafe {
+            BNTypeParserParseTypesFromSource(
+                self.handle,
+                source.as_ptr(),
+                file_name.as_ptr(),
+                platform.handle,
+                container_raw(existing_types),
+                options_raw.as_ptr(),
+                options_raw.len(),
+                include_dirs_raw.as_ptr(),
+                include_dirs_raw.len(),
+                auto_type_source.as_ptr(),
+                &mut result,
+                &mut errors,
+                &mut error_count,
+            )
+        };
+        let errors = unsafe { errors_from_raw(errors, error_count) };
+        if success {
+            let parsed = unsafe { TypeParserResult::from_raw(&result) };
+            unsafe { BNFreeTypeParserResult(&mut result) };
+            Ok(parsed)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn parse_type_string(
+        &self,
+        source: &str,
+        platform: &Platform,
+        existing_types: Option<&TypeContainer>,
+    ) -> Result<QualifiedNameAndType, Vec<TypeParserError>> {
+        let source = BnString::new(source);
+        let mut result = BNQualifiedNameAndType::default();
+        let mut errors = ptr::null_mut();
+        let mut error_count = 0;
+        let success = unsafe {
+            BNTypeParserParseTypeString(
+                self.handle,
+                source.as_ptr(),
+                platform.handle,
+                container_raw(existing_types),
+                &mut result,
+                &mut errors,
+                &mut error_count,
+            )
+        };
+        let errors = unsafe { errors_from_raw(errors, error_count) };
+        if success {
+            Ok(QualifiedNameAndType(result))
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl fmt::Debug for CoreTypeParser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<type parser '{}'>", self.name())
+    }
+}
+
+impl CoreArrayProvider for CoreTypeParser {
+    type Raw = *mut BNTypeParser;
+    type Context = ();
+    type Wrapped<'a> = CoreTypeParser;
+}
+
+unsafe impl CoreArrayProviderInner for CoreTypeParser {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeTypeParserList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::from_raw(*raw)
+    }
+}
+
+/// Split a string of command line style options, eg: `-x c++ "-DNAME=a b"`, into a list
+/// suitable for the `options` of [TypeParser::parse_types_from_source]
+pub fn parse_options_text<S: BnStrCompatible>(text: S) -> Array<BnString> {
+    let text = text.into_bytes_with_nul();
+    let mut count = 0;
+    let options = unsafe {
+        BNParseTypeParserOptionsText(text.as_ref().as_ptr() as *const c_char, &mut count)
+    };
+    unsafe { Array::new(options, count, ()) }
+}
+
+pub(crate) fn string_list(values: &[String]) -> (Vec<BnString>, Vec<*const c_char>) {
+    let values: Vec<BnString> = values.iter().map(BnString::new).collect();
+    let raw = values
+        .iter()
+        .map(|value| value.as_ptr() as *const c_char)
+        .collect();
+    (values, raw)
+}
+
+fn container_raw(container: Option<&TypeContainer>) -> *mut BNTypeContainer {
+    container
+        .map(|container| unsafe { container.as_raw() as *mut _ })
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Copy and free the list of errors returned by the core
+pub(crate) unsafe fn errors_from_raw(
+    errors: *mut BNTypeParserError,
+    count: usize,
+) -> Vec<TypeParserError> {
+    if errors.is_null() {
+        return vec![];
+    }
+    let result = slice::from_raw_parts(errors, count)
+        .iter()
+        .map(|error| TypeParserError::from_raw(error))
+        .collect();
+    BNFreeTypeParserErrors(errors, count);
+    result
+}
+
+unsafe fn errors_into_raw(
+    errors: Vec<TypeParserError>,
+    result: *mut *mut BNTypeParserError,
+    count: *mut usize,
+) {
+    let errors: Box<[BNTypeParserError]> =
+        errors.into_iter().map(TypeParserError::into_raw).collect();
+    *count = errors.len();
+    *result = Box::leak(errors).as_mut_ptr();
+}
+
+unsafe fn string_list_from_raw(values: *const *const c_char, count: usize) -> Vec<String> {
+    if values.is_null() {
+        return vec![];
+    }
+    slice::from_raw_parts(values, count)
+        .iter()
+        .map(|value| CStr::from_ptr(*value).to_string_lossy().into_owned())
+        .collect()
+}
+
+extern "C" fn cb_get_option_text<T: TypeParser>(
+    ctxt: *mut c_void,
+    option: BNTypeParserOption,
+    value: *const c_char,
+    result: *mut *mut c_char,
+) -> bool {
+    ffi_wrap!("TypeParser::option_text", unsafe {
+        let parser = &*(ctxt as *const T);
+        match parser.option_text(option, &str_from_raw(value)) {
+            Some(text) => {
+                *result = BnString::new(text).into_raw();
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+extern "C" fn cb_preprocess_source<T: TypeParser>(
+    ctxt: *mut c_void,
+    source: *const c_char,
+    file_name: *const c_char,
+    platform: *mut BNPlatform,
+    existing_types: *mut BNTypeContainer,
+    options: *const *const c_char,
+    option_count: usize,
+    include_dirs: *const *const c_char,
+    include_dir_count: usize,
+    output: *mut *mut c_char,
+    errors: *mut *mut BNTypeParserError,
+    error_count: *mut usize,
+) -> bool {
+    ffi_wrap!("TypeParser::preprocess_source", unsafe {
+        let parser = &*(ctxt as *const T);
+        let platform = Platform { handle: platform };
+        let existing_types =
+            (!existing_types.is_null()).then(|| TypeContainer::ref_from_raw(&existing_types));
+        let result = parser.preprocess_source(
+            &str_from_raw(source),
+            &str_from_raw(file_name),
+            &platform,
+            existing_types,
+            &string_list_from_raw(options, option_count),
+            &string_list_from_raw(include_dirs, include_dir_count),
+        );
+        match result {
+            Ok(text) => {
+                *output = BnString::new(text).into_raw();
+                errors_into_raw(vec![], errors, error_count);
+                true
+            }
+            Err(errs) => {
+                errors_into_raw(errs, errors, error_count);
+                false
+            }
+        }
+    })
+}
+
+extern "C" fn cb_parse_types_from_source<T: TypeParser>(
+    ctxt: *mut c_void,
+    source: *const c_char,
+    file_name: *const c_char,
+    platform: *mut BNPlatform,
+    existing_types: *mut BNTypeContainer,
+    options: *const *const c_char,
+    option_count: usize,
+    include_dirs: *const *const c_char,
+    include_dir_count: usize,
+    auto_type_source: *const c_char,
+    result: *mut BNTypeParserResult,
+    errors: *mut *mut BNTypeParserError,
+    error_count: *mut usize,
+) -> bool {
+    ffi_wrap!("TypeParser::parse_types_from_source", unsafe {
+        let parser = &*(ctxt as *const T);
+        let platform = Platform { handle: platform };
+        let existing_types =
+            (!existing_types.is_null()).then(|| TypeContainer::ref_from_raw(&existing_types));
+        let parsed = parser.parse_types_from_source(
+            &str_from_raw(source),
+            &str_from_raw(file_name),
+            &platform,
+            existing_types,
+            &string_list_from_raw(options, option_count),
+            &string_list_from_raw(include_dirs, include_dir_count),
+            &str_from_raw(auto_type_source),
+        );
+        match parsed {
+            Ok(parsed) => {
+                *result = parsed.into_raw();
+                errors_into_raw(vec![], errors, error_count);
+                true
+            }
+            Err(errs) => {
+                errors_into_raw(errs, errors, error_count);
+                false
+            }
+        }
+    })
+}
+
+extern "C" fn cb_parse_type_string<T: TypeParser>(
+    ctxt: *mut c_void,
+    source: *const c_char,
+    platform: *mut BNPlatform,
+    existing_types: *mut BNTypeContainer,
+    result: *mut BNQualifiedNameAndType,
+    errors: *mut *mut BNTypeParserError,
+    error_count: *mut usize,
+) -> bool {
+    ffi_wrap!("TypeParser::parse_type_string", unsafe {
+        let parser = &*(ctxt as *const T);
+        let platform = Platform { handle: platform };
+        let existing_types =
+            (!existing_types.is_null()).then(|| TypeContainer::ref_from_raw(&existing_types));
+        match parser.parse_type_string(&str_from_raw(source), &platform, existing_types) {
+            Ok(parsed) => {
+                // ownership of the name and the type reference moves to the core
+                *result = ManuallyDrop::new(parsed).0;
+                errors_into_raw(vec![], errors, error_count);
+                true
+            }
+            Err(errs) => {
+                errors_into_raw(errs, errors, error_count);
+                false
+            }
+        }
+    })
+}
+
+extern "C" fn cb_free_string(_ctxt: *mut c_void, string: *mut c_char) {
+    ffi_wrap!("TypeParser::free_string", unsafe {
+        drop(BnString::from_raw(string));
+    })
+}
+
+extern "C" fn cb_free_result(_ctxt: *mut c_void, result: *mut BNTypeParserResult) {
+    ffi_wrap!("TypeParser::free_result", unsafe {
+        unsafe fn free_parsed_types(values: *mut BNParsedType, count: usize) {
+            let values = Box::from_raw(ptr::slice_from_raw_parts_mut(values, count));
+            for value in values.iter() {
+                drop(QualifiedName(value.name));
+                BNFreeType(value.type_);
+            }
+        }
+
+        let result = &*result;
+        free_parsed_types(result.types, result.typeCount);
+        free_parsed_types(result.variables, result.variableCount);
+        free_parsed_types(result.functions, result.functionCount);
+    })
+}
+
+extern "C" fn cb_free_error_list(
+    _ctxt: *mut c_void,
+    errors: *mut BNTypeParserError,
+    error_count: usize,
+) {
+    ffi_wrap!("TypeParser::free_error_list", unsafe {
+        let errors = Box::from_raw(ptr::slice_from_raw_parts_mut(errors, error_count));
+        for error in errors.iter() {
+            drop(BnString::from_raw(error.message));
+            drop(BnString::from_raw(error.fileName));
+        }
+    })
+}
@@ -0,0 +1,892 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Printers turning [Type]s back into source code, usually C.
+//!
+//! Printers registered with the core (eg: `CoreTypePrinter`) are listed by
+//! [CoreTypePrinter::list], new ones can be added by implementing [TypePrinter] and calling
+//! [CoreTypePrinter::register].
+//!
+//! ```no_run
+//! # use binaryninja::binaryview::BinaryView;
+//! use binaryninja::typeprinter::{CoreTypePrinter, TokenEscapingType};
+//!
+//! # let bv: BinaryView = todo!();
+//! let printer = CoreTypePrinter::default_printer().unwrap();
+//! let header = printer
+//!     .print_all_types_in_view(&bv, 64, TokenEscapingType::NoTokenEscapingType)
+//!     .unwrap();
+//! std::fs::write("types.h", header).unwrap();
+//! ```
+
+use binaryninjacore_sys::*;
+
+pub use binaryninjacore_sys::BNTokenEscapingType as TokenEscapingType;
+pub use binaryninjacore_sys::BNTypeDefinitionLineType as TypeDefinitionLineType;
+
+use std::ffi::{c_char, c_int, c_void};
+use std::fmt;
+use std::mem;
+use std::ptr;
+use std::slice;
+
+use crate::binaryview::{BinaryView, BinaryViewExt};
+use crate::disassembly::InstructionTextToken;
+use crate::platform::Platform;
+use crate::rc::*;
+use crate::settings::Settings;
+use crate::string::*;
+use crate::typecontainer::TypeContainer;
+use crate::types::{NamedTypeReference, QualifiedName, Type};
+
+/// A line of the definition of a type, as printed by [TypePrinter::type_lines]
+#[derive(Clone)]
+pub struct TypeDefinitionLine {
+    pub line_type: TypeDefinitionLineType,
+    pub tokens: Vec<InstructionTextToken>,
+    /// Type described by the line
+    pub ty: Ref<Type>,
+    /// Type containing the line, for fields and members
+    pub parent_type: Option<Ref<Type>>,
+    /// Outermost type being printed
+    pub root_type: Option<Ref<Type>>,
+    pub root_type_name: Option<String>,
+    /// Base structure the field was inherited from
+    pub base_type: Option<Ref<NamedTypeReference>>,
+    pub base_offset: u64,
+    pub offset: u64,
+    pub field_index: usize,
+}
+
+impl TypeDefinitionLine {
+    pub(crate) unsafe fn from_raw(value: &BNTypeDefinitionLine) -> Self {
+        let tokens = if value.tokens.is_null() {
+            vec![]
+        } else {
+            slice::from_raw_parts(value.tokens, value.count)
+                .iter()
+                .map(|token| InstructionTextToken::from_raw(token).clone())
+                .collect()
+        };
+        let type_ref = |handle: *mut BNType| {
+            (!handle.is_null()).then(|| Type::ref_from_raw(BNNewTypeReference(handle)))
+        };
+        Self {
+            line_type: value.lineType,
+            tokens,
+            ty: Type::ref_from_raw(BNNewTypeReference(value.type_)),
+            parent_type: type_ref(value.parentType),
+            root_type: type_ref(value.rootType),
+            root_type_name: raw_to_string(value.rootTypeName),
+            base_type: (!value.baseType.is_null())
+                .then(|| NamedTypeReference::ref_from_raw(BNNewNamedTypeReference(value.baseType))),
+            base_offset: value.baseOffset,
+            offset: value.offset,
+            field_index: value.fieldIndex,
+        }
+    }
+
+    fn into_raw(self) -> BNTypeDefinitionLine {
+        let (tokens, count) = tokens_into_raw(self.tokens);
+        let type_raw = |ty: Option<Ref<Type>>| {
+            ty.map(|ty| unsafe { BNNewTypeReference(ty.handle) })
+                .unwrap_or(ptr::null_mut())
+        };
+        BNTypeDefinitionLine {
+            lineType: self.line_type,
+            tokens,
+            count,
+            type_: unsafe { BNNewTypeReference(self.ty.handle) },
+            parentType: type_raw(self.parent_type),
+            rootType: type_raw(self.root_type),
+            rootTypeName: self
+                .root_type_name
+                .map(|name| BnString::new(name).into_raw())
+                .unwrap_or(ptr::null_mut()),
+            baseType: self
+                .base_type
+                .map(|base| unsafe { BNNewNamedTypeReference(base.handle) })
+                .unwrap_or(ptr::null_mut()),
+            baseOffset: self.base_offset,
+            offset: self.offset,
+            fieldIndex: self.field_index,
+        }
+    }
+}
+
+/// A printer turning types into source code, either one registered with the core, see
+/// [CoreTypePrinter], or a custom one registered with [CoreTypePrinter::register]
+pub trait TypePrinter {
+    /// Tokens of the declaration of `name` with type `ty`, eg: `int32_t foo[4]`
+    ///
+    /// * `base_confidence` - confidence of the type, combined with the confidence of the tokens
+    fn type_tokens(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        name: &QualifiedName,
+        base_confidence: u8,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<InstructionTextToken>>;
+
+    /// Tokens of `ty` placed before the name in a declaration, eg: `int32_t` of `int32_t foo[4]`
+    ///
+    /// * `parent_type` - type containing `ty`, if any
+    fn type_tokens_before_name(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        base_confidence: u8,
+        parent_type: Option<&Type>,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<InstructionTextToken>>;
+
+    /// Tokens of `ty` placed after the name in a declaration, eg: `[4]` of `int32_t foo[4]`
+    fn type_tokens_after_name(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        base_confidence: u8,
+        parent_type: Option<&Type>,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<InstructionTextToken>>;
+
+    /// Text of the declaration of `name` with type `ty`, see [TypePrinter::type_tokens]
+    fn type_string(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        name: &QualifiedName,
+        escaping: TokenEscapingType,
+    ) -> Option<String>;
+
+    /// Text of `ty` placed before the name, see [TypePrinter::type_tokens_before_name]
+    fn type_string_before_name(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        escaping: TokenEscapingType,
+    ) -> Option<String>;
+
+    /// Text of `ty` placed after the name, see [TypePrinter::type_tokens_after_name]
+    fn type_string_after_name(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        escaping: TokenEscapingType,
+    ) -> Option<String>;
+
+    /// Lines of the full definition of `name` with type `ty`, as shown in the types view
+    ///
+    /// * `types` - types used to resolve the named type references of `ty`
+    /// * `padding_cols` - width of the column of the field types
+    /// * `collapsed` - if the padding between fields is collapsed into a single line
+    #[allow(clippy::too_many_arguments)]
+    fn type_lines(
+        &self,
+        ty: &Type,
+        types: &TypeContainer,
+        name: &QualifiedName,
+        padding_cols: usize,
+        collapsed: bool,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<TypeDefinitionLine>>;
+
+    /// Header declaring all of `types`, in dependency order
+    ///
+    /// Custom printers can delegate to [CoreTypePrinter::default_print_all_types] with their
+    /// own handle.
+    fn print_all_types(
+        &self,
+        types: &[(QualifiedName, Ref<Type>)],
+        data: &BinaryView,
+        padding_cols: usize,
+        escaping: TokenEscapingType,
+    ) -> Option<String>;
+}
+
+/// A type printer registered with the core
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CoreTypePrinter {
+    handle: *mut BNTypePrinter,
+}
+
+unsafe impl Send for CoreTypePrinter {}
+unsafe impl Sync for CoreTypePrinter {}
+
+impl CoreTypePrinter {
+    pub(crate) unsafe fn from_raw(handle: *mut BNTypePrinter) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// List of all registered type printers
+    pub fn list() -> Array<CoreTypePrinter> {
+        let mut count = 0;
+        let printers = unsafe { BNGetTypePrinterList(&mut count) };
+        assert!(!printers.is_null());
+        unsafe { Array::new(printers, count, ()) }
+    }
+
+    /// Retrieve a type printer by its name
+    pub fn by_name<S: BnStrCompatible>(name: S) -> Option<CoreTypePrinter> {
+        let name = name.into_bytes_with_nul();
+        let printer = unsafe { BNGetTypePrinterByName(name.as_ref().as_ptr() as *const c_char) };
+        (!printer.is_null()).then(|| unsafe { Self::from_raw(printer) })
+    }
+
+    /// The type printer selected by the `analysis.types.printerName` setting
+    pub fn default_printer() -> Option<CoreTypePrinter> {
+        let name = Settings::new("").get_string("analysis.types.printerName", None, None);
+        Self::by_name(name)
+    }
+
+    /// Register a new type printer, making it available for every user of the core.
+    pub fn register<S, T>(name: S, printer: T) -> CoreTypePrinter
+    where
+        S: BnStrCompatible,
+        T: TypePrinter + 'static + Sync,
+    {
+        let name = name.into_bytes_with_nul();
+        let ctxt = Box::into_raw(Box::new(printer));
+
+        let mut callbacks = BNTypePrinterCallbacks {
+            context: ctxt as *mut c_void,
+            getTypeTokens: Some(cb_get_type_tokens::<T>),
+            getTypeTokensBeforeName: Some(cb_get_type_tokens_before_name::<T>),
+            getTypeTokensAfterName: Some(cb_get_type_tokens_after_name::<T>),
+            getTypeString: Some(cb_get_type_string::<T>),
+            getTypeStringBeforeName: Some(cb_get_type_string_before_name::<T>),
+            getTypeStringAfterName: Some(cb_get_type_string_after_name::<T>),
+            getTypeLines: Some(cb_get_type_lines::<T>),
+            printAllTypes: Some(cb_print_all_types::<T>),
+            freeTokens: Some(cb_free_tokens),
+            freeString: Some(cb_free_string),
+            freeLines: Some(cb_free_lines),
+        };
+
+        unsafe {
+            Self::from_raw(BNRegisterTypePrinter(
+                name.as_ref().as_ptr() as *const c_char,
+                &mut callbacks,
+            ))
+        }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetTypePrinterName(self.handle)) }
+    }
+
+    /// Header declaring all the types of `view`, see [TypePrinter::print_all_types]
+    pub fn print_all_types_in_view(
+        &self,
+        view: &BinaryView,
+        padding_cols: usize,
+        escaping: TokenEscapingType,
+    ) -> Option<String> {
+        let types: Vec<_> = view
+            .types()
+            .iter()
+            .map(|t| (t.name().clone(), t.type_object().to_owned()))
+            .collect();
+        self.print_all_types(&types, view, padding_cols, escaping)
+    }
+
+    /// The implementation of [TypePrinter::print_all_types] provided by the core, printing the
+    /// types with the other methods of this printer
+    pub fn default_print_all_types(
+        &self,
+        types: &[(QualifiedName, Ref<Type>)],
+        data: &BinaryView,
+        padding_cols: usize,
+        escaping: TokenEscapingType,
+    ) -> Option<String> {
+        self.call_print_all_types(
+            BNTypePrinterDefaultPrintAllTypes,
+            types,
+            data,
+            padding_cols,
+            escaping,
+        )
+    }
+
+    fn call_print_all_types(
+        &self,
+        func: unsafe extern "C" fn(
+            *mut BNTypePrinter,
+            *mut BNQualifiedName,
+            *mut *mut BNType,
+            usize,
+            *mut BNBinaryView,
+            c_int,
+            BNTokenEscapingType,
+            *mut *mut c_char,
+        ) -> bool,
+        types: &[(QualifiedName, Ref<Type>)],
+        data: &BinaryView,
+        padding_cols: usize,
+        escaping: TokenEscapingType,
+    ) -> Option<String> {
+        let mut names: Vec<BNQualifiedName> = types.iter().map(|(name, _)| name.0).collect();
+        let mut types_raw: Vec<*mut BNType> = types.iter().map(|(_, ty)| ty.handle).collect();
+        let mut result = ptr::null_mut();
+        let success = unsafe {
+            func(
+                self.handle,
+                names.as_mut_ptr(),
+                types_raw.as_mut_ptr(),
+                types_raw.len(),
+                data.handle,
+                padding_cols as c_int,
+                escaping,
+                &mut result,
+            )
+        };
+        string_result(success, result)
+    }
+
+    fn call_type_tokens(
+        &self,
+        func: unsafe extern "C" fn(
+            *mut BNTypePrinter,
+            *mut BNType,
+            *mut BNPlatform,
+            u8,
+            *mut BNType,
+            BNTokenEscapingType,
+            *mut *mut BNInstructionTextToken,
+            *mut usize,
+        ) -> bool,
+        ty: &Type,
+        platform: Option<&Platform>,
+        base_confidence: u8,
+        parent_type: Option<&Type>,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<InstructionTextToken>> {
+        let mut result = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            func(
+                self.handle,
+                ty.handle,
+                platform_raw(platform),
+                base_confidence,
+                parent_type.map(|ty| ty.handle).unwrap_or(ptr::null_mut()),
+                escaping,
+                &mut result,
+                &mut count,
+            )
+        };
+        unsafe { tokens_result(success, result, count) }
+    }
+
+    fn call_type_string(
+        &self,
+        func: unsafe extern "C" fn(
+            *mut BNTypePrinter,
+            *mut BNType,
+            *mut BNPlatform,
+            BNTokenEscapingType,
+            *mut *mut c_char,
+        ) -> bool,
+        ty: &Type,
+        platform: Option<&Platform>,
+        escaping: TokenEscapingType,
+    ) -> Option<String> {
+        let mut result = ptr::null_mut();
+        let success = unsafe {
+            func(
+                self.handle,
+                ty.handle,
+                platform_raw(platform),
+                escaping,
+                &mut result,
+            )
+        };
+        string_result(success, result)
+    }
+}
+
+impl TypePrinter for CoreTypePrinter {
+    fn type_tokens(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        name: &QualifiedName,
+        base_confidence: u8,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<InstructionTextToken>> {
+        let mut result = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            BNGetTypePrinterTypeTokens(
+                self.handle,
+                ty.handle,
+                platform_raw(platform),
+                &name.0 as *const _ as *mut _,
+                base_confidence,
+                escaping,
+                &mut result,
+                &mut count,
+            )
+        };
+        unsafe { tokens_result(success, result, count) }
+    }
+
+    fn type_tokens_before_name(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        base_confidence: u8,
+        parent_type: Option<&Type>,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<InstructionTextToken>> {
+        self.call_type_tokens(
+            BNGetTypePrinterTypeTokensBeforeName,
+            ty,
+            platform,
+            base_confidence,
+            parent_type,
+            escaping,
+        )
+    }
+
+    fn type_tokens_after_name(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        base_confidence: u8,
+        parent_type: Option<&Type>,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<InstructionTextToken>> {
+        self.call_type_tokens(
+            BNGetTypePrinterTypeTokensAfterName,
+            ty,
+            platform,
+            base_confidence,
+            parent_type,
+            escaping,
+        )
+    }
+
+    fn type_string(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        name: &QualifiedName,
+        escaping: TokenEscapingType,
+    ) -> Option<String> {
+        let mut result = ptr::null_mut();
+        let success = unsafe {
+            BNGetTypePrinterTypeString(
+                self.handle,
+                ty.handle,
+                platform_raw(platform),
+                &name.0 as *const _ as *mut _,
+                escaping,
+                &mut result,
+            )
+        };
+        string_result(success, result)
+    }
+
+    fn type_string_before_name(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        escaping: TokenEscapingType,
+    ) -> Option<String> {
+        self.call_type_string(BNGetTypePrinterTypeStringBeforeName, ty, platform, escaping)
+    }
+
+    fn type_string_after_name(
+        &self,
+        ty: &Type,
+        platform: Option<&Platform>,
+        escaping: TokenEscapingType,
+    ) -> Option<String> {
+        self.call_type_string(BNGetTypePrinterTypeStringAfterName, ty, platform, escaping)
+    }
+
+    fn type_lines(
+        &self,
+        ty: &Type,
+        types: &TypeContainer,
+        name: &QualifiedName,
+        padding_cols: usize,
+        collapsed: bool,
+        escaping: TokenEscapingType,
+    ) -> Option<Vec<TypeDefinitionLine>> {
+        let mut result = ptr::null_mut();
+        let mut count = 0;
+        let success = unsafe {
+            BNGetTypePrinterTypeLines(
+                self.handle,
+                ty.handle,
+                types.as_raw(),
+                &name.0 as *const _ as *mut _,
+                padding_cols as c_int,
+                collapsed,
+                escaping,
+                &mut result,
+                &mut count,
+            )
+        };
+        if !success || result.is_null() {
+            return None;
+        }
+        let lines = unsafe { slice::from_raw_parts(result, count) }
+            .iter()
+            .map(|line| unsafe { TypeDefinitionLine::from_raw(line) })
+            .collect();
+        unsafe { BNFreeTypeDefinitionLineList(result, count) };
+        Some(lines)
+    }
+
+    fn print_all_types(
+        &self,
+        types: &[(QualifiedName, Ref<Type>)],
+        data: &BinaryView,
+        padding_cols: usize,
+        escaping: TokenEscapingType,
+    ) -> Option<String> {
+        self.call_print_all_types(
+            BNTypePrinterPrintAllTypes,
+            types,
+            data,
+            padding_cols,
+            escaping,
+        )
+    }
+}
+
+impl fmt::Debug for CoreTypePrinter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<type printer '{}'>", self.name())
+    }
+}
+
+impl CoreArrayProvider for CoreTypePrinter {
+    type Raw = *mut BNTypePrinter;
+    type Context = ();
+    type Wrapped<'a> = CoreTypePrinter;
+}
+
+unsafe impl CoreArrayProviderInner for CoreTypePrinter {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeTypePrinterList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::from_raw(*raw)
+    }
+}
+
+fn platform_raw(platform: Option<&Platform>) -> *mut BNPlatform {
+    platform
+        .map(|platform| platform.handle)
+        .unwrap_or(ptr::null_mut())
+}
+
+fn string_result(success: bool, result: *mut c_char) -> Option<String> {
+    (success && !result.is_null()).then(|| unsafe { BnString::from_raw(result) }.to_string())
+}
+
+/// Copy and free the list of tokens returned by the core
+unsafe fn tokens_result(
+    success: bool,
+    result: *mut BNInstructionTextToken,
+    count: usize,
+) -> Option<Vec<InstructionTextToken>> {
+    if !success || result.is_null() {
+        return None;
+    }
+    let tokens = slice::from_raw_parts(result, count)
+        .iter()
+        .map(|token| InstructionTextToken::from_raw(token).clone())
+        .collect();
+    BNFreeInstructionText(result, count);
+    Some(tokens)
+}
+
+fn tokens_into_raw(tokens: Vec<InstructionTextToken>) -> (*mut BNInstructionTextToken, usize) {
+    let tokens: Box<[BNInstructionTextToken]> = tokens
+        .into_iter()
+        .map(InstructionTextToken::into_raw)
+        .collect();
+    let count = tokens.len();
+    (Box::leak(tokens).as_mut_ptr(), count)
+}
+
+unsafe fn free_tokens(tokens: *mut BNInstructionTextToken, count: usize) {
+    let tokens = Box::from_raw(ptr::slice_from_raw_parts_mut(tokens, count));
+    for token in tokens.into_vec() {
+        drop(InstructionTextToken(token));
+    }
+}
+
+fn borrowed_platform(platform: *mut BNPlatform) -> Option<Platform> {
+    (!platform.is_null()).then_some(Platform { handle: platform })
+}
+
+unsafe fn borrowed_type(ty: &*mut BNType) -> Option<&Type> {
+    // Type is a transparent wrapper over the handle, the reference is borrowed from the core
+    (!ty.is_null()).then(|| mem::transmute::<&*mut BNType, &Type>(ty))
+}
+
+unsafe fn write_tokens(
+    tokens: Option<Vec<InstructionTextToken>>,
+    result: *mut *mut BNInstructionTextToken,
+    result_count: *mut usize,
+) -> bool {
+    match tokens {
+        Some(tokens) => {
+            (*result, *result_count) = tokens_into_raw(tokens);
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe fn write_string(value: Option<String>, result: *mut *mut c_char) -> bool {
+    match value {
+        Some(value) => {
+            *result = BnString::new(value).into_raw();
+            true
+        }
+        None => false,
+    }
+}
+
+extern "C" fn cb_get_type_tokens<T: TypePrinter>(
+    ctxt: *mut c_void,
+    ty: *mut BNType,
+    platform: *mut BNPlatform,
+    name: *mut BNQualifiedName,
+    base_confidence: u8,
+    escaping: BNTokenEscapingType,
+    result: *mut *mut BNInstructionTextToken,
+    result_count: *mut usize,
+) -> bool {
+    ffi_wrap!("TypePrinter::type_tokens", unsafe {
+        let printer = &*(ctxt as *const T);
+        let tokens = printer.type_tokens(
+            borrowed_type(&ty).unwrap(),
+            borrowed_platform(platform).as_ref(),
+            &*(name as *const QualifiedName),
+            base_confidence,
+            escaping,
+        );
+        write_tokens(tokens, result, result_count)
+    })
+}
+
+extern "C" fn cb_get_type_tokens_before_name<T: TypePrinter>(
+    ctxt: *mut c_void,
+    ty: *mut BNType,
+    platform: *mut BNPlatform,
+    base_confidence: u8,
+    parent_type: *mut BNType,
+    escaping: BNTokenEscapingType,
+    result: *mut *mut BNInstructionTextToken,
+    result_count: *mut usize,
+) -> bool {
+    ffi_wrap!("TypePrinter::type_tokens_before_name", unsafe {
+        let printer = &*(ctxt as *const T);
+        let tokens = printer.type_tokens_before_name(
+            borrowed_type(&ty).unwrap(),
+            borrowed_platform(platform).as_ref(),
+            base_confidence,
+            borrowed_type(&parent_type),
+            escaping,
+        );
+        write_tokens(tokens, result, result_count)
+    })
+}
+
+extern "C" fn cb_get_type_tokens_after_name<T: TypePrinter>(
+    ctxt: *mut c_void,
+    ty: *mut BNType,
+    platform: *mut BNPlatform,
+    base_confidence: u8,
+    parent_type: *mut BNType,
+    escaping: BNTokenEscapingType,
+    result: *mut *mut BNInstructionTextToken,
+    result_count: *mut usize,
+) -> bool {
+    ffi_wrap!("TypePrinter::type_tokens_after_name", unsafe {
+        let printer = &*(ctxt as *const T);
+        let tokens = printer.type_tokens_after_name(
+            borrowed_type(&ty).unwrap(),
+            borrowed_platform(platform).as_ref(),
+            base_confidence,
+            borrowed_type(&parent_type),
+            escaping,
+        );
+        write_tokens(tokens, result, result_count)
+    })
+}
+
+extern "C" fn cb_get_type_string<T: TypePrinter>(
+    ctxt: *mut c_void,
+    ty: *mut BNType,
+    platform: *mut BNPlatform,
+    name: *mut BNQualifiedName,
+    escaping: BNTokenEscapingType,
+    result: *mut *mut c_char,
+) -> bool {
+    ffi_wrap!("TypePrinter::type_string", unsafe {
+        let printer = &*(ctxt as *const T);
+        let value = printer.type_string(
+            borrowed_type(&ty).unwrap(),
+            borrowed_platform(platform).as_ref(),
+            &*(name as *const QualifiedName),
+            escaping,
+        );
+        write_string(value, result)
+    })
+}
+
+extern "C" fn cb_get_type_string_before_name<T: TypePrinter>(
+    ctxt: *mut c_void,
+    ty: *mut BNType,
+    platform: *mut BNPlatform,
+    escaping: BNTokenEscapingType,
+    result: *mut *mut c_char,
+) -> bool {
+    ffi_wrap!("TypePrinter::type_string_before_name", unsafe {
+        let printer = &*(ctxt as *const T);
+        let value = printer.type_string_before_name(
+            borrowed_type(&ty).unwrap(),
+            borrowed_platform(platform).as_ref(),
+            escaping,
+        );
+        write_string(value, result)
+    })
+}
+
+extern "C" fn cb_get_type_string_after_name<T: TypePrinter>(
+    ctxt: *mut c_void,
+    ty: *mut BNType,
+    platform: *mut BNPlatform,
+    escaping: BNTokenEscapingType,
+    result: *mut *mut c_char,
+) -> bool {
+    ffi_wrap!("TypePrinter::type_string_after_name", unsafe {
+        let printer = &*(ctxt as *const T);
+        let value = printer.type_string_after_name(
+            borrowed_type(&ty).unwrap(),
+            borrowed_platform(platform).as_ref(),
+            escaping,
+        );
+        write_string(value, result)
+    })
+}
+
+extern "C" fn cb_get_type_lines<T: TypePrinter>(
+    ctxt: *mut c_void,
+    ty: *mut BNType,
+    types: *mut BNTypeContainer,
+    name: *mut BNQualifiedName,
+    padding_cols: c_int,
+    collapsed: bool,
+    escaping: BNTokenEscapingType,
+    result: *mut *mut BNTypeDefinitionLine,
+    result_count: *mut usize,
+) -> bool {
+    ffi_wrap!("TypePrinter::type_lines", unsafe {
+        let printer = &*(ctxt as *const T);
+        let lines = printer.type_lines(
+            borrowed_type(&ty).unwrap(),
+            TypeContainer::ref_from_raw(&types),
+            &*(name as *const QualifiedName),
+            padding_cols.max(0) as usize,
+            collapsed,
+            escaping,
+        );
+        match lines {
+            Some(lines) => {
+                let lines: Box<[BNTypeDefinitionLine]> = lines
+                    .into_iter()
+                    .map(TypeDefinitionLine::into_raw)
+                    .collect();
+                *result_count = lines.len();
+                *result = Box::leak(lines).as_mut_ptr();
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+extern "C" fn cb_print_all_types<T: TypePrinter>(
+    ctxt: *mut c_void,
+    names: *mut BNQualifiedName,
+    types: *mut *mut BNType,
+    type_count: usize,
+    data: *mut BNBinaryView,
+    padding_cols: c_int,
+    escaping: BNTokenEscapingType,
+    result: *mut *mut c_char,
+) -> bool {
+    ffi_wrap!("TypePrinter::print_all_types", unsafe {
+        let printer = &*(ctxt as *const T);
+        let names = slice::from_raw_parts(names as *const QualifiedName, type_count);
+        let types: Vec<_> = slice::from_raw_parts(types, type_count)
+            .iter()
+            .zip(names)
+            .map(|(ty, name)| (name.clone(), Type::ref_from_raw(BNNewTypeReference(*ty))))
+            .collect();
+        let data = BinaryView { handle: data };
+        let value = printer.print_all_types(&types, &data, padding_cols.max(0) as usize, escaping);
+        write_string(value, result)
+    })
+}
+
+extern "C" fn cb_free_tokens(
+    _ctxt: *mut c_void,
+    tokens: *mut BNInstructionTextToken,
+    count: usize,
+) {
+    ffi_wrap!("TypePrinter::free_tokens", unsafe {
+        free_tokens(tokens, count)
+    })
+}
+
+extern "C" fn cb_free_string(_ctxt: *mut c_void, string: *mut c_char) {
+    ffi_wrap!("TypePrinter::free_string", unsafe {
+        drop(BnString::from_raw(string));
+    })
+}
+
+extern "C" fn cb_free_lines(_ctxt: *mut c_void, lines: *mut BNTypeDefinitionLine, count: usize) {
+    ffi_wrap!("TypePrinter::free_lines", unsafe {
+        let lines = Box::from_raw(ptr::slice_from_raw_parts_mut(lines, count));
+        for line in lines.iter() {
+            free_tokens(line.tokens, line.count);
+            for ty in [line.type_, line.parentType, line.rootType] {
+                if !ty.is_null() {
+                    BNFreeType(ty);
+                }
+            }
+            if !line.rootTypeName.is_null() {
+                drop(BnString::from_raw(line.rootTypeName));
+            }
+            if !line.baseType.is_null() {
+                BNFreeNamedTypeReference(line.baseType);
+            }
+        }
+    })
+}
@@ -1434,6 +1434,10 @@ impl Variable {
         Self::from_raw(unsafe { BNFromVariableIdentifier(var) })
     }
 
+    pub(crate) fn to_identifier(self) -> u64 {
+        unsafe { BNToVariableIdentifier(&self.raw()) }
+    }
+
     pub(crate) fn raw(&self) -> BNVariable {
         BNVariable {
             type_: self.t,
@@ -2555,6 +2559,14 @@ unsafe impl CoreArrayProviderInner for QualifiedName {
 pub struct QualifiedNameAndType(pub(crate) BNQualifiedNameAndType);
 
 impl QualifiedNameAndType {
+    pub fn new(name: QualifiedName, type_object: &Type) -> Self {
+        let name = mem::ManuallyDrop::new(name);
+        Self(BNQualifiedNameAndType {
+            name: name.0,
+            type_: unsafe { BNNewTypeReference(type_object.handle) },
+        })
+    }
+
     pub fn name(&self) -> &QualifiedName {
         unsafe { mem::transmute(&self.0.name) }
     }
@@ -2945,6 +2957,10 @@ impl ConstantData {
     pub(crate) fn new(function: Ref<Function>, value: RegisterValue) -> Self {
         Self { function, value }
     }
+
+    pub fn value(&self) -> RegisterValue {
+        self.value
+    }
 }
 
 // unsafe impl<S: BnStrCompatible> CoreArrayProvider for DataVariableAndName<S> {
@@ -3602,7 +3618,7 @@ pub type IntegerDisplayType = binaryninjacore_sys::BNIntegerDisplayType;
 
 #[derive(Debug, Clone)]
 pub struct StackVariableReference {
-    _source_operand: u32,
+    source_operand: u32,
     var_type: Conf<Ref<Type>>,
     name: BnString,
     var: Variable,
@@ -3621,7 +3637,7 @@ impl StackVariableReference {
         let offset = value.referencedOffset;
         let size = value.size;
         Self {
-            _source_operand: value.sourceOperand,
+            source_operand: value.sourceOperand,
             var_type,
             name,
             var,
@@ -3629,6 +3645,18 @@ impl StackVariableReference {
             size,
         }
     }
+    /// The raw reference borrows the name and type of `self`, it must not be freed
+    pub(crate) fn raw(&self) -> BNStackVariableReference {
+        BNStackVariableReference {
+            sourceOperand: self.source_operand,
+            type_: self.var_type.contents.handle,
+            typeConfidence: self.var_type.confidence,
+            name: self.name.as_raw() as *const c_char as *mut c_char,
+            varIdentifier: self.var.to_identifier(),
+            referencedOffset: self.offset,
+            size: self.size,
+        }
+    }
     pub fn variable(&self) -> &Variable {
         &self.var
     }
@@ -0,0 +1,585 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Websocket transports used by the core, the websocket counterpart of the
+//! [DownloadProvider](crate::downloadprovider::DownloadProvider).
+//!
+//! Connecting with the first registered provider:
+//!
+//! ```no_run
+//! use binaryninja::websocketprovider::*;
+//!
+//! struct Printer;
+//!
+//! impl WebsocketClientCallback for Printer {
+//!     fn read(&self, data: &[u8]) -> bool {
+//!         println!("{}", String::from_utf8_lossy(data));
+//!         true
+//!     }
+//! }
+//!
+//! let provider = CoreWebsocketProvider::list().get(0);
+//! let client = provider.create_client().unwrap();
+//! let headers: [(&str, &str); 0] = [];
+//! let connection = client.connect("ws://localhost:8080", headers, Printer).unwrap();
+//! connection.write(b"hello");
+//! ```
+//!
+//! New transports are added by implementing [WebsocketProvider] and [WebsocketClient], and
+//! registering them with [CoreWebsocketProvider::register].
+
+use binaryninjacore_sys::*;
+
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void};
+use std::fmt;
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use crate::rc::*;
+use crate::string::*;
+
+/// A websocket transport, creating a [WebsocketClient] for each connection
+pub trait WebsocketProvider: 'static + Sync {
+    type Client: WebsocketClient;
+
+    fn create_client(&self) -> Option<Self::Client>;
+}
+
+/// A connection of a [WebsocketProvider]
+///
+/// Events of the connection are reported back to the core through the `client` handle passed
+/// to [WebsocketClient::connect], see [CoreWebsocketClient::notify_read_data]. The reference to
+/// the handle must be dropped once disconnected for the client to be freed.
+pub trait WebsocketClient: 'static + Sync {
+    /// Connect to `host` asynchronously, calling [CoreWebsocketClient::notify_connect] once the
+    /// connection is established
+    fn connect(
+        &self,
+        client: &CoreWebsocketClient,
+        host: &str,
+        headers: &HashMap<String, String>,
+    ) -> bool;
+
+    fn write(&self, data: &[u8]) -> bool;
+
+    fn disconnect(&self) -> bool;
+}
+
+/// Receives the events of a connection opened with [CoreWebsocketClient::connect]
+pub trait WebsocketClientCallback: 'static + Send + Sync {
+    /// The connection is established, returning `false` closes it
+    fn connected(&self) -> bool {
+        true
+    }
+
+    /// The connection is closed, this is the last event of the connection
+    fn disconnected(&self) {}
+
+    fn error(&self, _msg: &str) {}
+
+    /// `data` was received, returning `false` closes the connection
+    fn read(&self, data: &[u8]) -> bool;
+}
+
+/// A websocket provider registered with the core
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CoreWebsocketProvider {
+    handle: *mut BNWebsocketProvider,
+}
+
+unsafe impl Send for CoreWebsocketProvider {}
+unsafe impl Sync for CoreWebsocketProvider {}
+
+impl CoreWebsocketProvider {
+    pub(crate) unsafe fn from_raw(handle: *mut BNWebsocketProvider) -> Self {
+        debug_assert!(!handle.is_null());
+
+        Self { handle }
+    }
+
+    /// List of all registered websocket providers
+    pub fn list() -> Array<CoreWebsocketProvider> {
+        let mut count = 0;
+        let providers = unsafe { BNGetWebsocketProviderList(&mut count) };
+        assert!(!providers.is_null());
+        unsafe { Array::new(providers, count, ()) }
+    }
+
+    /// Retrieve a websocket provider by its name
+    pub fn by_name<S: BnStrCompatible>(name: S) -> Option<CoreWebsocketProvider> {
+        let name = name.into_bytes_with_nul();
+        let provider =
+            unsafe { BNGetWebsocketProviderByName(name.as_ref().as_ptr() as *const c_char) };
+        (!provider.is_null()).then(|| unsafe { Self::from_raw(provider) })
+    }
+
+    /// Register a new websocket provider, making it available for every user of the core.
+    pub fn register<S, T>(name: S, provider: T) -> CoreWebsocketProvider
+    where
+        S: BnStrCompatible,
+        T: WebsocketProvider,
+    {
+        let name = name.into_bytes_with_nul();
+        let ctxt = Box::into_raw(Box::new(ProviderContext {
+            handle: ptr::null_mut(),
+            provider,
+        }));
+
+        let mut callbacks = BNWebsocketProviderCallbacks {
+            context: ctxt as *mut c_void,
+            createClient: Some(cb_create_client::<T>),
+        };
+
+        unsafe {
+            let handle = BNRegisterWebsocketProvider(
+                name.as_ref().as_ptr() as *const c_char,
+                &mut callbacks,
+            );
+            (*ctxt).handle = handle;
+            Self::from_raw(handle)
+        }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetWebsocketProviderName(self.handle)) }
+    }
+
+    pub fn create_client(&self) -> Option<Ref<CoreWebsocketClient>> {
+        let client = unsafe { BNCreateWebsocketProviderClient(self.handle) };
+        (!client.is_null()).then(|| unsafe { CoreWebsocketClient::ref_from_raw(client) })
+    }
+}
+
+impl fmt::Debug for CoreWebsocketProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<websocket provider '{}'>", self.name())
+    }
+}
+
+impl CoreArrayProvider for CoreWebsocketProvider {
+    type Raw = *mut BNWebsocketProvider;
+    type Context = ();
+    type Wrapped<'a> = CoreWebsocketProvider;
+}
+
+unsafe impl CoreArrayProviderInner for CoreWebsocketProvider {
+    unsafe fn free(raw: *mut Self::Raw, _count: usize, _context: &Self::Context) {
+        BNFreeWebsocketProviderList(raw)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, _context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Self::from_raw(*raw)
+    }
+}
+
+/// A websocket client, either created by the core or by a [WebsocketProvider] registered from
+/// Rust
+#[derive(PartialEq, Eq, Hash)]
+pub struct CoreWebsocketClient {
+    handle: *mut BNWebsocketClient,
+}
+
+unsafe impl Send for CoreWebsocketClient {}
+unsafe impl Sync for CoreWebsocketClient {}
+
+impl CoreWebsocketClient {
+    pub(crate) unsafe fn ref_from_raw(handle: *mut BNWebsocketClient) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+        Ref::new(Self { handle })
+    }
+
+    /// Connect to `url`, the events of the connection are sent to `callbacks` until the
+    /// disconnection is reported, which may happen after the returned connection is dropped
+    pub fn connect<U, HK, HV, I, C>(
+        &self,
+        url: U,
+        headers: I,
+        callbacks: C,
+    ) -> Option<WebsocketConnection<C>>
+    where
+        U: BnStrCompatible,
+        HK: BnStrCompatible,
+        HV: BnStrCompatible,
+        I: IntoIterator<Item = (HK, HV)>,
+        C: WebsocketClientCallback,
+    {
+        let url = url.into_bytes_with_nul();
+        let (header_keys, header_values): (Vec<_>, Vec<_>) = headers
+            .into_iter()
+            .map(|(key, value)| (key.into_bytes_with_nul(), value.into_bytes_with_nul()))
+            .unzip();
+        let header_key_ptrs: Vec<_> = header_keys
+            .iter()
+            .map(|key| key.as_ref().as_ptr() as *const c_char)
+            .collect();
+        let header_value_ptrs: Vec<_> = header_values
+            .iter()
+            .map(|value| value.as_ref().as_ptr() as *const c_char)
+            .collect();
+
+        // the core holds its own reference to the callbacks, released by `cb_disconnected`
+        let callbacks = Arc::new(callbacks);
+        let ctxt = Arc::into_raw(callbacks.clone());
+        let mut output_callbacks = BNWebsocketClientOutputCallbacks {
+            context: ctxt as *mut c_void,
+            connectedCallback: Some(cb_connected::<C>),
+            disconnectedCallback: Some(cb_disconnected::<C>),
+            errorCallback: Some(cb_error::<C>),
+            readCallback: Some(cb_read::<C>),
+        };
+
+        let success = unsafe {
+            BNConnectWebsocketClient(
+                self.handle,
+                url.as_ref().as_ptr() as *const c_char,
+                header_key_ptrs.len() as u64,
+                header_key_ptrs.as_ptr(),
+                header_value_ptrs.as_ptr(),
+                &mut output_callbacks,
+            )
+        };
+
+        if !success {
+            unsafe { drop(Arc::from_raw(ctxt)) };
+            return None;
+        }
+
+        Some(WebsocketConnection {
+            client: self.to_owned(),
+            callbacks,
+        })
+    }
+
+    pub fn write(&self, data: &[u8]) -> bool {
+        unsafe { BNWriteWebsocketClientData(self.handle, data.as_ptr(), data.len() as u64) != 0 }
+    }
+
+    pub fn disconnect(&self) -> bool {
+        unsafe { BNDisconnectWebsocketClient(self.handle) }
+    }
+
+    /// Report that the connection is established, returns `false` if it must be closed
+    pub fn notify_connect(&self) -> bool {
+        unsafe { BNNotifyWebsocketClientConnect(self.handle) }
+    }
+
+    pub fn notify_disconnect(&self) {
+        unsafe { BNNotifyWebsocketClientDisconnect(self.handle) }
+    }
+
+    pub fn notify_error<S: BnStrCompatible>(&self, msg: S) {
+        let msg = msg.into_bytes_with_nul();
+        unsafe { BNNotifyWebsocketClientError(self.handle, msg.as_ref().as_ptr() as *const c_char) }
+    }
+
+    /// Report that `data` was received, returns `false` if the connection must be closed
+    pub fn notify_read_data(&self, data: &[u8]) -> bool {
+        unsafe {
+            BNNotifyWebsocketClientReadData(
+                self.handle,
+                data.as_ptr() as *mut u8,
+                data.len() as u64,
+            )
+        }
+    }
+}
+
+impl ToOwned for CoreWebsocketClient {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for CoreWebsocketClient {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewWebsocketClientReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeWebsocketClient(handle.handle);
+    }
+}
+
+/// A connection opened with [CoreWebsocketClient::connect], disconnected when dropped
+///
+/// The callbacks are kept alive until [WebsocketClientCallback::disconnected] is called, so
+/// they can still receive the events sent by the transport while it closes the connection.
+pub struct WebsocketConnection<C: WebsocketClientCallback> {
+    client: Ref<CoreWebsocketClient>,
+    callbacks: Arc<C>,
+}
+
+impl<C: WebsocketClientCallback> WebsocketConnection<C> {
+    pub fn client(&self) -> &CoreWebsocketClient {
+        &self.client
+    }
+
+    pub fn callbacks(&self) -> &C {
+        &self.callbacks
+    }
+
+    pub fn write(&self, data: &[u8]) -> bool {
+        self.client.write(data)
+    }
+}
+
+impl<C: WebsocketClientCallback> Drop for WebsocketConnection<C> {
+    fn drop(&mut self) {
+        self.client.disconnect();
+    }
+}
+
+struct ProviderContext<T: WebsocketProvider> {
+    handle: *mut BNWebsocketProvider,
+    provider: T,
+}
+
+struct ClientContext<C: WebsocketClient> {
+    handle: *mut BNWebsocketClient,
+    client: C,
+}
+
+extern "C" fn cb_create_client<T: WebsocketProvider>(ctxt: *mut c_void) -> *mut BNWebsocketClient {
+    ffi_wrap!("WebsocketProvider::create_client", unsafe {
+        let ctxt = &*(ctxt as *const ProviderContext<T>);
+        let Some(client) = ctxt.provider.create_client() else {
+            return ptr::null_mut();
+        };
+
+        let client_ctxt = Box::into_raw(Box::new(ClientContext {
+            handle: ptr::null_mut(),
+            client,
+        }));
+        let mut callbacks = BNWebsocketClientCallbacks {
+            context: client_ctxt as *mut c_void,
+            destroyClient: Some(cb_destroy_client::<T::Client>),
+            connect: Some(cb_connect::<T::Client>),
+            write: Some(cb_write::<T::Client>),
+            disconnect: Some(cb_disconnect::<T::Client>),
+        };
+
+        // The reference returned by the core is handed over to the caller, the context is
+        // freed by `destroyClient` once the last reference is released
+        let handle = BNInitWebsocketClient(ctxt.handle, &mut callbacks);
+        (*client_ctxt).handle = handle;
+        handle
+    })
+}
+
+extern "C" fn cb_destroy_client<C: WebsocketClient>(ctxt: *mut c_void) {
+    ffi_wrap!("WebsocketClient::destroy", unsafe {
+        drop(Box::from_raw(ctxt as *mut ClientContext<C>));
+    })
+}
+
+extern "C" fn cb_connect<C: WebsocketClient>(
+    ctxt: *mut c_void,
+    host: *const c_char,
+    header_count: u64,
+    header_keys: *const *const c_char,
+    header_values: *const *const c_char,
+) -> bool {
+    ffi_wrap!("WebsocketClient::connect", unsafe {
+        let ctxt = &*(ctxt as *const ClientContext<C>);
+        let mut headers = HashMap::new();
+        if header_count > 0 {
+            let keys = slice::from_raw_parts(header_keys, header_count as usize);
+            let values = slice::from_raw_parts(header_values, header_count as usize);
+            for (key, value) in keys.iter().zip(values) {
+                headers.insert(
+                    str_from_raw(*key).into_owned(),
+                    str_from_raw(*value).into_owned(),
+                );
+            }
+        }
+
+        let handle = CoreWebsocketClient {
+            handle: ctxt.handle,
+        };
+        ctxt.client.connect(&handle, &str_from_raw(host), &headers)
+    })
+}
+
+extern "C" fn cb_write<C: WebsocketClient>(data: *const u8, len: u64, ctxt: *mut c_void) -> bool {
+    ffi_wrap!("WebsocketClient::write", unsafe {
+        let ctxt = &*(ctxt as *const ClientContext<C>);
+        ctxt.client.write(slice::from_raw_parts(data, len as usize))
+    })
+}
+
+extern "C" fn cb_disconnect<C: WebsocketClient>(ctxt: *mut c_void) -> bool {
+    ffi_wrap!("WebsocketClient::disconnect", unsafe {
+        let ctxt = &*(ctxt as *const ClientContext<C>);
+        ctxt.client.disconnect()
+    })
+}
+
+extern "C" fn cb_connected<C: WebsocketClientCallback>(ctxt: *mut c_void) -> bool {
+    ffi_wrap!("WebsocketClientCallback::connected", unsafe {
+        let callbacks = &*(ctxt as *const C);
+        callbacks.connected()
+    })
+}
+
+extern "C" fn cb_disconnected<C: WebsocketClientCallback>(ctxt: *mut c_void) {
+    ffi_wrap!("WebsocketClientCallback::disconnected", unsafe {
+        // this is the last event of the connection, release the reference of the core
+        let callbacks = Arc::from_raw(ctxt as *const C);
+        callbacks.disconnected()
+    })
+}
+
+extern "C" fn cb_error<C: WebsocketClientCallback>(msg: *const c_char, ctxt: *mut c_void) {
+    ffi_wrap!("WebsocketClientCallback::error", unsafe {
+        let callbacks = &*(ctxt as *const C);
+        callbacks.error(&str_from_raw(msg))
+    })
+}
+
+extern "C" fn cb_read<C: WebsocketClientCallback>(
+    data: *mut u8,
+    len: u64,
+    ctxt: *mut c_void,
+) -> bool {
+    ffi_wrap!("WebsocketClientCallback::read", unsafe {
+        let callbacks = &*(ctxt as *const C);
+        callbacks.read(slice::from_raw_parts(data, len as usize))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::sync::{mpsc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::rc::Ref;
+
+    use super::*;
+
+    const TEST_PROVIDER: &str = "RustTestEchoProvider";
+    const NO_HEADERS: [(&str, &str); 0] = [];
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Connected,
+        Disconnected,
+        Read(Vec<u8>),
+    }
+
+    struct Recorder(Mutex<mpsc::Sender<Event>>);
+
+    impl WebsocketClientCallback for Recorder {
+        fn connected(&self) -> bool {
+            self.0.lock().unwrap().send(Event::Connected).is_ok()
+        }
+
+        fn disconnected(&self) {
+            let _ = self.0.lock().unwrap().send(Event::Disconnected);
+        }
+
+        fn read(&self, data: &[u8]) -> bool {
+            self.0
+                .lock()
+                .unwrap()
+                .send(Event::Read(data.to_vec()))
+                .is_ok()
+        }
+    }
+
+    /// Transport echoing back everything written to it, the events are sent from another thread
+    /// like a network transport would
+    struct EchoProvider;
+
+    #[derive(Default)]
+    struct EchoClient {
+        handle: Mutex<Option<Ref<CoreWebsocketClient>>>,
+    }
+
+    impl WebsocketProvider for EchoProvider {
+        type Client = EchoClient;
+
+        fn create_client(&self) -> Option<EchoClient> {
+            Some(EchoClient::default())
+        }
+    }
+
+    impl WebsocketClient for EchoClient {
+        fn connect(
+            &self,
+            client: &CoreWebsocketClient,
+            _host: &str,
+            _headers: &HashMap<String, String>,
+        ) -> bool {
+            *self.handle.lock().unwrap() = Some(client.to_owned());
+            let client = client.to_owned();
+            thread::spawn(move || client.notify_connect());
+            true
+        }
+
+        fn write(&self, data: &[u8]) -> bool {
+            let Some(client) = self.handle.lock().unwrap().clone() else {
+                return false;
+            };
+            let data = data.to_vec();
+            thread::spawn(move || client.notify_read_data(&data));
+            true
+        }
+
+        fn disconnect(&self) -> bool {
+            let Some(client) = self.handle.lock().unwrap().take() else {
+                return false;
+            };
+            thread::spawn(move || client.notify_disconnect());
+            true
+        }
+    }
+
+    #[test]
+    fn custom_provider() {
+        crate::headless::init();
+
+        let provider = CoreWebsocketProvider::register(TEST_PROVIDER, EchoProvider);
+        assert_eq!(provider.name().as_str(), TEST_PROVIDER);
+        assert_eq!(
+            CoreWebsocketProvider::by_name(TEST_PROVIDER),
+            Some(provider)
+        );
+
+        let (sender, events) = mpsc::channel();
+        let client = provider.create_client().unwrap();
+        let connection = client
+            .connect("ws://localhost", NO_HEADERS, Recorder(Mutex::new(sender)))
+            .unwrap();
+        assert_eq!(events.recv_timeout(TIMEOUT), Ok(Event::Connected));
+
+        assert!(connection.write(b"hello"));
+        assert_eq!(
+            events.recv_timeout(TIMEOUT),
+            Ok(Event::Read(b"hello".to_vec()))
+        );
+
+        // the disconnection is still reported once the connection is dropped
+        drop(connection);
+        assert_eq!(events.recv_timeout(TIMEOUT), Ok(Event::Disconnected));
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Queueing work on the worker threads of the core, keeping long running tasks off the UI thread.
+//!
+//! ```no_run
+//! use binaryninja::worker;
+//!
+//! worker::enqueue("example", || println!("on a worker thread"));
+//!
+//! worker::enqueue_background_task("Counting...", true, |task| {
+//!     for i in 0..1000 {
+//!         if task.is_cancelled() {
+//!             break;
+//!         }
+//!         task.set_progress_text(format!("Counting... {}/1000", i));
+//!     }
+//! })
+//! .unwrap();
+//! ```
+
+use binaryninjacore_sys::*;
+
+use std::ffi::{c_char, c_void};
+
+use crate::backgroundtask::{self, BackgroundTask};
+use crate::rc::*;
+use crate::string::*;
+
+/// Queue `action` to be run on a worker thread
+pub fn enqueue<S, F>(name: S, action: F)
+where
+    S: BnStrCompatible,
+    F: FnOnce() + Send + 'static,
+{
+    let name = name.into_bytes_with_nul();
+    let ctxt = Box::into_raw(Box::new(action));
+    unsafe {
+        BNWorkerEnqueueNamed(
+            ctxt as *mut c_void,
+            Some(cb_action::<F>),
+            name.as_ref().as_ptr() as *const c_char,
+        )
+    }
+}
+
+/// Queue `action` to be run on a worker thread, ahead of the actions queued with [enqueue]
+pub fn priority_enqueue<S, F>(name: S, action: F)
+where
+    S: BnStrCompatible,
+    F: FnOnce() + Send + 'static,
+{
+    let name = name.into_bytes_with_nul();
+    let ctxt = Box::into_raw(Box::new(action));
+    unsafe {
+        BNWorkerPriorityEnqueueNamed(
+            ctxt as *mut c_void,
+            Some(cb_action::<F>),
+            name.as_ref().as_ptr() as *const c_char,
+        )
+    }
+}
+
+/// Queue `action` to be run on a worker thread reserved for tasks the user is waiting on
+pub fn interactive_enqueue<S, F>(name: S, action: F)
+where
+    S: BnStrCompatible,
+    F: FnOnce() + Send + 'static,
+{
+    let name = name.into_bytes_with_nul();
+    let ctxt = Box::into_raw(Box::new(action));
+    unsafe {
+        BNWorkerInteractiveEnqueueNamed(
+            ctxt as *mut c_void,
+            Some(cb_action::<F>),
+            name.as_ref().as_ptr() as *const c_char,
+        )
+    }
+}
+
+/// Start a [BackgroundTask] and queue `action` to be run with it on a worker thread
+///
+/// The task is finished once `action` returns or panics, `action` is responsible for reporting
+/// progress and checking [BackgroundTask::is_cancelled].
+pub fn enqueue_background_task<S, F>(
+    initial_text: S,
+    can_cancel: bool,
+    action: F,
+) -> backgroundtask::Result<Ref<BackgroundTask>>
+where
+    S: BnStrCompatible,
+    F: FnOnce(&BackgroundTask) + Send + 'static,
+{
+    // finishes the task even if `action` panics
+    struct FinishGuard(Ref<BackgroundTask>);
+
+    impl Drop for FinishGuard {
+        fn drop(&mut self) {
+            self.0.finish();
+        }
+    }
+
+    let task = BackgroundTask::new(initial_text, can_cancel)?;
+    let guard = FinishGuard(task.clone());
+    enqueue(task.get_progress_text(), move || action(&guard.0));
+    Ok(task)
+}
+
+pub fn thread_count() -> usize {
+    unsafe { BNGetWorkerThreadCount() }
+}
+
+pub fn set_thread_count(count: usize) {
+    unsafe { BNSetWorkerThreadCount(count) }
+}
+
+extern "C" fn cb_action<F: FnOnce() + Send + 'static>(ctxt: *mut c_void) {
+    ffi_wrap!("worker::enqueue", unsafe {
+        let action = Box::from_raw(ctxt as *mut F);
+        action()
+    })
+}
@@ -0,0 +1,601 @@
+// Copyright 2021-2024 Vector 35 Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Analysis pipeline customization through workflows and activities.
+//!
+//! A [Workflow] is an ordered tree of activities that the core executes when analyzing a
+//! [Function]. Plugins can clone one of the registered workflows (usually `core.function.defaultAnalysis`),
+//! insert their own [Activity] implementations at any point in the pipeline, and register the
+//! result under a new name so it can be selected through the `analysis.workflows.functionWorkflow` setting.
+//!
+//! ```no_run
+//! use binaryninja::workflow::{AnalysisContext, CoreActivity, Workflow};
+//!
+//! const CONFIG: &str = r#"{
+//!     "name": "extension.example.cleanup",
+//!     "title": "Example Cleanup",
+//!     "description": "Runs after the LLIL is generated",
+//!     "eligibility": { "auto": { "default": true } }
+//! }"#;
+//!
+//! #[no_mangle]
+//! pub extern "C" fn CorePluginInit() -> bool {
+//!     let workflow = Workflow::instance("core.function.defaultAnalysis").clone_to("ExampleWorkflow");
+//!     let activity = CoreActivity::new(CONFIG, |context: &AnalysisContext| {
+//!         if let Some(llil) = context.llil_function() {
+//!             // inspect or rewrite the IL here, then hand it back with `set_llil_function`
+//!             let _ = llil.instruction_count();
+//!         }
+//!     });
+//!     workflow.register_activity(&activity).unwrap();
+//!     workflow.insert("core.function.generateMediumLevelIL", ["extension.example.cleanup"]);
+//!     workflow.register().is_ok()
+//! }
+//! ```
+
+use binaryninjacore_sys::*;
+
+use std::ffi::{c_char, c_void};
+
+use crate::architecture::CoreArchitecture;
+use crate::basicblock::BasicBlock;
+use crate::flowgraph::FlowGraph;
+use crate::function::{Function, NativeBlock};
+use crate::hlil::HighLevelILFunction;
+use crate::llil;
+use crate::mlil::MediumLevelILFunction;
+use crate::rc::*;
+use crate::string::*;
+
+/// The state the core hands to an [Activity] while it is analyzing a single [Function].
+///
+/// The IL accessors return the IL as it is at this point of the pipeline, the setters replace
+/// the IL the remaining activities will operate on.
+pub struct AnalysisContext {
+    handle: *mut BNAnalysisContext,
+}
+
+impl AnalysisContext {
+    pub(crate) unsafe fn from_raw(handle: *mut BNAnalysisContext) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+
+        Ref::new(Self { handle })
+    }
+
+    /// Create an empty context, mainly useful to drive an [Activity] outside of the pipeline.
+    pub fn new() -> Ref<Self> {
+        unsafe { Self::from_raw(BNCreateAnalysisContext()) }
+    }
+
+    /// The [Function] currently being analyzed
+    pub fn function(&self) -> Ref<Function> {
+        unsafe {
+            let func = BNAnalysisContextGetFunction(self.handle);
+            Function::from_raw(func)
+        }
+    }
+
+    /// The Low Level IL of the current [Function], if it was already generated
+    pub fn llil_function(&self) -> Option<Ref<llil::RegularFunction<CoreArchitecture>>> {
+        let llil = unsafe { BNAnalysisContextGetLowLevelILFunction(self.handle) };
+        (!llil.is_null())
+            .then(|| unsafe { llil::RegularFunction::from_raw(self.function().arch(), llil) })
+    }
+
+    /// The Medium Level IL of the current [Function], if it was already generated
+    pub fn mlil_function(&self) -> Option<Ref<MediumLevelILFunction>> {
+        let mlil = unsafe { BNAnalysisContextGetMediumLevelILFunction(self.handle) };
+        (!mlil.is_null()).then(|| unsafe { MediumLevelILFunction::ref_from_raw(mlil) })
+    }
+
+    /// The High Level IL of the current [Function], if it was already generated
+    pub fn hlil_function(&self, full_ast: bool) -> Option<Ref<HighLevelILFunction>> {
+        let hlil = unsafe { BNAnalysisContextGetHighLevelILFunction(self.handle) };
+        (!hlil.is_null()).then(|| unsafe { HighLevelILFunction::ref_from_raw(hlil, full_ast) })
+    }
+
+    /// Replace the basic blocks of the current [Function]
+    pub fn set_basic_blocks<I>(&self, blocks: I)
+    where
+        I: IntoIterator<Item = BasicBlock<NativeBlock>>,
+    {
+        let mut blocks: Vec<*mut BNBasicBlock> = blocks.into_iter().map(|b| b.handle).collect();
+        unsafe { BNSetBasicBlockList(self.handle, blocks.as_mut_ptr(), blocks.len()) }
+    }
+
+    /// Replace the Lifted IL of the current [Function]
+    pub fn set_lifted_il_function(&self, lifted_il: &llil::LiftedFunction<CoreArchitecture>) {
+        unsafe { BNSetLiftedILFunction(self.handle, lifted_il.handle) }
+    }
+
+    /// Replace the Low Level IL of the current [Function]
+    pub fn set_llil_function(&self, llil: &llil::RegularFunction<CoreArchitecture>) {
+        unsafe { BNSetLowLevelILFunction(self.handle, llil.handle) }
+    }
+
+    /// Replace the Medium Level IL of the current [Function]
+    pub fn set_mlil_function(&self, mlil: &MediumLevelILFunction) {
+        unsafe { BNSetMediumLevelILFunction(self.handle, mlil.handle) }
+    }
+
+    /// Replace the High Level IL of the current [Function]
+    pub fn set_hlil_function(&self, hlil: &HighLevelILFunction) {
+        unsafe { BNSetHighLevelILFunction(self.handle, hlil.handle) }
+    }
+
+    /// Send a JSON encoded request to the analysis pipeline, returns whether it was accepted
+    pub fn inform<S: BnStrCompatible>(&self, request: S) -> bool {
+        let request = request.into_bytes_with_nul();
+        unsafe { BNAnalysisContextInform(self.handle, request.as_ref().as_ptr() as *const c_char) }
+    }
+}
+
+impl ToOwned for AnalysisContext {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for AnalysisContext {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewAnalysisContextReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeAnalysisContext(handle.handle);
+    }
+}
+
+/// The trait required for custom analysis activities. See [CoreActivity::new] for example usage.
+pub trait Activity: 'static + Sync {
+    fn action(&self, context: &AnalysisContext);
+}
+
+impl<T> Activity for T
+where
+    T: 'static + Sync + Fn(&AnalysisContext),
+{
+    fn action(&self, context: &AnalysisContext) {
+        self(context)
+    }
+}
+
+/// An activity known by the core, either built-in or created through [CoreActivity::new].
+pub struct CoreActivity {
+    handle: *mut BNActivity,
+}
+
+unsafe impl Send for CoreActivity {}
+unsafe impl Sync for CoreActivity {}
+
+impl CoreActivity {
+    pub(crate) unsafe fn from_raw(handle: *mut BNActivity) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+
+        Ref::new(Self { handle })
+    }
+
+    /// Create a new activity from a JSON `configuration`, the `name` key of the configuration
+    /// is the name used to reference the activity inside a [Workflow].
+    ///
+    /// ```no_run
+    /// # use binaryninja::workflow::{AnalysisContext, CoreActivity};
+    /// let activity = CoreActivity::new(
+    ///     r#"{"name": "extension.example.activity", "title": "Example", "description": "", "eligibility": {}}"#,
+    ///     |context: &AnalysisContext| println!("analyzing {:?}", context.function()),
+    /// );
+    /// ```
+    pub fn new<S, A>(configuration: S, activity: A) -> Ref<Self>
+    where
+        S: BnStrCompatible,
+        A: Activity,
+    {
+        extern "C" fn cb_action<A>(ctxt: *mut c_void, analysis: *mut BNAnalysisContext)
+        where
+            A: Activity,
+        {
+            ffi_wrap!("Activity::action", unsafe {
+                let activity = &*(ctxt as *const A);
+
+                debug_assert!(!analysis.is_null());
+                let context = AnalysisContext { handle: analysis };
+
+                activity.action(&context);
+            })
+        }
+
+        let configuration = configuration.into_bytes_with_nul();
+        let ctxt = Box::into_raw(Box::new(activity));
+
+        unsafe {
+            Self::from_raw(BNCreateActivity(
+                configuration.as_ref().as_ptr() as *const c_char,
+                ctxt as *mut c_void,
+                Some(cb_action::<A>),
+            ))
+        }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNActivityGetName(self.handle)) }
+    }
+}
+
+impl ToOwned for CoreActivity {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for CoreActivity {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewActivityReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeActivity(handle.handle);
+    }
+}
+
+/// An ordered tree of activities the core executes to analyze a [Function].
+///
+/// Registered workflows are immutable, to modify one [Workflow::clone_to] it first, make the
+/// changes and [Workflow::register] the copy under a new name.
+#[derive(PartialEq, Eq, Hash)]
+pub struct Workflow {
+    handle: *mut BNWorkflow,
+}
+
+unsafe impl Send for Workflow {}
+unsafe impl Sync for Workflow {}
+
+impl Workflow {
+    pub(crate) unsafe fn from_raw(handle: *mut BNWorkflow) -> Ref<Self> {
+        debug_assert!(!handle.is_null());
+
+        Ref::new(Self { handle })
+    }
+
+    /// Create a new, empty and unregistered workflow
+    pub fn new<S: BnStrCompatible>(name: S) -> Ref<Self> {
+        let name = name.into_bytes_with_nul();
+        unsafe { Self::from_raw(BNCreateWorkflow(name.as_ref().as_ptr() as *const c_char)) }
+    }
+
+    /// Get the workflow registered with `name`, if no such workflow exists a new
+    /// unregistered one is created
+    pub fn instance<S: BnStrCompatible>(name: S) -> Ref<Self> {
+        let name = name.into_bytes_with_nul();
+        unsafe { Self::from_raw(BNWorkflowInstance(name.as_ref().as_ptr() as *const c_char)) }
+    }
+
+    /// List of all the registered workflows
+    pub fn list() -> Array<Workflow> {
+        let mut count = 0;
+        let workflows = unsafe { BNGetWorkflowList(&mut count) };
+        assert!(!workflows.is_null());
+        unsafe { Array::new(workflows, count, ()) }
+    }
+
+    pub fn name(&self) -> BnString {
+        unsafe { BnString::from_raw(BNGetWorkflowName(self.handle)) }
+    }
+
+    /// Make a new unregistered copy of this workflow named `name`
+    pub fn clone_to<S: BnStrCompatible>(&self, name: S) -> Ref<Self> {
+        self.clone_to_with_root(name, "")
+    }
+
+    /// Make a new unregistered copy of this workflow named `name`, only including the
+    /// activities under `root_activity`
+    pub fn clone_to_with_root<S: BnStrCompatible, A: BnStrCompatible>(
+        &self,
+        name: S,
+        root_activity: A,
+    ) -> Ref<Self> {
+        let name = name.into_bytes_with_nul();
+        let root_activity = root_activity.into_bytes_with_nul();
+        unsafe {
+            Self::from_raw(BNWorkflowClone(
+                self.handle,
+                name.as_ref().as_ptr() as *const c_char,
+                root_activity.as_ref().as_ptr() as *const c_char,
+            ))
+        }
+    }
+
+    /// Register this workflow, making it immutable and available for use
+    pub fn register(&self) -> Result<(), ()> {
+        self.register_with_config("")
+    }
+
+    /// Register this workflow with a JSON `config`, making it immutable and available for use
+    pub fn register_with_config<S: BnStrCompatible>(&self, config: S) -> Result<(), ()> {
+        let config = config.into_bytes_with_nul();
+        if unsafe { BNRegisterWorkflow(self.handle, config.as_ref().as_ptr() as *const c_char) } {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    /// Add `activity` to the activities known by this workflow, this does not add it to the
+    /// execution pipeline, use [Workflow::insert] or [Workflow::assign_subactivities] for that
+    pub fn register_activity(&self, activity: &CoreActivity) -> Result<Ref<CoreActivity>, ()> {
+        self.register_activity_with_subactivities::<Vec<String>>(activity, vec![])
+    }
+
+    /// Add `activity` to the activities known by this workflow with `subactivities` as children
+    pub fn register_activity_with_subactivities<I>(
+        &self,
+        activity: &CoreActivity,
+        subactivities: I,
+    ) -> Result<Ref<CoreActivity>, ()>
+    where
+        I: IntoIterator,
+        I::Item: BnStrCompatible,
+    {
+        let subactivities_raw: Vec<_> = subactivities
+            .into_iter()
+            .map(|a| a.into_bytes_with_nul())
+            .collect();
+        let mut subactivities_ptr: Vec<*const c_char> = subactivities_raw
+            .iter()
+            .map(|a| a.as_ref().as_ptr() as *const c_char)
+            .collect();
+        let result = unsafe {
+            BNWorkflowRegisterActivity(
+                self.handle,
+                activity.handle,
+                subactivities_ptr.as_mut_ptr(),
+                subactivities_ptr.len(),
+            )
+        };
+        if result.is_null() {
+            Err(())
+        } else {
+            Ok(unsafe { CoreActivity::from_raw(result) })
+        }
+    }
+
+    /// Whether this workflow contains `activity`
+    pub fn contains<S: BnStrCompatible>(&self, activity: S) -> bool {
+        let activity = activity.into_bytes_with_nul();
+        unsafe { BNWorkflowContains(self.handle, activity.as_ref().as_ptr() as *const c_char) }
+    }
+
+    /// The JSON configuration of this workflow
+    pub fn configuration(&self) -> BnString {
+        self.configuration_for_activity("")
+    }
+
+    /// The JSON configuration of this workflow starting at `activity`
+    pub fn configuration_for_activity<S: BnStrCompatible>(&self, activity: S) -> BnString {
+        let activity = activity.into_bytes_with_nul();
+        unsafe {
+            BnString::from_raw(BNWorkflowGetConfiguration(
+                self.handle,
+                activity.as_ref().as_ptr() as *const c_char,
+            ))
+        }
+    }
+
+    /// Whether this workflow is registered, registered workflows can't be modified
+    pub fn registered(&self) -> bool {
+        unsafe { BNWorkflowIsRegistered(self.handle) }
+    }
+
+    /// Number of activities in this workflow
+    pub fn size(&self) -> usize {
+        unsafe { BNWorkflowSize(self.handle) }
+    }
+
+    /// Retrieve the activity named `activity`
+    pub fn activity<S: BnStrCompatible>(&self, activity: S) -> Option<Ref<CoreActivity>> {
+        let activity = activity.into_bytes_with_nul();
+        let result = unsafe {
+            BNWorkflowGetActivity(self.handle, activity.as_ref().as_ptr() as *const c_char)
+        };
+        (!result.is_null()).then(|| unsafe { CoreActivity::from_raw(result) })
+    }
+
+    /// The root activities of `activity`, or of the whole workflow if `activity` is empty
+    pub fn activity_roots<S: BnStrCompatible>(&self, activity: S) -> Array<BnString> {
+        let activity = activity.into_bytes_with_nul();
+        let mut count = 0;
+        let result = unsafe {
+            BNWorkflowGetActivityRoots(
+                self.handle,
+                activity.as_ref().as_ptr() as *const c_char,
+                &mut count,
+            )
+        };
+        assert!(!result.is_null());
+        unsafe { Array::new(result as *mut *mut c_char, count, ()) }
+    }
+
+    /// The subactivities of `activity`, only including direct children if `immediate`
+    pub fn subactivities<S: BnStrCompatible>(
+        &self,
+        activity: S,
+        immediate: bool,
+    ) -> Array<BnString> {
+        let activity = activity.into_bytes_with_nul();
+        let mut count = 0;
+        let result = unsafe {
+            BNWorkflowGetSubactivities(
+                self.handle,
+                activity.as_ref().as_ptr() as *const c_char,
+                immediate,
+                &mut count,
+            )
+        };
+        assert!(!result.is_null());
+        unsafe { Array::new(result as *mut *mut c_char, count, ()) }
+    }
+
+    /// Replace the children of `activity` with `activities`
+    pub fn assign_subactivities<S, I>(&self, activity: S, activities: I) -> bool
+    where
+        S: BnStrCompatible,
+        I: IntoIterator,
+        I::Item: BnStrCompatible,
+    {
+        let activity = activity.into_bytes_with_nul();
+        let activities_raw: Vec<_> = activities
+            .into_iter()
+            .map(|a| a.into_bytes_with_nul())
+            .collect();
+        let mut activities_ptr: Vec<*const c_char> = activities_raw
+            .iter()
+            .map(|a| a.as_ref().as_ptr() as *const c_char)
+            .collect();
+        unsafe {
+            BNWorkflowAssignSubactivities(
+                self.handle,
+                activity.as_ref().as_ptr() as *const c_char,
+                activities_ptr.as_mut_ptr(),
+                activities_ptr.len(),
+            )
+        }
+    }
+
+    /// Remove all activities from this workflow
+    pub fn clear(&self) -> bool {
+        unsafe { BNWorkflowClear(self.handle) }
+    }
+
+    /// Insert `activities` in the pipeline right before `activity`
+    pub fn insert<S, I>(&self, activity: S, activities: I) -> bool
+    where
+        S: BnStrCompatible,
+        I: IntoIterator,
+        I::Item: BnStrCompatible,
+    {
+        let activity = activity.into_bytes_with_nul();
+        let activities_raw: Vec<_> = activities
+            .into_iter()
+            .map(|a| a.into_bytes_with_nul())
+            .collect();
+        let mut activities_ptr: Vec<*const c_char> = activities_raw
+            .iter()
+            .map(|a| a.as_ref().as_ptr() as *const c_char)
+            .collect();
+        unsafe {
+            BNWorkflowInsert(
+                self.handle,
+                activity.as_ref().as_ptr() as *const c_char,
+                activities_ptr.as_mut_ptr(),
+                activities_ptr.len(),
+            )
+        }
+    }
+
+    /// Remove `activity` from the pipeline
+    pub fn remove<S: BnStrCompatible>(&self, activity: S) -> bool {
+        let activity = activity.into_bytes_with_nul();
+        unsafe { BNWorkflowRemove(self.handle, activity.as_ref().as_ptr() as *const c_char) }
+    }
+
+    /// Replace `activity` with `new_activity` in the pipeline
+    pub fn replace<S: BnStrCompatible, N: BnStrCompatible>(
+        &self,
+        activity: S,
+        new_activity: N,
+    ) -> bool {
+        let activity = activity.into_bytes_with_nul();
+        let new_activity = new_activity.into_bytes_with_nul();
+        unsafe {
+            BNWorkflowReplace(
+                self.handle,
+                activity.as_ref().as_ptr() as *const c_char,
+                new_activity.as_ref().as_ptr() as *const c_char,
+            )
+        }
+    }
+
+    /// Generate a [FlowGraph] of the workflow starting at `activity`, or of the whole
+    /// workflow if `activity` is empty
+    pub fn graph<S: BnStrCompatible>(
+        &self,
+        activity: S,
+        sequential: bool,
+    ) -> Option<Ref<FlowGraph>> {
+        let activity = activity.into_bytes_with_nul();
+        let graph = unsafe {
+            BNWorkflowGetGraph(
+                self.handle,
+                activity.as_ref().as_ptr() as *const c_char,
+                sequential,
+            )
+        };
+        (!graph.is_null()).then(|| unsafe { Ref::new(FlowGraph::from_raw(graph)) })
+    }
+
+    /// Show the report named `name` (eg: `topology`) for this workflow
+    pub fn show_report<S: BnStrCompatible>(&self, name: S) {
+        let name = name.into_bytes_with_nul();
+        unsafe { BNWorkflowShowReport(self.handle, name.as_ref().as_ptr() as *const c_char) }
+    }
+}
+
+impl ToOwned for Workflow {
+    type Owned = Ref<Self>;
+
+    fn to_owned(&self) -> Self::Owned {
+        unsafe { RefCountable::inc_ref(self) }
+    }
+}
+
+unsafe impl RefCountable for Workflow {
+    unsafe fn inc_ref(handle: &Self) -> Ref<Self> {
+        Ref::new(Self {
+            handle: BNNewWorkflowReference(handle.handle),
+        })
+    }
+
+    unsafe fn dec_ref(handle: &Self) {
+        BNFreeWorkflow(handle.handle);
+    }
+}
+
+impl CoreArrayProvider for Workflow {
+    type Raw = *mut BNWorkflow;
+    type Context = ();
+    type Wrapped<'a> = Guard<'a, Workflow>;
+}
+
+unsafe impl CoreArrayProviderInner for Workflow {
+    unsafe fn free(raw: *mut Self::Raw, count: usize, _context: &Self::Context) {
+        BNFreeWorkflowList(raw, count)
+    }
+
+    unsafe fn wrap_raw<'a>(raw: &'a Self::Raw, context: &'a Self::Context) -> Self::Wrapped<'a> {
+        Guard::new(Workflow { handle: *raw }, context)
+    }
+}
+
+impl std::fmt::Debug for Workflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<workflow '{}'>", self.name())
+    }
+}